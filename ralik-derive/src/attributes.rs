@@ -0,0 +1,120 @@
+use syn::{Attribute, Error, Ident, Lit, Meta, NestedMeta, Result};
+
+/// The options that can be given to `#[derive(RalikType)]` on the type itself via `#[ralik(...)]`.
+#[derive(Default)]
+pub(crate) struct ContainerOptions {
+	/// Whether to include the member functions from `RalikMethods`, enabled by `#[ralik(methods)]`.
+	pub methods: bool,
+	/// The behaviour to derive for the ralik type, given by `#[ralik(derive(...))]`.
+	pub derives: Vec<Ident>,
+}
+
+impl ContainerOptions {
+	pub fn from_attributes(attributes: &[Attribute]) -> Result<Self> {
+		let mut options = ContainerOptions::default();
+		for nested in ralik_meta(attributes)? {
+			match nested {
+				NestedMeta::Meta(Meta::Path(path)) if path.is_ident("methods") => options.methods = true,
+				NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("derive") => {
+					for nested in list.nested {
+						match nested {
+							NestedMeta::Meta(Meta::Path(path)) if path.get_ident().is_some() => {
+								options.derives.push(path.get_ident().unwrap().clone())
+							}
+							nested => {
+								return Err(Error::new_spanned(
+									nested,
+									"expected the name of a `ralik::types::Derive`",
+								))
+							}
+						}
+					}
+				}
+				nested => return Err(Error::new_spanned(nested, "unknown ralik option")),
+			}
+		}
+		Ok(options)
+	}
+}
+
+/// Checks whether `attributes` contain `#[ralik(method)]`.
+pub(crate) fn is_method(attributes: &[Attribute]) -> Result<bool> {
+	let mut result = false;
+	for nested in ralik_meta(attributes)? {
+		match nested {
+			NestedMeta::Meta(Meta::Path(path)) if path.is_ident("method") => result = true,
+			nested => return Err(Error::new_spanned(nested, "unknown ralik option")),
+		}
+	}
+	Ok(result)
+}
+
+pub(crate) fn is_ralik_attribute(attribute: &Attribute) -> bool {
+	attribute.path.is_ident("ralik")
+}
+
+/// Serde attributes that change the shape in which serde serializes an item, which the derived ralik types cannot mirror.
+const UNSUPPORTED_SERDE_ATTRIBUTES: &[&str] = &[
+	"content",
+	"default",
+	"flatten",
+	"rename_all",
+	"skip",
+	"skip_deserializing",
+	"skip_serializing",
+	"skip_serializing_if",
+	"tag",
+	"transparent",
+	"untagged",
+];
+
+/// Returns the name that serde uses for an item, i.e., the value of `#[serde(rename = "...")]` if it is present. The
+/// derived ralik types use the same names, so that they can be created via `Value::from_serde`. Serde attributes that
+/// change the shape of the serialized item are rejected.
+pub(crate) fn serde_name(attributes: &[Attribute], ident: &Ident) -> Result<String> {
+	let mut name = ident.to_string();
+	for attribute in attributes.iter().filter(|attribute| attribute.path.is_ident("serde")) {
+		if let Meta::List(list) = attribute.parse_meta()? {
+			for nested in list.nested {
+				let meta = match nested {
+					NestedMeta::Meta(meta) => meta,
+					NestedMeta::Lit(_) => continue,
+				};
+				if let Some(unsupported) = UNSUPPORTED_SERDE_ATTRIBUTES
+					.iter()
+					.find(|unsupported| meta.path().is_ident(unsupported))
+				{
+					return Err(Error::new_spanned(
+						meta,
+						format!("`#[serde({})]` is not supported by `#[derive(RalikType)]`", unsupported),
+					));
+				}
+				if meta.path().is_ident("rename") {
+					match meta {
+						Meta::NameValue(syn::MetaNameValue {
+							lit: Lit::Str(value), ..
+						}) => name = value.value(),
+						meta => {
+							return Err(Error::new_spanned(
+								meta,
+								"only `#[serde(rename = \"...\")]` is supported by `#[derive(RalikType)]`",
+							))
+						}
+					}
+				}
+			}
+		}
+	}
+	Ok(name.trim_start_matches("r#").to_owned())
+}
+
+fn ralik_meta(attributes: &[Attribute]) -> Result<Vec<NestedMeta>> {
+	let mut result = Vec::new();
+	for attribute in attributes.iter().filter(|attribute| is_ralik_attribute(attribute)) {
+		match attribute.parse_meta()? {
+			Meta::List(list) => result.extend(list.nested),
+			meta => return Err(Error::new_spanned(meta, "expected `#[ralik(...)]`")),
+		}
+	}
+	Ok(result)
+}
@@ -0,0 +1,112 @@
+#![forbid(unsafe_code)]
+// doc tests are indented with tabs like the rest of the code
+#![allow(clippy::tabs_in_doc_comments)]
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, ItemImpl};
+
+mod attributes;
+mod methods;
+mod ralik_type;
+
+/**
+Derives `ralik::types::RalikType`, `ralik::FromValue` and `ralik::IntoValue` for a struct or enum.
+
+The generated ralik type mirrors the shape in which serde serializes the Rust type, including the names given by
+`#[serde(rename = "...")]`, so that values can be created via `Value::from_serde`. Registering the type also registers
+the types of all fields. The following options can be given via `#[ralik(...)]`:
+- `derive(...)`: derives the given `ralik::types::Derive`s for the ralik type.
+- `methods`: adds the member functions of an `impl` block annotated with `#[ralik::methods]`.
+
+```rust
+use ralik::types::RalikType;
+use ralik::{eval_str, Context, FromValue, IntoValue, Value};
+use serde::Serialize;
+
+#[derive(ralik::RalikType, Serialize, Debug, PartialEq)]
+#[ralik(derive(PartialEq, Debug), methods)]
+struct Rectangle { width: u32, height: u32, #[serde(rename = "label")] name: Option<String> }
+
+#[ralik::methods]
+impl Rectangle {
+	#[ralik(method)]
+	fn area(&self) -> u32 { self.width * self.height }
+
+	#[ralik(method)]
+	fn scale(&self, factor: u32) -> Rectangle { Rectangle { width: self.width * factor, height: self.height * factor, name: None } }
+}
+
+#[derive(ralik::RalikType, Serialize)]
+enum Shape { Empty, Square(u32), Rectangle { width: u32, height: u32 } }
+
+let context = Context::new();
+Rectangle::register(&context).unwrap();
+Shape::register(&context).unwrap();
+
+let rectangle = Rectangle { width: 2, height: 3, name: Some("r".into()) };
+context.insert_variable("$", Value::from_serde(&context, &rectangle, "Rectangle").unwrap());
+assert_eq!(eval_str("$.area()", &context).unwrap().as_u32(), Some(6));
+assert_eq!(eval_str("$.label.unwrap()", &context).unwrap().as_string(), Some("r"));
+
+let scaled = eval_str("$.scale(2)", &context).unwrap();
+assert_eq!(Rectangle::from_value(&scaled).unwrap(), Rectangle { width: 4, height: 6, name: None });
+
+for shape in [Shape::Empty, Shape::Square(2), Shape::Rectangle { width: 2, height: 3 }] {
+	let value = Value::from_serde(&context, &shape, "Shape").unwrap();
+	assert_eq!(value, shape.into_value(&context).unwrap());
+}
+```
+
+Types that refer to themselves are registered together with the containers of their fields. Registering fails if the
+name of the type is already in use by something that is not a type:
+```rust
+use ralik::types::RalikType;
+use ralik::{eval_str, Context, Value};
+
+#[derive(ralik::RalikType, serde::Serialize)]
+struct Node { value: u32, children: Vec<Node>, parent: Option<(u32, Vec<Node>)> }
+
+let context = Context::new();
+Node::register(&context).unwrap();
+let value = eval_str(
+	r#"{
+		let leaf = Node { value: 2, children: Vec::<Node>::new(), parent: Option::<(Integer, [Node])>::None };
+		Node { value: 1, children: [leaf], parent: Option::<(Integer, [Node])>::None }.children[0].value
+	}"#,
+	&context,
+)
+.unwrap();
+assert_eq!(value.as_u32(), Some(2));
+
+let context = Context::new();
+context.insert_variable("Node", Value::new_integer(&context, 1).unwrap());
+assert!(Node::register(&context).is_err());
+```
+
+Serde attributes that change the shape of the serialized value, such as `rename_all`, `skip` or `flatten`, cannot be
+mirrored and are rejected:
+```compile_fail
+#[derive(ralik::RalikType, serde::Serialize)]
+struct Point { x: i64, #[serde(skip)] y: i64 }
+```
+*/
+#[proc_macro_derive(RalikType, attributes(ralik))]
+pub fn derive_ralik_type(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	ralik_type::derive(input)
+		.unwrap_or_else(|error| error.to_compile_error())
+		.into()
+}
+
+/**
+Implements `ralik::types::RalikMethods` for the type of an `impl` block, exposing all functions that are marked with
+`#[ralik(method)]` as member functions. The functions must take `self` or `&self`, and their arguments and results are
+converted as for `Context::register_fn`.
+*/
+#[proc_macro_attribute]
+pub fn methods(_attribute: TokenStream, input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as ItemImpl);
+	methods::methods(input)
+		.unwrap_or_else(|error| error.to_compile_error())
+		.into()
+}
@@ -0,0 +1,79 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Error, FnArg, ImplItem, ItemImpl, Result};
+
+use super::attributes::{is_method, is_ralik_attribute};
+
+pub(crate) fn methods(mut input: ItemImpl) -> Result<TokenStream> {
+	if !input.generics.params.is_empty() {
+		return Err(Error::new_spanned(
+			&input.generics,
+			"`ralik::methods` cannot be used on generic impl blocks",
+		));
+	}
+
+	let mut methods = Vec::new();
+	for item in &mut input.items {
+		let method = match item {
+			ImplItem::Method(method) => method,
+			_ => continue,
+		};
+		let exposed = is_method(&method.attrs)?;
+		method.attrs.retain(|attribute| !is_ralik_attribute(attribute));
+		if !exposed {
+			continue;
+		}
+
+		let signature = &method.sig;
+		let ident = &signature.ident;
+		let this = match signature.receiver() {
+			Some(FnArg::Receiver(receiver)) if receiver.mutability.is_some() && receiver.reference.is_some() => {
+				return Err(Error::new_spanned(
+					receiver,
+					"ralik member functions cannot take `&mut self`",
+				))
+			}
+			Some(FnArg::Receiver(receiver)) if receiver.reference.is_some() => quote! { &this },
+			Some(FnArg::Receiver(_receiver)) => quote! { this },
+			_ => {
+				return Err(Error::new_spanned(
+					signature,
+					"ralik member functions must take `self` or `&self`",
+				))
+			}
+		};
+
+		let mut parameters = Vec::new();
+		let mut arguments = Vec::new();
+		for (index, input) in signature.inputs.iter().skip(1).enumerate() {
+			let r#type = match input {
+				FnArg::Typed(pattern) => &pattern.ty,
+				FnArg::Receiver(receiver) => return Err(Error::new_spanned(receiver, "unexpected receiver")),
+			};
+			let argument = format_ident!("argument_{}", index);
+			parameters.push(quote! { #argument: #r#type });
+			arguments.push(argument);
+		}
+
+		let name = ident.to_string();
+		methods.push(quote! {
+			(
+				#name,
+				::ralik::types::NativeMemberFunction::from_typed(|this: Self, #(#parameters),*| {
+					Self::#ident(#this, #(#arguments),*)
+				}),
+			)
+		});
+	}
+
+	let self_type = &input.self_ty;
+	Ok(quote! {
+		#input
+
+		impl ::ralik::types::RalikMethods for #self_type {
+			fn methods() -> ::std::vec::Vec<(&'static str, ::ralik::types::NativeMemberFunction)> {
+				vec![#(#methods),*]
+			}
+		}
+	})
+}
@@ -0,0 +1,326 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{Data, DeriveInput, Error, Fields, Ident, Result, Type};
+
+use super::attributes::{serde_name, ContainerOptions};
+
+/// A field of a struct or enum variant, named by its serde name if the fields are named.
+struct Field<'a> {
+	name: Option<String>,
+	ident: Ident,
+	r#type: &'a Type,
+}
+
+/// The shape of a struct or enum variant.
+enum Shape<'a> {
+	Unit,
+	Tuple(Vec<Field<'a>>),
+	Struct(Vec<Field<'a>>),
+}
+
+impl<'a> Shape<'a> {
+	fn new(fields: &'a Fields) -> Result<Self> {
+		Ok(match fields {
+			Fields::Unit => Shape::Unit,
+			Fields::Unnamed(fields) => Shape::Tuple(
+				fields
+					.unnamed
+					.iter()
+					.enumerate()
+					.map(|(index, field)| Field {
+						name: None,
+						ident: format_ident!("field_{}", index),
+						r#type: &field.ty,
+					})
+					.collect(),
+			),
+			Fields::Named(fields) => {
+				let mut result = Vec::new();
+				for field in &fields.named {
+					let ident = field.ident.as_ref().unwrap();
+					result.push(Field {
+						name: Some(serde_name(&field.attrs, ident)?),
+						ident: format_ident!("field_{}", ident.unraw()),
+						r#type: &field.ty,
+					});
+				}
+				Shape::Struct(result)
+			}
+		})
+	}
+
+	fn fields(&self) -> &[Field<'a>] {
+		match self {
+			Shape::Unit => &[],
+			Shape::Tuple(fields) | Shape::Struct(fields) => fields,
+		}
+	}
+
+	/// The pattern that binds all fields of a value of this shape to their `Field::ident`.
+	fn pattern(&self, fields: &Fields) -> TokenStream {
+		match (self, fields) {
+			(Shape::Tuple(shape_fields), _) => {
+				let idents = shape_fields.iter().map(|field| &field.ident);
+				quote! { (#(#idents),*) }
+			}
+			(Shape::Struct(shape_fields), Fields::Named(fields)) => {
+				let members = fields.named.iter().map(|field| field.ident.as_ref().unwrap());
+				let idents = shape_fields.iter().map(|field| &field.ident);
+				quote! { { #(#members: #idents),* } }
+			}
+			_ => quote! {},
+		}
+	}
+
+	/// The expression that converts a ralik value of this shape, named `value`, into the Rust value `constructor`.
+	fn construct(&self, fields: &Fields, constructor: TokenStream, name: &str) -> TokenStream {
+		let conversions = self.fields().iter().enumerate().map(|(index, field)| {
+			let r#type = field.r#type;
+			let element = match &field.name {
+				Some(name) => quote! { value.field(#name) },
+				None => quote! { value.tuple_field(#index) },
+			};
+			quote! {
+				<#r#type as ::ralik::FromValue>::from_value(
+					#element.ok_or_else(|| ::ralik::error::ConversionError::type_mismatch(value, #name))?,
+				)?
+			}
+		});
+		match (self, fields) {
+			(Shape::Unit, _) => constructor,
+			(Shape::Tuple(_), _) => quote! { #constructor(#(#conversions),*) },
+			(Shape::Struct(_), Fields::Named(fields)) => {
+				let members = fields.named.iter().map(|field| field.ident.as_ref().unwrap());
+				quote! { #constructor { #(#members: #conversions),* } }
+			}
+			_ => unreachable!(),
+		}
+	}
+
+	/// The values of all fields bound by `Shape::pattern`, converted to ralik values.
+	fn field_values(&self) -> Vec<TokenStream> {
+		self
+			.fields()
+			.iter()
+			.map(|field| {
+				let ident = &field.ident;
+				let value = quote! { ::ralik::IntoValue::into_value(#ident, context)? };
+				match &field.name {
+					Some(name) => quote! { (#name, #value) },
+					None => value,
+				}
+			})
+			.collect()
+	}
+
+	fn field_type_names(&self) -> Vec<TokenStream> {
+		self
+			.fields()
+			.iter()
+			.map(|field| {
+				let r#type = field.r#type;
+				quote! { <#r#type as ::ralik::types::RalikType>::type_name() }
+			})
+			.collect()
+	}
+}
+
+pub(crate) fn derive(input: DeriveInput) -> Result<TokenStream> {
+	if !input.generics.params.is_empty() {
+		return Err(Error::new_spanned(
+			&input.generics,
+			"`RalikType` cannot be derived for generic types",
+		));
+	}
+
+	let ident = &input.ident;
+	let name = serde_name(&input.attrs, ident)?;
+	let options = ContainerOptions::from_attributes(&input.attrs)?;
+
+	let mut field_types = Vec::new();
+	let (type_builder, from_value, into_value) = match &input.data {
+		Data::Struct(data) => {
+			let shape = Shape::new(&data.fields)?;
+			field_types.extend(shape.fields().iter().map(|field| field.r#type));
+			let pattern = shape.pattern(&data.fields);
+			let values = shape.field_values();
+			let field_type_names = shape.field_type_names();
+			match &shape {
+				Shape::Unit => (
+					quote! { ::ralik::types::new_unit_struct_type(#name) },
+					shape.construct(&data.fields, quote! { Self }, &name),
+					quote! { ::ralik::Value::new_unit_struct(context, #name)? },
+				),
+				Shape::Tuple(_) => (
+					quote! { ::ralik::types::new_tuple_struct_type(#name, vec![#(#field_type_names),*]) },
+					shape.construct(&data.fields, quote! { Self }, &name),
+					quote! {{
+						let Self #pattern = self;
+						let values: ::std::vec::Vec<::ralik::Value> = vec![#(#values),*];
+						::ralik::Value::new_tuple_struct(context, #name, values)?
+					}},
+				),
+				Shape::Struct(fields) => {
+					let field_names = fields.iter().map(|field| field.name.as_ref().unwrap());
+					(
+						quote! {
+							::ralik::types::new_struct_type(#name, vec![#((#field_names, #field_type_names)),*].into_iter())
+						},
+						shape.construct(&data.fields, quote! { Self }, &name),
+						quote! {{
+							let Self #pattern = self;
+							let fields: ::std::vec::Vec<(&str, ::ralik::Value)> = vec![#(#values),*];
+							::ralik::Value::new_struct(context, #name, fields.into_iter())?
+						}},
+					)
+				}
+			}
+		}
+		Data::Enum(data) => {
+			let mut variant_builders = Vec::new();
+			let mut from_value_arms = Vec::new();
+			let mut into_value_arms = Vec::new();
+			for (index, variant) in data.variants.iter().enumerate() {
+				let variant_ident = &variant.ident;
+				let variant_name = serde_name(&variant.attrs, variant_ident)?;
+				let shape = Shape::new(&variant.fields)?;
+				field_types.extend(shape.fields().iter().map(|field| field.r#type));
+				let pattern = shape.pattern(&variant.fields);
+				let values = shape.field_values();
+				let field_type_names = shape.field_type_names();
+
+				let conversion = shape.construct(&variant.fields, quote! { Self::#variant_ident }, &name);
+				from_value_arms.push(quote! { Some(#index) => #conversion, });
+
+				match &shape {
+					Shape::Unit => {
+						variant_builders.push(quote! { ::ralik::types::VariantBuilder::Unit(#variant_name.into()) });
+						into_value_arms.push(quote! {
+							Self::#variant_ident => ::ralik::Value::new_enum_unit_variant(context, #name, #variant_name)?,
+						});
+					}
+					Shape::Tuple(_) => {
+						variant_builders.push(quote! {
+							::ralik::types::VariantBuilder::Tuple(#variant_name.into(), vec![#(#field_type_names),*])
+						});
+						into_value_arms.push(quote! {
+							Self::#variant_ident #pattern => {
+								let values: ::std::vec::Vec<::ralik::Value> = vec![#(#values),*];
+								::ralik::Value::new_enum_tuple_variant(context, #name, #variant_name, values)?
+							}
+						});
+					}
+					Shape::Struct(fields) => {
+						let field_names = fields.iter().map(|field| field.name.as_ref().unwrap());
+						let field_indices = 0..fields.len();
+						variant_builders.push(quote! {
+							::ralik::types::VariantBuilder::Struct(
+								#variant_name.into(),
+								vec![#((#field_names.to_owned(), #field_indices)),*].into_iter().collect(),
+								vec![#(#field_type_names),*],
+							)
+						});
+						into_value_arms.push(quote! {
+							Self::#variant_ident #pattern => {
+								let fields: ::std::vec::Vec<(&str, ::ralik::Value)> = vec![#(#values),*];
+								::ralik::Value::new_enum_struct_variant(context, #name, #variant_name, fields.into_iter())?
+							}
+						});
+					}
+				}
+			}
+			(
+				quote! { ::ralik::types::new_enum_type(#name, vec![#(#variant_builders),*]) },
+				quote! {
+					match value.as_variant_id() {
+						#(#from_value_arms)*
+						_ => return Err(::ralik::error::ConversionError::type_mismatch(value, #name)),
+					}
+				},
+				quote! {
+					match self {
+						#(#into_value_arms)*
+					}
+				},
+			)
+		}
+		Data::Union(data) => {
+			return Err(Error::new_spanned(
+				data.union_token,
+				"`RalikType` cannot be derived for unions",
+			))
+		}
+	};
+
+	let methods = if options.methods {
+		quote! {
+			type_builder.functions.extend(
+				<Self as ::ralik::types::RalikMethods>::methods()
+					.into_iter()
+					.map(|(name, function)| (name.to_owned(), function)),
+			);
+		}
+	} else {
+		quote! {}
+	};
+	let derives = &options.derives;
+
+	Ok(quote! {
+		impl ::ralik::types::RalikType for #ident {
+			fn type_name() -> ::std::string::String {
+				#name.into()
+			}
+
+			fn register(context: &::ralik::Context) -> ::std::result::Result<::ralik::TypeHandle, ::ralik::error::RuntimeError> {
+				if let Some(r#type) = context.get_type(#name) {
+					return Ok(r#type);
+				}
+				let mut type_builders = ::std::vec::Vec::new();
+				<Self as ::ralik::types::RalikType>::collect_type_builders(context, &mut type_builders)?;
+				context.try_register_types(type_builders)?;
+				<Self as ::ralik::types::RalikType>::register(context)
+			}
+
+			fn collect_type_builders(
+				context: &::ralik::Context,
+				type_builders: &mut ::std::vec::Vec<::ralik::types::TypeBuilder>,
+			) -> ::std::result::Result<(), ::ralik::error::RuntimeError> {
+				if context.get_type(#name).is_some() || type_builders.iter().any(|type_builder| type_builder.name == #name) {
+					return Ok(());
+				}
+				#[allow(unused_mut)]
+				let mut type_builder = #type_builder;
+				#methods
+				// the type is added before its fields, so that fields of the same type find it
+				type_builders.push(type_builder.derive(vec![#(::ralik::types::Derive::#derives),*]));
+				#(<#field_types as ::ralik::types::RalikType>::collect_type_builders(context, type_builders)?;)*
+				Ok(())
+			}
+		}
+
+		impl ::ralik::FromValue for #ident {
+			fn type_name() -> ::std::string::String {
+				#name.into()
+			}
+
+			fn from_value(value: &::ralik::Value) -> ::std::result::Result<Self, ::ralik::error::ConversionError> {
+				if *value.get_type().name() != *#name {
+					return Err(::ralik::error::ConversionError::type_mismatch(value, #name));
+				}
+				Ok(#from_value)
+			}
+		}
+
+		impl ::ralik::IntoValue for #ident {
+			fn value_type(context: &::ralik::Context) -> ::std::result::Result<::ralik::TypeHandle, ::ralik::error::RuntimeError> {
+				<Self as ::ralik::types::RalikType>::register(context)
+			}
+
+			fn into_value(self, context: &::ralik::Context) -> ::std::result::Result<::ralik::Value, ::ralik::error::RuntimeError> {
+				<Self as ::ralik::types::RalikType>::register(context)?;
+				Ok(#into_value)
+			}
+		}
+	})
+}
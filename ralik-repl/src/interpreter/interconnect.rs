@@ -1,4 +1,6 @@
-use anyhow::anyhow;
+use anyhow::{anyhow, Context as _};
+use ralik::error::RuntimeError;
+use ralik::{Context, PrettyOptions, TypeHandle, Value};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -10,46 +12,74 @@ impl Interpreter {
 		Self
 	}
 
-	pub fn register_type(context: &ralik::Context) {
+	/// Registers the type of `$`, whose `dump` prints values with the same `options` as the results.
+	pub fn register_type(context: &ralik::Context, options: PrettyOptions) {
 		let mut interpreter_type = ralik::types::new_unit_struct_type("$Interpreter");
 
-		interpreter_type
-			.functions
-			.insert("dump".into(), |context, this_type, args| {
-				let _this = args
-					.get(0)
-					.filter(|value| value.has_type(this_type))
-					.ok_or_else(|| anyhow!("Interpreter member functions should only be called on interpreter object"))?;
-
-				if args.len() != 2 {
-					return Err(anyhow!("`$Interpreter::exit` takes 2 arguments ({} provided)", args.len()).into());
-				}
-
-				println!("{:+#?}", args[1]);
-
-				Ok(ralik::Value::new_unit(context)?)
-			});
-
-		interpreter_type
-			.functions
-			.insert("exit".into(), |_context, this_type, args| {
-				let _this = args
-					.get(0)
-					.filter(|value| value.has_type(this_type))
-					.ok_or_else(|| anyhow!("Interpreter member functions should only be called on interpreter object"))?;
-
-				match args.len() {
-					0 | 1 => {
-						std::process::exit(0);
-					}
-					2 => args[1]
-						.as_i32()
-						.map(std::process::exit)
-						.ok_or_else(|| anyhow!("Argument to `$Interpreter::exit` must be a valid `i32` if it exists."))?,
-					n => Err(anyhow!("`$Interpreter::exit` takes 1 or 2 arguments ({} provided)", n).into()),
-				}
-			});
+		let dump =
+			move |context: &Context, this_type: &TypeHandle, args: &[Value]| dump(context, this_type, args, &options);
+		interpreter_type.functions.insert("dump".into(), dump.into());
+		interpreter_type.functions.insert("exit".into(), exit.into());
+		interpreter_type.functions.insert("load_json".into(), load_json.into());
 
 		context.register_types(vec![interpreter_type]);
 	}
 }
+
+fn dump(
+	context: &Context,
+	this_type: &TypeHandle,
+	args: &[Value],
+	options: &PrettyOptions,
+) -> Result<Value, RuntimeError> {
+	let _this = args
+		.get(0)
+		.filter(|value| value.has_type(this_type))
+		.ok_or_else(|| anyhow!("Interpreter member functions should only be called on interpreter object"))?;
+
+	if args.len() != 2 {
+		return Err(anyhow!("`$Interpreter::exit` takes 2 arguments ({} provided)", args.len()).into());
+	}
+
+	println!("{}", args[1].pretty(options));
+
+	Ok(Value::new_unit(context)?)
+}
+
+fn exit(_context: &Context, this_type: &TypeHandle, args: &[Value]) -> Result<Value, RuntimeError> {
+	let _this = args
+		.get(0)
+		.filter(|value| value.has_type(this_type))
+		.ok_or_else(|| anyhow!("Interpreter member functions should only be called on interpreter object"))?;
+
+	match args.len() {
+		0 | 1 => {
+			std::process::exit(0);
+		}
+		2 => args[1]
+			.as_i32()
+			.map(std::process::exit)
+			.ok_or_else(|| anyhow!("Argument to `$Interpreter::exit` must be a valid `i32` if it exists."))?,
+		n => Err(anyhow!("`$Interpreter::exit` takes 1 or 2 arguments ({} provided)", n).into()),
+	}
+}
+
+/// Loads a JSON file, inferring the types of its contents.
+fn load_json(context: &Context, this_type: &TypeHandle, args: &[Value]) -> Result<Value, RuntimeError> {
+	let _this = args
+		.first()
+		.filter(|value| value.has_type(this_type))
+		.ok_or_else(|| anyhow!("Interpreter member functions should only be called on interpreter object"))?;
+
+	if args.len() != 2 {
+		return Err(anyhow!("`$Interpreter::load_json` takes 2 arguments ({} provided)", args.len()).into());
+	}
+	let path = args[1]
+		.as_string()
+		.ok_or_else(|| anyhow!("Argument to `$Interpreter::load_json` must be a string."))?;
+
+	let file = std::fs::File::open(path).with_context(|| format!("Could not open {:?}", path))?;
+	let json: serde_json::Value =
+		serde_json::from_reader(std::io::BufReader::new(file)).with_context(|| format!("Could not parse {:?}", path))?;
+	Ok(Value::from_serde_inferred(context, &json).map_err(anyhow::Error::from)?)
+}
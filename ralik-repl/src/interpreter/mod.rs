@@ -8,11 +8,92 @@ use interconnect::Interpreter;
 pub mod repl;
 pub mod run;
 
-fn create_context(register_interconnect: bool) -> Result<ralik::Context> {
+#[derive(Copy, Clone, Debug)]
+pub enum Format {
+	Human,
+	Json,
+}
+
+impl std::str::FromStr for Format {
+	type Err = anyhow::Error;
+	fn from_str(string: &str) -> Result<Self, Self::Err> {
+		match string {
+			"human" => Ok(Format::Human),
+			"json" => Ok(Format::Json),
+			_ => Err(anyhow!("The given format {:?} is not supported.", string)),
+		}
+	}
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum Color {
+	Auto,
+	Always,
+	Never,
+}
+
+impl std::str::FromStr for Color {
+	type Err = anyhow::Error;
+	fn from_str(string: &str) -> Result<Self, Self::Err> {
+		match string {
+			"auto" => Ok(Color::Auto),
+			"always" => Ok(Color::Always),
+			"never" => Ok(Color::Never),
+			_ => Err(anyhow!("The given color mode {:?} is not supported.", string)),
+		}
+	}
+}
+
+// Controls how results are printed in the human readable format. This is a plain comment, as a doc comment would
+// become the description of the subcommands that flatten these arguments.
+#[derive(structopt::StructOpt, Debug)]
+pub struct PrettyArgs {
+	#[structopt(
+		long = "width",
+		help = "Width that printed results should fit into",
+		default_value = "100"
+	)]
+	width: usize,
+
+	#[structopt(long = "max-depth", help = "Elide values that are nested deeper than this")]
+	max_depth: Option<usize>,
+
+	#[structopt(long = "max-length", help = "Elide array elements beyond this many")]
+	max_length: Option<usize>,
+
+	#[structopt(long = "color", help = "Whether to colorize printed results", default_value = "auto", possible_values = &["auto", "always", "never"])]
+	color: Color,
+}
+
+impl PrettyArgs {
+	fn options(&self) -> ralik::PrettyOptions {
+		ralik::PrettyOptions {
+			width: self.width,
+			max_depth: self.max_depth,
+			max_length: self.max_length,
+			color: match self.color {
+				Color::Auto => atty::is(atty::Stream::Stdout),
+				Color::Always => true,
+				Color::Never => false,
+			},
+		}
+	}
+}
+
+/// Prints the result of evaluating an expression in the requested format.
+fn print_value(value: &ralik::Value, format: Format, pretty: &PrettyArgs) -> Result<(), serde_json::Error> {
+	match format {
+		Format::Human => println!("{}", value.pretty(&pretty.options())),
+		Format::Json => println!("{}", serde_json::to_string_pretty(value)?),
+	}
+	Ok(())
+}
+
+fn create_context(register_interconnect: bool, pretty: &PrettyArgs) -> Result<ralik::Context> {
 	let context = ralik::Context::new();
 
 	if register_interconnect {
-		Interpreter::register_type(&context);
+		Interpreter::register_type(&context, pretty.options());
 
 		context.insert_variable(
 			"$",
@@ -22,14 +103,18 @@ fn create_context(register_interconnect: bool) -> Result<ralik::Context> {
 		);
 	}
 
-	context.insert_function("exit", |_context, args| match args.len() {
+	context.insert_function("exit", exit);
+
+	Ok(context)
+}
+
+fn exit(_context: &ralik::Context, args: &[ralik::Value]) -> Result<ralik::Value, ralik::error::RuntimeError> {
+	match args.len() {
 		0 => std::process::exit(0),
 		1 => args[0]
 			.as_i32()
 			.map(std::process::exit)
 			.ok_or_else(|| anyhow!("Argument to `exit` must be a valid `i32` if it exists."))?,
 		n => Err(anyhow!("`exit` takes 0 or 1 arguments ({} provided)", n).into()),
-	});
-
-	Ok(context)
+	}
 }
@@ -6,7 +6,7 @@ use rustyline::Editor;
 use crate::cli::ReturnCode;
 use crate::util::print_error_chain;
 
-use super::create_context;
+use super::{create_context, print_value, Format, PrettyArgs};
 
 #[derive(structopt::StructOpt, Debug)]
 pub struct Args {
@@ -18,12 +18,18 @@ pub struct Args {
 
 	#[structopt(long = "no-$", help = "Skip registering the interpreter object `$`.")]
 	no_interpreter_value: bool,
+
+	#[structopt(short = "f", long = "format", help = "Format to print results in", default_value = "human", possible_values = &["human", "json"])]
+	format: Format,
+
+	#[structopt(flatten)]
+	pretty: PrettyArgs,
 }
 
 const PROMPT: &str = "> ";
 
 pub fn main(args: Args) -> Result<ReturnCode> {
-	let context = create_context(!args.no_interpreter_value)?;
+	let context = create_context(!args.no_interpreter_value, &args.pretty)?;
 
 	if args.dump_context {
 		println!("{:+#?}", context);
@@ -40,7 +46,9 @@ pub fn main(args: Args) -> Result<ReturnCode> {
 				editor.add_history_entry(line.as_str());
 				match ralik::eval_str(&line, &context) {
 					Ok(expr) => {
-						println!("{}", expr);
+						if let Err(err) = print_value(&expr, args.format, &args.pretty) {
+							print_error_chain(&err);
+						}
 					}
 					Err(err) => {
 						print_error_chain(&err);
@@ -3,7 +3,7 @@ use anyhow::Result;
 use crate::cli::ReturnCode;
 use crate::util::print_error_chain;
 
-use super::create_context;
+use super::{create_context, print_value, Format, PrettyArgs};
 
 #[derive(structopt::StructOpt, Debug)]
 pub struct Args {
@@ -16,22 +16,31 @@ pub struct Args {
 	#[structopt(long = "no-$", help = "Skip registering the interpreter object `$`.")]
 	no_interpreter_value: bool,
 
+	#[structopt(short = "f", long = "format", help = "Format to print results in", default_value = "human", possible_values = &["human", "json"])]
+	format: Format,
+
+	#[structopt(flatten)]
+	pretty: PrettyArgs,
+
 	#[structopt(help = "The command to run.")]
 	command: String,
 }
 
 pub fn main(args: Args) -> Result<ReturnCode> {
-	let context = create_context(!args.no_interpreter_value)?;
+	let context = create_context(!args.no_interpreter_value, &args.pretty)?;
 
 	if args.dump_context {
 		println!("{:+#?}", context);
 	}
 
 	match ralik::eval_str(&args.command, &context) {
-		Ok(expr) => {
-			println!("{}", expr);
-			Ok(ReturnCode::Success)
-		}
+		Ok(expr) => match print_value(&expr, args.format, &args.pretty) {
+			Ok(()) => Ok(ReturnCode::Success),
+			Err(err) => {
+				print_error_chain(&err);
+				Ok(ReturnCode::ScriptError)
+			}
+		},
 		Err(err) => {
 			print_error_chain(&err);
 			Ok(ReturnCode::ScriptError)
@@ -1,27 +1,170 @@
-use std::collections::HashMap;
+use crate::{TypeHandle, Value};
 
-use super::{Context, Function, Macro};
+use super::Context;
 
+/**
+Lists the names of all types, interfaces, functions, macros and variables of the context. The `+` flag additionally
+lists the kind, type parameters, fields, variants, interfaces, member functions and associated functions of each type
+as well as the value of each variable.
+
+```rust
+let context = ralik::Context::new_empty();
+context.register_types(vec![ralik::types::new_integer_type()]);
+context.insert_variable("answer", ralik::Value::new_integer(&context, 42).unwrap());
+assert_eq!(
+	format!("{:?}", context),
+	concat!(
+		r#"Context { types: ["Integer"], "#,
+		r#"interfaces: ["Clone", "Debug", "Default", "Display", "Ord", "PartialEq", "PartialOrd"], "#,
+		r#"functions: [], macros: [], variables: ["answer"] }"#,
+	),
+);
+assert!(format!("{:+?}", context).contains(r#"variables: {"answer": 42}"#));
+```
+*/
 impl std::fmt::Debug for Context {
-	fn fmt(&self, _f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		unimplemented!()
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let types = self.types().collect::<Vec<TypeHandle>>();
+		let interfaces = self.interfaces().collect::<Vec<String>>();
+		let functions = self.functions().map(|(name, _function)| name).collect::<Vec<String>>();
+		let macros = self.macros().collect::<Vec<String>>();
+		let variables = self.variables().collect::<Vec<(String, Value)>>();
+
+		let detailed = f.sign_plus();
+		let mut f = f.debug_struct("Context");
+		if detailed {
+			f.field("types", &TypeListFormatter { types: &types });
+		} else {
+			let type_names = types.iter().map(|r#type| r#type.name()).collect::<Vec<_>>();
+			f.field("types", &NameListFormatter { names: &type_names });
+		}
+		f.field("interfaces", &NameListFormatter { names: &interfaces });
+		f.field("functions", &NameListFormatter { names: &functions });
+		f.field("macros", &NameListFormatter { names: &macros });
+		if detailed {
+			f.field("variables", &VariableListFormatter { variables: &variables });
+		} else {
+			let variable_names = variables.iter().map(|(name, _value)| name).collect::<Vec<_>>();
+			f.field("variables", &NameListFormatter { names: &variable_names });
+		}
+		f.finish()
+	}
+}
+
+struct NameListFormatter<'a, T> {
+	names: &'a [T],
+}
+impl<'a, T: AsRef<str>> std::fmt::Debug for NameListFormatter<'a, T> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_list()
+			.entries(self.names.iter().map(|name| name.as_ref()))
+			.finish()
+	}
+}
+
+struct TypeListFormatter<'a> {
+	types: &'a [TypeHandle],
+}
+impl<'a> std::fmt::Debug for TypeListFormatter<'a> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_map()
+			.entries(
+				self
+					.types
+					.iter()
+					.map(|r#type| (r#type.name(), TypeFormatter { r#type })),
+			)
+			.finish()
+	}
+}
+
+struct TypeFormatter<'a> {
+	r#type: &'a TypeHandle,
+}
+impl<'a> std::fmt::Debug for TypeFormatter<'a> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let type_parameters = self
+			.r#type
+			.type_parameters()
+			.map(|r#type| r#type.name())
+			.collect::<Vec<_>>();
+		let field_names = self.r#type.field_names().collect::<Vec<String>>();
+		let variants = self.r#type.variants().collect::<Vec<String>>();
+		let interfaces = self.r#type.interfaces().collect::<Vec<String>>();
+		let member_functions = self
+			.r#type
+			.member_functions()
+			.map(|(name, _function)| name)
+			.collect::<Vec<String>>();
+		let associated_functions = self
+			.r#type
+			.associated_functions()
+			.map(|(name, _function)| name)
+			.collect::<Vec<String>>();
+
+		let mut f = f.debug_struct("Type");
+		f.field("kind", &self.r#type.kind());
+		if !type_parameters.is_empty() {
+			f.field(
+				"type_parameters",
+				&NameListFormatter {
+					names: &type_parameters,
+				},
+			);
+		}
+		if !field_names.is_empty() {
+			f.field("fields", &NameListFormatter { names: &field_names });
+		}
+		if !variants.is_empty() {
+			f.field("variants", &NameListFormatter { names: &variants });
+		}
+		if !interfaces.is_empty() {
+			f.field("interfaces", &NameListFormatter { names: &interfaces });
+		}
+		f.field(
+			"member_functions",
+			&NameListFormatter {
+				names: &member_functions,
+			},
+		);
+		if !associated_functions.is_empty() {
+			f.field(
+				"associated_functions",
+				&NameListFormatter {
+					names: &associated_functions,
+				},
+			);
+		}
+		f.finish()
 	}
 }
 
-struct FunctionNameListFormatter<'a> {
-	functions: &'a HashMap<String, Function>,
+struct VariableListFormatter<'a> {
+	variables: &'a [(String, Value)],
 }
-impl<'a> std::fmt::Debug for FunctionNameListFormatter<'a> {
+impl<'a> std::fmt::Debug for VariableListFormatter<'a> {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		f.debug_list().entries(self.functions.keys()).finish()
+		f.debug_map()
+			.entries(
+				self
+					.variables
+					.iter()
+					.map(|(name, value)| (name, ValueFormatter { value })),
+			)
+			.finish()
 	}
 }
 
-struct MacroNameListFormatter<'a> {
-	macros: &'a HashMap<String, Macro>,
+/// Formats the value without the `+` flag, which would otherwise dump its internal representation.
+struct ValueFormatter<'a> {
+	value: &'a Value,
 }
-impl<'a> std::fmt::Debug for MacroNameListFormatter<'a> {
+impl<'a> std::fmt::Debug for ValueFormatter<'a> {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		f.debug_list().entries(self.macros.keys()).finish()
+		if f.alternate() {
+			write!(f, "{:#?}", self.value)
+		} else {
+			write!(f, "{:?}", self.value)
+		}
 	}
 }
@@ -1,22 +1,188 @@
 use std::collections::hash_map::Entry;
+use std::sync::Arc;
+
+use crate::error::RuntimeError;
+use crate::{FromArgument, FunctionResult, Value};
 
 use super::{Context, Function, Thing};
 
+/// A free function that is implemented by a closure, which may capture state of the host program.
+pub type FunctionClosure = Arc<dyn Fn(&Context, &[Value]) -> Result<Value, RuntimeError> + Send + Sync>;
+
+/**
+A free function that is implemented by the host program.
+
+Plain `fn`s can be stored and called directly, while closures are shared via an `Arc`, which allows them to capture
+state:
+```rust
+# use ralik::{eval_str, Context, NativeFunction, Value};
+# use std::sync::atomic::{AtomicUsize, Ordering};
+# use std::sync::Arc;
+let context = Context::new();
+context.insert_native_function(
+	"answer",
+	NativeFunction::Function(|context, _arguments| Ok(Value::new_integer(context, 42)?)),
+);
+
+let counter = Arc::new(AtomicUsize::new(0));
+let captured = counter.clone();
+context.insert_function("count", move |context, _arguments| {
+	let count = captured.fetch_add(1, Ordering::Relaxed) + 1;
+	Ok(Value::new_integer(context, count)?)
+});
+
+let value = eval_str("(answer(), count(), count())", &context).unwrap();
+assert_eq!(value.to_string(), "(42, 1, 2)");
+assert_eq!(counter.load(Ordering::Relaxed), 2);
+```
+*/
+#[derive(Clone)]
+pub enum NativeFunction {
+	Function(Function),
+	Closure(FunctionClosure),
+}
+
+impl NativeFunction {
+	pub fn call(&self, context: &Context, arguments: &[Value]) -> Result<Value, RuntimeError> {
+		match self {
+			NativeFunction::Function(function) => function(context, arguments),
+			NativeFunction::Closure(closure) => closure(context, arguments),
+		}
+	}
+}
+
+impl<F> From<F> for NativeFunction
+where
+	F: Fn(&Context, &[Value]) -> Result<Value, RuntimeError> + Send + Sync + 'static,
+{
+	fn from(closure: F) -> Self {
+		NativeFunction::Closure(Arc::new(closure))
+	}
+}
+
+impl From<FunctionClosure> for NativeFunction {
+	fn from(closure: FunctionClosure) -> Self {
+		NativeFunction::Closure(closure)
+	}
+}
+
+impl std::fmt::Debug for NativeFunction {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			NativeFunction::Function(function) => f.debug_tuple("Function").field(function).finish(),
+			NativeFunction::Closure(closure) => f.debug_tuple("Closure").field(&Arc::as_ptr(closure)).finish(),
+		}
+	}
+}
+
+/**
+A Rust function or closure whose arguments and result are converted automatically, see `Context::register_fn`. The
+type parameter `Arguments` is the tuple of argument types, which disambiguates the implementations for each arity.
+*/
+pub trait TypedFunction<Arguments> {
+	fn into_native_function(self) -> NativeFunction;
+}
+
+macro_rules! impl_typed_function {
+	($len:literal: $($argument:ident $value:ident $index:tt),*) => {
+		impl<Function, Return, $($argument),*> TypedFunction<($($argument,)*)> for Function
+		where
+			// the first bound determines the argument types, while the second one allows passing arguments that borrow
+			// from the values that the function is called with
+			Function: Fn($($argument),*) -> Return,
+			Function: for<'a> Fn($($argument::Argument<'a>),*) -> Return + Send + Sync + 'static,
+			Return: FunctionResult,
+			$($argument: FromArgument,)*
+		{
+			fn into_native_function(self) -> NativeFunction {
+				NativeFunction::Closure(Arc::new(move |context, arguments| match arguments {
+					[$($value),*] => self($(
+						$argument::from_argument($value).map_err(|err| err.into_argument_error($index))?
+					),*)
+					.into_result(context),
+					_ => Err(RuntimeError::InvalidNumberOfArguments {
+						actual: arguments.len(),
+						expected: $len,
+					}),
+				}))
+			}
+		}
+	};
+}
+
+impl_typed_function!(0:);
+impl_typed_function!(1: A a 0);
+impl_typed_function!(2: A a 0, B b 1);
+impl_typed_function!(3: A a 0, B b 1, C c 2);
+impl_typed_function!(4: A a 0, B b 1, C c 2, D d 3);
+impl_typed_function!(5: A a 0, B b 1, C c 2, D d 3, E e 4);
+impl_typed_function!(6: A a 0, B b 1, C c 2, D d 3, E e 4, F f 5);
+impl_typed_function!(7: A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6);
+impl_typed_function!(8: A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7);
+
 impl Context {
-	pub fn get_function(&self, key: &str) -> Option<Function> {
+	pub fn get_function(&self, key: &str) -> Option<NativeFunction> {
 		match self.0.names.read().unwrap().get(key) {
 			Some(Thing::Function(value)) => Some(value.clone()),
 			_ => None,
 		}
 	}
 
-	pub fn insert_function(&self, key: impl Into<String>, value: Function) {
+	/// All free functions in this context, ordered by name.
+	pub fn functions(&self) -> impl Iterator<Item = (String, NativeFunction)> {
+		let mut functions = self
+			.0
+			.names
+			.read()
+			.unwrap()
+			.iter()
+			.filter_map(|(name, thing)| match thing {
+				Thing::Function(function) => Some((name.clone(), function.clone())),
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+		functions.sort_unstable_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+		functions.into_iter()
+	}
+
+	/// Inserts the free function `key`, which may be a plain `fn` or a closure that captures state of the host program.
+	pub fn insert_function(
+		&self,
+		key: impl Into<String>,
+		value: impl Fn(&Context, &[Value]) -> Result<Value, RuntimeError> + Send + Sync + 'static,
+	) {
+		self.insert_native_function(key, NativeFunction::Closure(Arc::new(value)));
+	}
+
+	/// Inserts the free function `key` as it is, e.g., a `NativeFunction::Function` that is called without indirection.
+	pub fn insert_native_function(&self, key: impl Into<String>, value: impl Into<NativeFunction>) {
 		let mut names = self.0.names.write().unwrap();
 		match names.entry(key.into()) {
 			Entry::Occupied(entry) => panic!("The name `{}` is defined multiple times", entry.key()),
 			Entry::Vacant(entry) => {
-				entry.insert(Thing::Function(value));
+				entry.insert(Thing::Function(value.into()));
 			}
 		}
 	}
+
+	/// Registers a Rust function or closure as the free function `key`. The number of arguments is checked and the
+	/// arguments are converted via `FromValue`, while the result is converted via `IntoValue`. Functions that can fail
+	/// return a `Result` whose error converts into a `RuntimeError`.
+	///
+	/// ```rust
+	/// # use ralik::{eval_str, Context};
+	/// let context = Context::new();
+	/// context.register_fn("clamp", |x: i64, lo: i64, hi: i64| -> i64 { x.max(lo).min(hi) });
+	/// context.register_fn("words", |s: String| -> Vec<String> { s.split(' ').map(ToOwned::to_owned).collect() });
+	///
+	/// let value = eval_str(r#"(clamp(12, 0, 10), words("a b"))"#, &context).unwrap();
+	/// assert_eq!(value.to_string(), "(10, ['a', 'b'])");
+	///
+	/// let error = eval_str(r#"clamp(12, 0, "10")"#, &context).unwrap_err();
+	/// let message = std::error::Error::source(&error).unwrap().to_string();
+	/// assert_eq!(message, "Argument 2 has type `std::string::String`, but type `Integer` was expected instead");
+	/// ```
+	pub fn register_fn<Arguments>(&self, key: impl Into<String>, function: impl TypedFunction<Arguments>) {
+		self.insert_native_function(key, function.into_native_function());
+	}
 }
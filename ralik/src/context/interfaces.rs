@@ -0,0 +1,88 @@
+use std::collections::hash_map::{Entry, HashMap};
+use std::sync::Arc;
+
+use crate::error::TypeRegistrationError;
+use crate::types::{Interface, NativeMemberFunction, TypeBuilder};
+
+use super::Context;
+
+impl Context {
+	/// Registers an interface that types can declare to implement, see `types::Interface`. Fails if an interface of the
+	/// same name is already registered.
+	pub fn register_interface(&self, interface: Interface) -> Result<(), TypeRegistrationError> {
+		match self.0.interfaces.write().unwrap().entry(interface.name.clone()) {
+			Entry::Occupied(entry) => Err(TypeRegistrationError::NameInUse {
+				name: entry.key().clone(),
+			}),
+			Entry::Vacant(entry) => {
+				entry.insert(Arc::new(interface));
+				Ok(())
+			}
+		}
+	}
+
+	/// The names of all interfaces of this context, ordered by name.
+	pub fn interfaces(&self) -> impl Iterator<Item = String> {
+		let mut interfaces = self.0.interfaces.read().unwrap().keys().cloned().collect::<Vec<_>>();
+		interfaces.sort_unstable();
+		interfaces.into_iter()
+	}
+
+	/// Checks that the type provides all functions that are required by the interfaces it declares, and adds the default
+	/// functions that it does not provide itself.
+	pub(super) fn implement_interfaces(&self, type_builder: &mut TypeBuilder) -> Result<(), TypeRegistrationError> {
+		self.implement_interfaces_for(
+			&type_builder.name,
+			&type_builder.interfaces,
+			&mut type_builder.functions,
+			&type_builder.associated_functions,
+		)
+	}
+
+	/// Checks that the member `functions` of the type `type_name` include all functions that are required by the given
+	/// interfaces, and adds the default functions that are missing.
+	pub(super) fn implement_interfaces_for(
+		&self,
+		type_name: &str,
+		interface_names: &[String],
+		functions: &mut HashMap<String, NativeMemberFunction>,
+		associated_functions: &HashMap<String, NativeMemberFunction>,
+	) -> Result<(), TypeRegistrationError> {
+		let interfaces = self.0.interfaces.read().unwrap();
+		for name in interface_names {
+			let interface = interfaces
+				.get(name)
+				.ok_or_else(|| TypeRegistrationError::MissingInterface {
+					type_name: type_name.into(),
+					interface_name: name.clone(),
+				})?;
+			if let Some(function_name) = interface
+				.required_functions
+				.iter()
+				.find(|function_name| !functions.contains_key(*function_name))
+				.or_else(|| {
+					interface
+						.required_associated_functions
+						.iter()
+						.find(|function_name| !associated_functions.contains_key(*function_name))
+				}) {
+				return Err(TypeRegistrationError::MissingRequiredFunction {
+					type_name: type_name.into(),
+					interface_name: name.clone(),
+					function_name: function_name.clone(),
+				});
+			}
+			for (function_name, function) in &interface.default_functions {
+				functions
+					.entry(function_name.clone())
+					.or_insert_with(|| function.clone());
+			}
+		}
+		Ok(())
+	}
+
+	/// The interface called `name`, if it is registered.
+	pub(crate) fn get_interface(&self, name: &str) -> Option<Arc<Interface>> {
+		self.0.interfaces.read().unwrap().get(name).cloned()
+	}
+}
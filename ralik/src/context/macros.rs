@@ -1,15 +1,188 @@
-use super::{Context, Macro};
+use proc_macro2::TokenStream;
+use syn::parse::Parser;
+
+use std::sync::Arc;
+
+use crate::ast;
+use crate::error::{EvalError, Location, RuntimeError};
+use crate::Value;
+
+use super::{Context, LazyMacro, Macro};
 
 impl Context {
 	pub fn get_macro(&self, key: &str) -> Option<Macro> {
 		self.0.macros.read().unwrap().get(key).cloned()
 	}
 
-	pub fn insert_macro(&self, key: impl Into<String>, value: Macro) -> Option<Macro> {
-		self.0.macros.write().unwrap().insert(key.into(), value)
+	/// The names of all macros in this context, including lazy macros, ordered by name.
+	pub fn macros(&self) -> impl Iterator<Item = String> {
+		let mut macros = self.0.macros.read().unwrap().keys().cloned().collect::<Vec<_>>();
+		macros.extend(self.0.lazy_macros.read().unwrap().keys().cloned());
+		macros.sort_unstable();
+		macros.dedup();
+		macros.into_iter()
+	}
+
+	pub fn insert_macro(
+		&self,
+		key: impl Into<String>,
+		value: impl Fn(&Context, &[Value]) -> Result<Value, RuntimeError> + Send + Sync + 'static,
+	) -> Option<Macro> {
+		let key = key.into();
+		let mut macros = self.0.macros.write().unwrap();
+		self.0.format_macros.write().unwrap().remove(&key);
+		macros.insert(key, Arc::new(value))
+	}
+
+	/**
+	Inserts a macro that takes a format string after `leading_arguments` other arguments, like `write!`. The macro
+	receives the leading arguments followed by the formatted string, which is omitted if the call has no format string.
+
+	```rust
+	# use ralik::{eval_str, Context, Value};
+	let context = Context::new();
+	context.insert_format_macro("shout", 0, |context, arguments| {
+		let message = arguments.get(0).and_then(|message| message.as_string()).unwrap_or_default();
+		Ok(Value::new_string(context, message.to_uppercase())?)
+	});
+	let value = eval_str(r#"shout!("{}, {name}!", "hello", name = "world")"#, &context).unwrap();
+	assert_eq!(value.as_string(), Some("HELLO, WORLD!"));
+
+	// like `panic!`, which is a format macro as well
+	let message = |source| {
+		let error = eval_str(source, &context).unwrap_err();
+		let panic = std::error::Error::source(&error).unwrap();
+		panic.source().unwrap().to_string()
+	};
+	assert_eq!(message(r#"panic!("{} {x}", 1, x = 2)"#), "1 2");
+	assert_eq!(message("panic!()"), "explicit panic");
+	```
+	*/
+	pub fn insert_format_macro(
+		&self,
+		key: impl Into<String>,
+		leading_arguments: usize,
+		value: impl Fn(&Context, &[Value]) -> Result<Value, RuntimeError> + Send + Sync + 'static,
+	) -> Option<Macro> {
+		let key = key.into();
+		let mut macros = self.0.macros.write().unwrap();
+		self
+			.0
+			.format_macros
+			.write()
+			.unwrap()
+			.insert(key.clone(), leading_arguments);
+		macros.insert(key, Arc::new(value))
 	}
 
 	pub fn remove_macro(&self, key: &str) -> Option<(String, Macro)> {
-		self.0.macros.write().unwrap().remove_entry(key)
+		let mut macros = self.0.macros.write().unwrap();
+		self.0.format_macros.write().unwrap().remove(key);
+		macros.remove_entry(key)
+	}
+
+	/// The number of arguments in front of the format string, if `key` was inserted with `insert_format_macro`.
+	pub(crate) fn get_format_string_index(&self, key: &str) -> Option<usize> {
+		self.0.format_macros.read().unwrap().get(key).cloned()
+	}
+
+	pub fn get_lazy_macro(&self, key: &str) -> Option<LazyMacro> {
+		self.0.lazy_macros.read().unwrap().get(key).cloned()
+	}
+
+	/// Inserts a macro that receives its arguments unevaluated. Lazy macros take precedence over macros of the same name
+	/// that were inserted with `insert_macro`.
+	pub fn insert_lazy_macro(
+		&self,
+		key: impl Into<String>,
+		value: impl Fn(&Context, &LazyMacroCall) -> Result<Value, EvalError> + Send + Sync + 'static,
+	) -> Option<LazyMacro> {
+		self.0.lazy_macros.write().unwrap().insert(key.into(), Arc::new(value))
+	}
+
+	pub fn remove_lazy_macro(&self, key: &str) -> Option<(String, LazyMacro)> {
+		self.0.lazy_macros.write().unwrap().remove_entry(key)
+	}
+}
+
+/**
+The call of a lazy macro, which gives access to the unevaluated arguments. They can be evaluated with
+`ralik::eval_expression` as needed.
+
+```rust
+# use ralik::{eval_expression, eval_str, Context};
+let context = Context::new();
+context.insert_lazy_macro("first", |context, call| eval_expression(&call.arguments()?[0], context));
+let value = eval_str("first!(1, does_not_exist())", &context).unwrap();
+assert_eq!(value.as_i32(), Some(1));
+```
+*/
+pub struct LazyMacroCall<'a> {
+	name: &'a str,
+	span: Location,
+	tokens: TokenStream,
+}
+
+impl<'a> LazyMacroCall<'a> {
+	pub(crate) fn new(name: &'a str, span: Location, tokens: &ast::SourceTokens) -> Self {
+		LazyMacroCall {
+			name,
+			span,
+			tokens: tokens.to_token_stream(),
+		}
+	}
+
+	pub fn name(&self) -> &str {
+		self.name
+	}
+
+	pub fn span(&self) -> Location {
+		self.span
+	}
+
+	/// The tokens between the delimiters of the macro call.
+	pub fn tokens(&self) -> &TokenStream {
+		&self.tokens
+	}
+
+	pub fn parse<T: syn::parse::Parse>(&self) -> syn::Result<T> {
+		syn::parse2(self.tokens.clone())
+	}
+
+	/// Parses the arguments as a comma-separated list of expressions.
+	pub fn arguments(&self) -> syn::Result<Vec<ast::Expression>> {
+		Ok(self.parse::<ast::Arguments>()?.arguments)
+	}
+
+	/// Splits the arguments of a comma-separated list of expressions, e.g., to print them as they were written.
+	pub fn argument_tokens(&self) -> syn::Result<Vec<TokenStream>> {
+		Ok(self.parse::<crate::syntax::ArgumentTokens>()?.0)
+	}
+
+	/// Parses the arguments as `leading_arguments` expressions followed by an optional format string and its arguments,
+	/// like those of `write!`.
+	pub fn format_arguments(&self, leading_arguments: usize) -> syn::Result<ast::FormatArguments> {
+		crate::syntax::parse_format_arguments(self.tokens.clone(), self.name, leading_arguments)
+	}
+
+	/// The source text of the first `count` arguments, e.g., to print the condition of `assert!` as it was written.
+	pub(crate) fn leading_argument_sources(&self, count: usize) -> syn::Result<Vec<String>> {
+		let tokens = (|input: syn::parse::ParseStream| crate::syntax::parse_argument_tokens(input, count))
+			.parse2(self.tokens.clone())?;
+		Ok(
+			tokens
+				.into_iter()
+				.map(|tokens| ast::SourceTokens::new(tokens).text().to_owned())
+				.collect(),
+		)
+	}
+
+	/// Wraps an error that occurred while executing the macro, so that it refers to this call.
+	pub fn error(&self, source: impl Into<RuntimeError>) -> EvalError {
+		EvalError::MacroRuntimeError {
+			name: self.name.into(),
+			source: Box::new(source.into()),
+			at: self.span,
+		}
 	}
 }
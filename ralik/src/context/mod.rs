@@ -1,23 +1,28 @@
 use anyhow::anyhow;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::{read_to_string, File};
 use std::io::Read;
 use std::sync::atomic::AtomicIsize;
 use std::sync::{Arc, RwLock};
 
-use crate::error::RuntimeError;
-use crate::types::{GenericTypeBuilder, Type};
+use crate::error::{AssertionFailed, EvalError, RuntimeError};
+use crate::types::{GenericTypeBuilder, Interface, Type};
 use crate::Value;
 
 mod debug;
 mod functions;
+mod interfaces;
+pub use functions::{FunctionClosure, NativeFunction, TypedFunction};
 mod macros;
+pub use macros::LazyMacroCall;
 
 mod types;
 pub use types::TypeHandle;
+pub(crate) use types::{prelude_name, sized_integer_range, OPTION_GENERIC, RESULT_GENERIC};
 
 mod variables;
+use variables::Scope;
 
 /**
 The `Context` stores all types, free functions and global variables.
@@ -37,15 +42,17 @@ The default context can then be extended for the specific use case:
 context.insert_variable("$", Value::new_unit(&context).unwrap());
 ```
 
-When starting with an empty context, the core types are not available by default:
+When starting with an empty context, the core types are not available by default, but the core interfaces are, see
+`types::new_core_interfaces`:
 ```rust
 # use ralik::{Context, Value};
 let context = Context::new_empty();
 Value::new_bool(&context, true).unwrap_err();
+assert!(context.interfaces().any(|name| name == "Ord"));
 ```
 */
 #[derive(Clone)]
-pub struct Context(Arc<ContextImpl>);
+pub struct Context(Arc<ContextImpl>, Option<Arc<Scope>>);
 
 impl PartialEq for Context {
 	fn eq(&self, other: &Self) -> bool {
@@ -55,30 +62,46 @@ impl PartialEq for Context {
 impl Eq for Context {}
 
 struct ContextImpl {
-	arrays: RwLock<Option<GenericTypeCreator>>,
-	tuples: RwLock<Option<GenericTypeCreator>>,
-
 	types: RwLock<Vec<(Type, AtomicIsize)>>,
 	names: RwLock<HashMap<String, Thing>>,
+	interfaces: RwLock<HashMap<String, Arc<Interface>>>,
 	macros: RwLock<HashMap<String, Macro>>,
+	format_macros: RwLock<HashMap<String, usize>>,
+	lazy_macros: RwLock<HashMap<String, LazyMacro>>,
+	/// The sources of the items that were declared by evaluated expressions.
+	declared_items: RwLock<HashSet<String>>,
 }
 
+/**
+The name that `file!` and `dbg!` report, as the evaluated source is not associated with a file.
+
+```rust
+# use ralik::eval_str;
+let context = ralik::Context::new();
+let value = eval_str("(file!(), line!(), column!(), stringify!(1 + 2))", &context).unwrap();
+assert_eq!(value.to_string(), "('<input>', 1, 20, '1 + 2')");
+```
+*/
+const FILE_NAME: &str = "<input>";
+
 pub type Function = fn(&Context, &[Value]) -> Result<Value, RuntimeError>;
 pub type GenericTypeCreator = fn(&Context, &[&str]) -> Result<GenericTypeBuilder, anyhow::Error>;
-pub type Macro = fn(&Context, &[Value]) -> Result<Value, RuntimeError>;
+pub type Macro = Arc<dyn Fn(&Context, &[Value]) -> Result<Value, RuntimeError> + Send + Sync>;
+pub type LazyMacro = Arc<dyn Fn(&Context, &LazyMacroCall) -> Result<Value, EvalError> + Send + Sync>;
 
 #[derive(Copy, Clone, Eq, PartialEq)]
 pub(crate) struct TypeId(usize);
 
 enum Thing {
 	Variable(Value),
-	Function(Function),
+	Function(NativeFunction),
 	Type(TypeId),
 	Generic(Arc<Generic>),
 }
 
 struct Generic {
 	name: String,
+	make_name: fn(&str, &[&str]) -> String,
 	creator: GenericTypeCreator,
 }
 
@@ -90,8 +113,24 @@ impl Context {
 		context.register_types(vec![crate::types::new_char_type()]);
 		context.register_types(vec![crate::types::new_integer_type()]);
 		context.register_types(vec![crate::types::new_string_type()]);
-		context.register_tuple_generic(crate::types::tuple_generic);
-		context.register_array_generic(crate::types::array_generic);
+		// the generics are registered to a new context, which cannot have them already
+		context.register_tuple_generic(crate::types::tuple_generic).unwrap();
+		context.register_array_generic(crate::types::array_generic).unwrap();
+		context.register_option_generic(crate::types::option_generic).unwrap();
+		context.register_result_generic(crate::types::result_generic).unwrap();
+		context.register_types(crate::types::new_range_types());
+		context.register_types(vec![crate::types::new_function_type()]);
+
+		context.insert_lazy_macro("assert", assert);
+		context.insert_lazy_macro("assert_eq", |context, call| assert_comparison(true, context, call));
+		context.insert_lazy_macro("assert_ne", |context, call| assert_comparison(false, context, call));
+
+		context.insert_lazy_macro("column", |context, call| {
+			if !call.tokens().is_empty() {
+				return Err(call.error(anyhow!("`column!` takes no arguments")));
+			}
+			Value::new_integer(context, call.span().start().column + 1).map_err(|err| call.error(err))
+		});
 
 		context.insert_macro("concat", |context, mut arguments| {
 			let mut result = String::new();
@@ -108,6 +147,32 @@ impl Context {
 			Ok(Value::new_string(context, result)?)
 		});
 
+		context.insert_lazy_macro("dbg", |context, call| {
+			let location = call.span().start();
+			let mut values = Vec::new();
+			for tokens in call.argument_tokens()? {
+				let value = crate::eval_tokens(tokens.clone(), context)?;
+				eprintln!("[{}:{}] {} = {:#?}", FILE_NAME, location.line, tokens, value);
+				values.push(value);
+			}
+			match values.len() {
+				0 => {
+					eprintln!("[{}:{}]", FILE_NAME, location.line);
+					Value::new_unit(context).map_err(|err| call.error(err))
+				}
+				1 => Ok(values.pop().unwrap()),
+				_ => Value::new_tuple(context, values).map_err(|err| call.error(err)),
+			}
+		});
+
+		context.insert_lazy_macro("debug_assert", assert);
+		context.insert_lazy_macro("debug_assert_eq", |context, call| {
+			assert_comparison(true, context, call)
+		});
+		context.insert_lazy_macro("debug_assert_ne", |context, call| {
+			assert_comparison(false, context, call)
+		});
+
 		context.insert_macro("env", |context, arguments| {
 			if arguments.len() != 1 {
 				return Err(anyhow!("`env!` takes exactly one argument of string type").into());
@@ -123,6 +188,27 @@ impl Context {
 			}
 		});
 
+		context.insert_format_macro("eprint", 0, |context, arguments| {
+			eprint!("{}", formatted_argument("eprint", arguments)?);
+			Ok(Value::new_unit(context)?)
+		});
+
+		context.insert_format_macro("eprintln", 0, |context, arguments| {
+			eprintln!("{}", formatted_argument("eprintln", arguments)?);
+			Ok(Value::new_unit(context)?)
+		});
+
+		context.insert_lazy_macro("file", |context, call| {
+			if !call.tokens().is_empty() {
+				return Err(call.error(anyhow!("`file!` takes no arguments")));
+			}
+			Value::new_string(context, FILE_NAME).map_err(|err| call.error(err))
+		});
+
+		context.insert_format_macro("format", 0, |context, arguments| {
+			Ok(Value::new_string(context, formatted_argument("format", arguments)?)?)
+		});
+
 		context.insert_macro("include", |context, arguments| {
 			if arguments.len() != 1 {
 				return Err(anyhow!("`include!` takes exactly one argument of string type").into());
@@ -200,22 +286,56 @@ impl Context {
 			}
 		});*/
 
-		context.insert_macro("panic", |_context, arguments| {
-			use std::fmt::Write;
-			let mut message = "Call to `panic!(".to_owned();
-			if !arguments.is_empty() {
-				write!(message, "\n").unwrap();
+		context.insert_lazy_macro("line", |context, call| {
+			if !call.tokens().is_empty() {
+				return Err(call.error(anyhow!("`line!` takes no arguments")));
 			}
-			for argument in arguments {
-				write!(message, "  {:?},\n", argument).unwrap();
+			Value::new_integer(context, call.span().start().line).map_err(|err| call.error(err))
+		});
+
+		context.insert_lazy_macro("matches", |context, call| {
+			let arguments = call.parse::<crate::ast::MatchesArguments>()?;
+			let value = crate::eval_expression(&arguments.expression, context)?;
+			let result = crate::eval::matches(context, &arguments.pattern, &value).map_err(|err| call.error(err))?;
+			Value::new_bool(context, result).map_err(|err| call.error(err))
+		});
+
+		context.insert_format_macro("panic", 0, |_context, arguments| {
+			match formatted_argument("panic", arguments)? {
+				"" => Err(anyhow!("explicit panic").into()),
+				message => Err(anyhow!("{}", message).into()),
 			}
-			write!(message, ")").unwrap();
-			Err(anyhow::anyhow!(message).into())
+		});
+
+		context.insert_format_macro("print", 0, |context, arguments| {
+			print!("{}", formatted_argument("print", arguments)?);
+			Ok(Value::new_unit(context)?)
+		});
+
+		context.insert_format_macro("println", 0, |context, arguments| {
+			println!("{}", formatted_argument("println", arguments)?);
+			Ok(Value::new_unit(context)?)
+		});
+
+		context.insert_lazy_macro("stringify", |context, call| {
+			Value::new_string(context, call.tokens().to_string()).map_err(|err| call.error(err))
+		});
+
+		context.insert_format_macro("todo", 0, |_context, arguments| {
+			Err(panic_message("todo", "not yet implemented", arguments)?.into())
+		});
+
+		context.insert_format_macro("unimplemented", 0, |_context, arguments| {
+			Err(panic_message("unimplemented", "not implemented", arguments)?.into())
+		});
+
+		context.insert_format_macro("unreachable", 0, |_context, arguments| {
+			Err(panic_message("unreachable", "internal error: entered unreachable code", arguments)?.into())
 		});
 
 		context.insert_macro("vec", |context, arguments| {
 			if arguments.is_empty() {
-				Err(anyhow::anyhow!("Empty `vec!` calls are currently not supported").into())
+				Err(anyhow::anyhow!("Empty `vec!` calls are currently not supported, use `Vec::<T>::default()` instead").into())
 			} else {
 				let type_0 = arguments[0].get_type();
 				Ok(Value::new_array(
@@ -226,17 +346,49 @@ impl Context {
 			}
 		});
 
+		context.insert_format_macro("write", 1, |context, arguments| {
+			let (target, formatted) = write_arguments("write", arguments)?;
+			Ok(Value::new_string(context, format!("{}{}", target, formatted))?)
+		});
+
+		context.insert_format_macro("writeln", 1, |context, arguments| {
+			let (target, formatted) = write_arguments("writeln", arguments)?;
+			Ok(Value::new_string(context, format!("{}{}\n", target, formatted))?)
+		});
+
 		context
 	}
 
 	pub fn new_empty() -> Self {
-		Context(Arc::new(ContextImpl {
-			tuples: Default::default(),
-			arrays: Default::default(),
-			types: Default::default(),
-			names: Default::default(),
-			macros: Default::default(),
-		}))
+		let context = Context(
+			Arc::new(ContextImpl {
+				types: Default::default(),
+				names: Default::default(),
+				interfaces: Default::default(),
+				macros: Default::default(),
+				format_macros: Default::default(),
+				lazy_macros: Default::default(),
+				declared_items: Default::default(),
+			}),
+			None,
+		);
+
+		// the core interfaces have distinct names and the context is empty
+		for interface in crate::types::new_core_interfaces() {
+			context.register_interface(interface).unwrap();
+		}
+		context
+	}
+
+	/// Whether an item with the given source was declared in this context before.
+	pub(crate) fn is_declared(&self, source: &str) -> bool {
+		self.0.declared_items.read().unwrap().contains(source)
+	}
+
+	/// Records that the items with the given sources were declared, so that declaring them again has no effect.
+	pub(crate) fn add_declared<'a>(&self, sources: impl IntoIterator<Item = &'a str>) {
+		let mut declared_items = self.0.declared_items.write().unwrap();
+		declared_items.extend(sources.into_iter().map(String::from));
 	}
 }
 
@@ -245,3 +397,110 @@ impl Default for Context {
 		Self::new()
 	}
 }
+
+/// Macros that take a format string receive the already formatted string as their last argument, or no argument at
+/// all if the format string was omitted.
+fn formatted_argument<'a>(name: &str, arguments: &'a [Value]) -> Result<&'a str, RuntimeError> {
+	match arguments {
+		[] => Ok(""),
+		[formatted] => Ok(
+			formatted
+				.as_string()
+				.ok_or_else(|| anyhow!("`{}!` takes an optional format string", name))?,
+		),
+		_ => Err(anyhow!("`{}!` takes an optional format string", name).into()),
+	}
+}
+
+fn panic_message(name: &str, message: &str, arguments: &[Value]) -> Result<anyhow::Error, RuntimeError> {
+	match formatted_argument(name, arguments)? {
+		"" => Ok(anyhow!("{}", message)),
+		formatted => Ok(anyhow!("{}: {}", message, formatted)),
+	}
+}
+
+/// Formats the optional message of an assertion macro, which is only done once the assertion has failed.
+fn assertion_message(
+	context: &Context,
+	call: &LazyMacroCall,
+	arguments: &crate::ast::FormatArguments,
+) -> Result<Option<String>, EvalError> {
+	crate::eval::declare_items(context, &arguments.arguments)?;
+	let message = crate::eval::eval_format_string(context, call.name(), arguments)?;
+	Ok(message.map(|message| message.as_string().unwrap().to_owned()))
+}
+
+/// Implements `assert!`, which prints its condition as it was written if it does not hold.
+fn assert(context: &Context, call: &LazyMacroCall) -> Result<Value, EvalError> {
+	let arguments = call.format_arguments(1)?;
+	let condition = match arguments.leading_arguments.as_slice() {
+		[condition] => crate::eval_expression(condition, context)?,
+		_ => {
+			let name = call.name();
+			return Err(call.error(anyhow!("`{}!` takes a condition and an optional format string", name)));
+		}
+	};
+	let holds = condition.as_bool().ok_or_else(|| {
+		call.error(RuntimeError::InvalidArgumentType {
+			argument_number: 0,
+			actual_type_name: (*condition.get_type().name()).into(),
+			expected_type_name: crate::types::make_bool_name().into(),
+		})
+	})?;
+
+	if holds {
+		Value::new_unit(context).map_err(|err| call.error(err))
+	} else {
+		let condition = call.leading_argument_sources(1)?.remove(0);
+		let message = assertion_message(context, call, &arguments)?;
+		Err(call.error(AssertionFailed::Assertion { condition, message }))
+	}
+}
+
+/// Implements `assert_eq!` (for `equal == true`) and `assert_ne!` (for `equal == false`) using `[op]::Equal`.
+fn assert_comparison(equal: bool, context: &Context, call: &LazyMacroCall) -> Result<Value, EvalError> {
+	let arguments = call.format_arguments(2)?;
+	let (left, right) = match arguments.leading_arguments.as_slice() {
+		[left, right] => (
+			crate::eval_expression(left, context)?,
+			crate::eval_expression(right, context)?,
+		),
+		_ => {
+			let name = call.name();
+			return Err(call.error(anyhow!("`{}!` takes two operands and an optional format string", name)));
+		}
+	};
+
+	let result = left
+		.get_type()
+		.call_predicate(context, crate::ops::EQUAL, &[left.clone(), right.clone()])
+		.map_err(|err| call.error(err))?;
+	if result == equal {
+		Value::new_unit(context).map_err(|err| call.error(err))
+	} else {
+		let (left, right, message) = (
+			format!("{:?}", left),
+			format!("{:?}", right),
+			assertion_message(context, call, &arguments)?,
+		);
+		Err(call.error(if equal {
+			AssertionFailed::Equal { left, right, message }
+		} else {
+			AssertionFailed::NotEqual { left, right, message }
+		}))
+	}
+}
+
+/// `write!` and `writeln!` cannot mutate their target, so they return the target string with the formatted string
+/// appended instead.
+fn write_arguments<'a>(name: &str, arguments: &'a [Value]) -> Result<(&'a str, &'a str), RuntimeError> {
+	match arguments {
+		[target, rest @ ..] => Ok((
+			target
+				.as_string()
+				.ok_or_else(|| anyhow!("`{}!` can only write to a `{}`", name, crate::types::make_string_name()))?,
+			formatted_argument(name, rest)?,
+		)),
+		[] => Err(anyhow!("`{}!` takes a target and an optional format string", name).into()),
+	}
+}
@@ -1,89 +1,320 @@
-use crate::error::{InvalidArrayType, InvalidTupleType};
+use std::collections::hash_map::Entry;
+use std::sync::Arc;
+
+use crate::error::{
+	InvalidArrayType, InvalidGenericType, InvalidOptionType, InvalidResultType, InvalidTupleType, TypeRegistrationError,
+};
 use crate::types::TypeBuilder;
 
-use super::super::{Context, GenericTypeCreator, Thing, TypeHandle};
+use super::super::{Context, Generic, GenericTypeCreator, Thing, TypeHandle};
+use super::prelude_name;
+
+/// The names that the built-in generics are registered as. Arrays and tuples are not written as paths, so their names
+/// cannot collide with declared types.
+const ARRAY_GENERIC: &str = "[_]";
+const TUPLE_GENERIC: &str = "(..)";
+pub(crate) const OPTION_GENERIC: &str = "std::option::Option";
+pub(crate) const RESULT_GENERIC: &str = "std::result::Result";
+
+fn make_generic_name(name: &str, type_parameters: &[&str]) -> String {
+	format!("{}<{}>", name, type_parameters.join(", "))
+}
 
 impl Context {
-	pub fn register_tuple_generic(&self, tuple_generic: GenericTypeCreator) {
-		let mut tuples = self.0.tuples.write().unwrap();
-		if tuples.is_some() {
-			panic!("Replacing the array generic is not supported");
-		}
-		*tuples = Some(tuple_generic);
+	/// Registers the generic `name`, which is instantiated on demand by `get_generic_type` and by paths such as
+	/// `Wrapper::<Integer>`. The `creator` receives the names of the type parameters and describes the type that is
+	/// registered as `Wrapper<Integer>`. Fails if the name is already in use.
+	///
+	/// ```rust
+	/// # use ralik::eval_str;
+	/// use ralik::types::{GenericTypeBuilder, TypeKind};
+	///
+	/// let context = ralik::Context::new();
+	/// context.register_generic("Wrapper", |_context, type_parameters| {
+	/// 	anyhow::ensure!(type_parameters.len() == 1, "`Wrapper` takes exactly one type parameter");
+	/// 	Ok(GenericTypeBuilder {
+	/// 		kind: TypeKind::TupleStruct,
+	/// 		type_parameters: vec![type_parameters[0].into()],
+	/// 		field_names: Default::default(),
+	/// 		field_types: vec![type_parameters[0].into()],
+	/// 		variant_names: Default::default(),
+	/// 		variants: Default::default(),
+	/// 		functions: Default::default(),
+	/// 		associated_functions: Default::default(),
+	/// 		interfaces: Default::default(),
+	/// 	})
+	/// }).unwrap();
+	///
+	/// let wrapper = context.get_generic_type("Wrapper", vec!["Integer"]).unwrap();
+	/// assert_eq!(wrapper.name().as_ref(), "Wrapper<Integer>");
+	/// assert!(wrapper.is_same(&context.get_generic_type("Wrapper", vec!["Integer"]).unwrap()));
+	/// assert!(context.get_generic_type("Wrapper", vec!["Integer", "char"]).is_err());
+	/// assert!(context.register_generic("Wrapper", ralik::types::option_generic).is_err());
+	///
+	/// let value = eval_str("Wrapper::<char>('x')", &context).unwrap();
+	/// assert_eq!(value.get_type().name().as_ref(), "Wrapper<char>");
+	/// ```
+	pub fn register_generic(
+		&self,
+		name: impl Into<String>,
+		creator: GenericTypeCreator,
+	) -> Result<(), TypeRegistrationError> {
+		self.insert_generic(name.into(), make_generic_name, creator)
 	}
 
-	pub fn register_array_generic(&self, array_generic: GenericTypeCreator) {
-		let mut arrays = self.0.arrays.write().unwrap();
-		if arrays.is_some() {
-			panic!("Replacing the array generic is not supported");
+	fn insert_generic(
+		&self,
+		name: String,
+		make_name: fn(&str, &[&str]) -> String,
+		creator: GenericTypeCreator,
+	) -> Result<(), TypeRegistrationError> {
+		let mut names = self.0.names.write().unwrap();
+		match names.entry(name) {
+			Entry::Occupied(entry) => Err(TypeRegistrationError::NameInUse {
+				name: entry.key().clone(),
+			}),
+			Entry::Vacant(entry) => {
+				let name = entry.key().clone();
+				entry.insert(Thing::Generic(Arc::new(Generic {
+					name,
+					make_name,
+					creator,
+				})));
+				Ok(())
+			}
 		}
-		*arrays = Some(array_generic);
 	}
 
-	pub fn get_array_type(&self, element_type_name: &str) -> Result<TypeHandle, InvalidArrayType> {
-		let name = crate::types::make_array_name(element_type_name);
-		if let Some(array_type) = self.get_type(&name) {
-			return Ok(array_type);
-		}
+	/// Registers the generic that creates the tuple types such as `(Integer, char)`. Fails if it is already
+	/// registered, as replacing it is not supported.
+	pub fn register_tuple_generic(&self, tuple_generic: GenericTypeCreator) -> Result<(), TypeRegistrationError> {
+		self.insert_generic(
+			TUPLE_GENERIC.into(),
+			|_name, element_types| crate::types::make_tuple_name(element_types.iter()),
+			tuple_generic,
+		)
+	}
 
-		let generic_type_build = match self
-			.0
-			.arrays
-			.read()
-			.unwrap()
-			.ok_or_else(|| InvalidArrayType::MissingGeneric)?(self, &[element_type_name])
-		{
-			Ok(result) => result,
-			Err(error) => return Err(InvalidArrayType::GenericFailed { name, error }),
-		};
+	/// Registers the generic that creates the array types such as `[Integer]`. Fails if it is already registered, as
+	/// replacing it is not supported.
+	pub fn register_array_generic(&self, array_generic: GenericTypeCreator) -> Result<(), TypeRegistrationError> {
+		self.insert_generic(
+			ARRAY_GENERIC.into(),
+			|_name, element_type| crate::types::make_array_name(&element_type.join(", ")),
+			array_generic,
+		)
+	}
 
-		self.register_types(vec![TypeBuilder::from_generic_type_builder(&name, generic_type_build)]);
-		Ok(self.get_type(&name).unwrap())
+	/// Registers the generic that creates the `std::option::Option` types. Fails if it is already registered, as
+	/// replacing it is not supported.
+	///
+	/// ```rust
+	/// let context = ralik::Context::new();
+	/// assert!(context.register_option_generic(ralik::types::option_generic).is_err());
+	/// let option_type = context.get_generic_type("Option", vec!["Integer"]).unwrap();
+	/// assert_eq!(option_type.name().as_ref(), "std::option::Option<Integer>");
+	/// assert!(option_type.is_same(&context.get_option_type("Integer").unwrap()));
+	/// ```
+	pub fn register_option_generic(&self, option_generic: GenericTypeCreator) -> Result<(), TypeRegistrationError> {
+		self.insert_generic(OPTION_GENERIC.into(), make_generic_name, option_generic)
+	}
+
+	/// Registers the generic that creates the `std::result::Result` types. Fails if it is already registered, as
+	/// replacing it is not supported.
+	pub fn register_result_generic(&self, result_generic: GenericTypeCreator) -> Result<(), TypeRegistrationError> {
+		self.insert_generic(RESULT_GENERIC.into(), make_generic_name, result_generic)
+	}
+
+	pub fn get_array_type(&self, element_type_name: &str) -> Result<TypeHandle, InvalidArrayType> {
+		self
+			.get_generic_type(ARRAY_GENERIC, vec![element_type_name])
+			.map_err(|err| match err {
+				InvalidGenericType::MissingGeneric { .. } => InvalidArrayType::MissingGeneric,
+				InvalidGenericType::MissingSubtype { type_parameter, .. } => InvalidArrayType::MissingSubtype {
+					element_type_name: type_parameter,
+				},
+				InvalidGenericType::GenericFailed { name, error, .. } => InvalidArrayType::GenericFailed { name, error },
+				InvalidGenericType::RegistrationFailed { name, error } => InvalidArrayType::GenericFailed {
+					name,
+					error: error.into(),
+				},
+			})
+	}
+
+	/// Instantiates the array generic without registering the result, which allows registering it together with a
+	/// recursive element type.
+	pub(crate) fn array_type_builder(&self, element_type_name: &str) -> Result<TypeBuilder, InvalidArrayType> {
+		self
+			.generic_type_builder(ARRAY_GENERIC, &[element_type_name])
+			.map_err(|err| match err {
+				InvalidGenericType::GenericFailed { name, error, .. } => InvalidArrayType::GenericFailed { name, error },
+				_ => InvalidArrayType::MissingGeneric,
+			})
 	}
 
 	pub fn get_tuple_type(&self, element_type_names: Vec<impl AsRef<str>>) -> Result<TypeHandle, InvalidTupleType> {
-		let name = crate::types::make_tuple_name(element_type_names.iter().map(|name| name.as_ref()));
-		if let Some(tuple_type) = self.get_type(&name) {
-			return Ok(tuple_type);
-		}
+		let make_tuple_name = crate::types::make_tuple_name(element_type_names.iter().map(|name| name.as_ref()));
+		self
+			.get_generic_type(TUPLE_GENERIC, element_type_names)
+			.map_err(|err| match err {
+				InvalidGenericType::MissingGeneric { .. } => InvalidTupleType::MissingGeneric,
+				InvalidGenericType::MissingSubtype { type_parameter, .. } => InvalidTupleType::MissingSubtype {
+					make_tuple_name,
+					missing_element_type_name: type_parameter,
+				},
+				InvalidGenericType::GenericFailed { name, error, .. } => InvalidTupleType::GenericFailed { name, error },
+				InvalidGenericType::RegistrationFailed { name, error } => InvalidTupleType::GenericFailed {
+					name,
+					error: error.into(),
+				},
+			})
+	}
 
+	/// Instantiates the tuple generic without registering the result, which allows registering it together with a
+	/// recursive element type.
+	pub(crate) fn tuple_type_builder(
+		&self,
+		element_type_names: Vec<impl AsRef<str>>,
+	) -> Result<TypeBuilder, InvalidTupleType> {
 		let element_type_names = element_type_names.iter().map(|name| name.as_ref()).collect::<Vec<_>>();
-		let generic_type_build = match self
-			.0
-			.tuples
-			.read()
-			.unwrap()
-			.ok_or_else(|| InvalidTupleType::MissingGeneric)?(self, &element_type_names)
-		{
-			Ok(result) => result,
-			Err(error) => return Err(InvalidTupleType::GenericFailed { name, error }),
-		};
+		self
+			.generic_type_builder(TUPLE_GENERIC, &element_type_names)
+			.map_err(|err| match err {
+				InvalidGenericType::GenericFailed { name, error, .. } => InvalidTupleType::GenericFailed { name, error },
+				_ => InvalidTupleType::MissingGeneric,
+			})
+	}
 
-		self.register_types(vec![TypeBuilder::from_generic_type_builder(&name, generic_type_build)]);
-		Ok(self.get_type(&name).unwrap())
+	pub fn get_option_type(&self, element_type_name: &str) -> Result<TypeHandle, InvalidOptionType> {
+		self
+			.get_generic_type(OPTION_GENERIC, vec![element_type_name])
+			.map_err(|err| match err {
+				InvalidGenericType::MissingGeneric { .. } => InvalidOptionType::MissingGeneric,
+				InvalidGenericType::GenericFailed { name, error, .. } => InvalidOptionType::GenericFailed { name, error },
+				err => InvalidOptionType::GenericFailed {
+					name: crate::types::make_option_name(element_type_name),
+					error: err.into(),
+				},
+			})
 	}
 
-	pub fn get_generic_type(&self, name: impl AsRef<str>, type_parameters: Vec<impl AsRef<str>>) -> TypeHandle {
-		{
-			// fast path
-			let names = self.0.names.read().unwrap();
-			let generic = match names.get(name.as_ref()) {
-				Some(Thing::Generic(generic)) => generic.clone(),
-				_ => panic!("The generic {} is not registered", name.as_ref()),
-			};
+	/// Instantiates the option generic without registering the result, which allows registering it together with a
+	/// recursive element type.
+	pub(crate) fn option_type_builder(&self, element_type_name: &str) -> Result<TypeBuilder, InvalidOptionType> {
+		self
+			.generic_type_builder(OPTION_GENERIC, &[element_type_name])
+			.map_err(|err| match err {
+				InvalidGenericType::GenericFailed { name, error, .. } => InvalidOptionType::GenericFailed { name, error },
+				_ => InvalidOptionType::MissingGeneric,
+			})
+	}
 
-			let mut canonical_name = format!("{}<", generic.name);
-			for param in &type_parameters {
-				canonical_name.push_str(param.as_ref());
-			}
-			canonical_name.push_str(">");
+	pub fn get_result_type(&self, ok_type_name: &str, err_type_name: &str) -> Result<TypeHandle, InvalidResultType> {
+		self
+			.get_generic_type(RESULT_GENERIC, vec![ok_type_name, err_type_name])
+			.map_err(|err| match err {
+				InvalidGenericType::MissingGeneric { .. } => InvalidResultType::MissingGeneric,
+				InvalidGenericType::GenericFailed { name, error, .. } => InvalidResultType::GenericFailed { name, error },
+				err => InvalidResultType::GenericFailed {
+					name: crate::types::make_result_name(ok_type_name, err_type_name),
+					error: err.into(),
+				},
+			})
+	}
 
-			match names.get(&canonical_name) {
-				Some(Thing::Type(id)) => return TypeHandle::from_type_id(self.clone(), *id),
-				_ => {}
-			}
+	/// Instantiates the result generic without registering the result, which allows registering it together with
+	/// recursive type parameters.
+	pub(crate) fn result_type_builder(
+		&self,
+		ok_type_name: &str,
+		err_type_name: &str,
+	) -> Result<TypeBuilder, InvalidResultType> {
+		self
+			.generic_type_builder(RESULT_GENERIC, &[ok_type_name, err_type_name])
+			.map_err(|err| match err {
+				InvalidGenericType::GenericFailed { name, error, .. } => InvalidResultType::GenericFailed { name, error },
+				_ => InvalidResultType::MissingGeneric,
+			})
+	}
+
+	/// Instantiates the generic `name` with the given type parameters, e.g., `Map<Integer, std::string::String>`. Each
+	/// instantiation is created once and then reused. Names of the prelude such as `Option` refer to the `std` generics
+	/// unless the context defines a generic of that name.
+	pub fn get_generic_type(
+		&self,
+		name: impl AsRef<str>,
+		type_parameters: Vec<impl AsRef<str>>,
+	) -> Result<TypeHandle, InvalidGenericType> {
+		let type_parameters = type_parameters
+			.iter()
+			.map(|param| param.as_ref())
+			.collect::<Vec<&str>>();
+		let generic = self.get_generic(name.as_ref())?;
+		let name = (generic.make_name)(&generic.name, &type_parameters);
+		if let Some(r#type) = self.get_type(&name) {
+			return Ok(r#type);
+		}
+
+		if let Some(type_parameter) = type_parameters.iter().find(|param| self.get_type(param).is_none()) {
+			return Err(InvalidGenericType::MissingSubtype {
+				generic_name: generic.name.clone(),
+				type_parameter: (*type_parameter).into(),
+			});
+		}
+
+		// The name is checked and inserted under the same lock, so if another thread instantiated the generic in the
+		// meantime, registering fails and its type is used instead
+		let registered = self.try_register_types(vec![self.build_generic(&generic, name.clone(), &type_parameters)?]);
+		match (self.get_type(&name), registered) {
+			(Some(r#type), Ok(()) | Err(TypeRegistrationError::NameInUse { .. })) => Ok(r#type),
+			(_, Err(error)) => Err(InvalidGenericType::RegistrationFailed { name, error }),
+			(None, Ok(())) => unreachable!(),
 		}
+	}
+
+	/// Whether `name` refers to a generic, including `Vec` and the generics of the prelude such as `Option`.
+	pub(crate) fn is_generic(&self, name: &str) -> bool {
+		name == "Vec" || self.get_generic(name).is_ok()
+	}
+
+	/// Instantiates the generic `name` without registering the result, which allows registering it together with a
+	/// recursive type parameter.
+	pub(crate) fn generic_type_builder(
+		&self,
+		name: &str,
+		type_parameters: &[&str],
+	) -> Result<TypeBuilder, InvalidGenericType> {
+		let generic = self.get_generic(name)?;
+		let name = (generic.make_name)(&generic.name, type_parameters);
+		self.build_generic(&generic, name, type_parameters)
+	}
 
-		unimplemented!()
+	fn get_generic(&self, name: &str) -> Result<Arc<Generic>, InvalidGenericType> {
+		let names = self.0.names.read().unwrap();
+		let generic = |name: &str| match names.get(name) {
+			Some(Thing::Generic(generic)) => Some(generic.clone()),
+			_ => None,
+		};
+		generic(name)
+			.or_else(|| prelude_name(name).and_then(generic))
+			.ok_or_else(|| InvalidGenericType::MissingGeneric {
+				generic_name: name.into(),
+			})
+	}
+
+	fn build_generic(
+		&self,
+		generic: &Generic,
+		name: String,
+		type_parameters: &[&str],
+	) -> Result<TypeBuilder, InvalidGenericType> {
+		match (generic.creator)(self, type_parameters) {
+			Ok(generic_type_builder) => Ok(TypeBuilder::from_generic_type_builder(name, generic_type_builder)),
+			Err(error) => Err(InvalidGenericType::GenericFailed {
+				generic_name: generic.name.clone(),
+				name,
+				error,
+			}),
+		}
 	}
 }
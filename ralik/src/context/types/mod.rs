@@ -1,16 +1,60 @@
 use std::collections::hash_map::HashMap;
+use std::ops::RangeInclusive;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
-use crate::error::{InvalidBoolType, InvalidCharType, InvalidIntegerType, InvalidStringType, InvalidTupleType};
+use num::{BigInt, Bounded};
+
+use crate::error::{
+	InvalidBoolType, InvalidCharType, InvalidFunctionType, InvalidIntegerType, InvalidStringType, InvalidTupleType,
+	TypeRegistrationError,
+};
 use crate::types::{Type, TypeBuilder, Variant, VariantBuilder};
 
 use super::{Context, Thing, TypeId};
 
 mod generics;
+pub(crate) use generics::{OPTION_GENERIC, RESULT_GENERIC};
 mod type_handle;
 pub use type_handle::TypeHandle;
 
+/// The full names of the types and generics that Rust makes available without a path. The fixed-size integer types all
+/// refer to the arbitrary precision `Integer`, see `sized_integer_range`.
+pub(crate) fn prelude_name(name: &str) -> Option<&'static str> {
+	match name {
+		"u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64" | "i128" | "isize" => {
+			Some("Integer")
+		}
+		"String" | "str" => Some("std::string::String"),
+		"Option" => Some("std::option::Option"),
+		"Result" => Some("std::result::Result"),
+		_ => None,
+	}
+}
+
+/// The values that the fixed-size integer type `name` of Rust can hold, e.g., `0..=255` for `u8`, or `None` if `name` is
+/// not such a type. Values of these types are `Integer`s that are checked against this range.
+pub(crate) fn sized_integer_range(name: &str) -> Option<RangeInclusive<BigInt>> {
+	fn range<T: Bounded + Into<BigInt>>() -> Option<RangeInclusive<BigInt>> {
+		Some(T::min_value().into()..=T::max_value().into())
+	}
+	match name {
+		"u8" => range::<u8>(),
+		"u16" => range::<u16>(),
+		"u32" => range::<u32>(),
+		"u64" => range::<u64>(),
+		"u128" => range::<u128>(),
+		"usize" => range::<usize>(),
+		"i8" => range::<i8>(),
+		"i16" => range::<i16>(),
+		"i32" => range::<i32>(),
+		"i64" => range::<i64>(),
+		"i128" => range::<i128>(),
+		"isize" => range::<isize>(),
+		_ => None,
+	}
+}
+
 impl Context {
 	pub fn get_type(&self, key: impl AsRef<str>) -> Option<TypeHandle> {
 		let names = self.0.names.read().unwrap();
@@ -20,6 +64,24 @@ impl Context {
 		}
 	}
 
+	/// All types that are registered in this context, ordered by name.
+	pub fn types(&self) -> impl Iterator<Item = TypeHandle> {
+		let names = self.0.names.read().unwrap();
+		let mut types = names
+			.iter()
+			.filter_map(|(name, thing)| match thing {
+				Thing::Type(id) => Some((name, *id)),
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+		types.sort_unstable_by_key(|(name, _id)| *name);
+		types
+			.into_iter()
+			.map(|(_name, id)| TypeHandle::from_type_id(self.clone(), id))
+			.collect::<Vec<_>>()
+			.into_iter()
+	}
+
 	pub fn get_unit_type(&self) -> Result<TypeHandle, InvalidTupleType> {
 		self.get_tuple_type(vec![] as Vec<String>)
 	}
@@ -48,12 +110,47 @@ impl Context {
 			.ok_or_else(|| InvalidStringType::Missing)
 	}
 
+	pub fn get_function_type(&self) -> Result<TypeHandle, InvalidFunctionType> {
+		self
+			.get_type(crate::types::make_function_name())
+			.ok_or(InvalidFunctionType::Missing)
+	}
+
+	/// Registers the given types, which may refer to each other. Panics if any of them cannot be registered, see
+	/// `try_register_types`.
 	pub fn register_types(&self, type_builders: Vec<TypeBuilder>) {
+		if let Err(err) = self.try_register_types(type_builders) {
+			panic!("{}", err);
+		}
+	}
+
+	/// Registers the given types, which may refer to each other. If any of them cannot be registered, e.g., because its
+	/// name is already in use, none of them are.
+	///
+	/// ```rust
+	/// # use ralik::error::TypeRegistrationError;
+	/// # use ralik::types::new_unit_struct_type;
+	/// let context = ralik::Context::new();
+	/// context.try_register_types(vec![new_unit_struct_type("Marker")]).unwrap();
+	/// let err = context.try_register_types(vec![new_unit_struct_type("Marker")]).unwrap_err();
+	/// assert!(matches!(err, TypeRegistrationError::NameInUse { name } if name == "Marker"));
+	/// ```
+	pub fn try_register_types(&self, mut type_builders: Vec<TypeBuilder>) -> Result<(), TypeRegistrationError> {
+		for type_builder in &mut type_builders {
+			self.implement_interfaces(type_builder)?;
+		}
+
 		let mut names = self.0.names.write().unwrap();
 
-		for type_builder in &type_builders {
-			if names.get(&type_builder.name).is_some() {
-				panic!("Name {} is already in use", type_builder.name);
+		for (index, type_builder) in type_builders.iter().enumerate() {
+			if names.get(&type_builder.name).is_some()
+				|| type_builders[..index]
+					.iter()
+					.any(|previous| previous.name == type_builder.name)
+			{
+				return Err(TypeRegistrationError::NameInUse {
+					name: type_builder.name.clone(),
+				});
 			}
 		}
 
@@ -72,30 +169,32 @@ impl Context {
 			)
 			.collect::<HashMap<String, usize>>();
 
-		for (name, resolution) in type_builders
-			.iter()
-			.flat_map(|type_builder| {
-				type_builder
-					.type_parameters
-					.iter()
-					.chain(type_builder.field_types.iter())
-					.chain(type_builder.variants.iter().flat_map(|variant| match variant {
-						VariantBuilder::Unit(_name) => [].iter(),
-						VariantBuilder::Tuple(_name, types) => types.iter(),
-						VariantBuilder::Struct(_name, _names, types) => types.iter(),
-					}))
-			})
-			.map(|type_name| (type_name, names.get(type_name)))
-		{
-			match resolution {
+		for (type_builder, name) in type_builders.iter().flat_map(|type_builder| {
+			type_builder
+				.type_parameters
+				.iter()
+				.chain(type_builder.field_types.iter())
+				.chain(type_builder.variants.iter().flat_map(|variant| match variant {
+					VariantBuilder::Unit(_name) => [].iter(),
+					VariantBuilder::Tuple(_name, types) => types.iter(),
+					VariantBuilder::Struct(_name, _names, types) => types.iter(),
+				}))
+				.map(move |name| (type_builder, name))
+		}) {
+			match names.get(name) {
 				Some(Thing::Type(_)) => (),
-				Some(_) => panic!(
-					"The dependent type {} resolved to something that is not actually a type",
-					name
-				),
+				Some(_) => {
+					return Err(TypeRegistrationError::NotAType {
+						type_name: type_builder.name.clone(),
+						dependent_type_name: name.clone(),
+					})
+				}
 				None => {
 					if new_type_map.get(name).is_none() {
-						panic!("The dependent type {} could not be resolved", name);
+						return Err(TypeRegistrationError::UnresolvedType {
+							type_name: type_builder.name.clone(),
+							dependent_type_name: name.clone(),
+						});
 					}
 				}
 			}
@@ -113,6 +212,13 @@ impl Context {
 				debug_assert!(previous == 0);
 			}
 
+			let mut interfaces = type_builder
+				.interfaces
+				.into_iter()
+				.map(String::into_boxed_str)
+				.collect::<Vec<_>>();
+			interfaces.sort_unstable();
+
 			types[id].0 = Type {
 				name: type_builder.name.into(),
 				kind: type_builder.kind,
@@ -126,8 +232,7 @@ impl Context {
 								Thing::Type(id) => *id,
 								_ => unreachable!(),
 							})
-							.ok_or_else(|| new_type_map.get(name).unwrap())
-							.unwrap()
+							.unwrap_or_else(|| TypeId(*new_type_map.get(name).unwrap()))
 					})
 					.collect(),
 				field_names: Arc::new(
@@ -147,8 +252,7 @@ impl Context {
 								Thing::Type(id) => *id,
 								_ => unreachable!(),
 							})
-							.ok_or_else(|| new_type_map.get(name).unwrap())
-							.unwrap()
+							.unwrap_or_else(|| TypeId(*new_type_map.get(name).unwrap()))
 					})
 					.collect(),
 				variant_names: Arc::new(
@@ -174,8 +278,7 @@ impl Context {
 											Thing::Type(id) => *id,
 											_ => unreachable!(),
 										})
-										.ok_or_else(|| new_type_map.get(name).unwrap())
-										.unwrap()
+										.unwrap_or_else(|| TypeId(*new_type_map.get(name).unwrap()))
 								})
 								.collect(),
 						),
@@ -194,8 +297,7 @@ impl Context {
 											Thing::Type(id) => *id,
 											_ => unreachable!(),
 										})
-										.ok_or_else(|| new_type_map.get(name).unwrap())
-										.unwrap()
+										.unwrap_or_else(|| TypeId(*new_type_map.get(name).unwrap()))
 								})
 								.collect(),
 						),
@@ -208,9 +310,22 @@ impl Context {
 						.map(|(name, function)| (name.into_boxed_str(), function))
 						.collect(),
 				),
+				associated_functions: Arc::new(
+					type_builder
+						.associated_functions
+						.into_iter()
+						.map(|(name, function)| (name.into_boxed_str(), function))
+						.collect(),
+				),
+				interfaces: interfaces.into(),
 			};
 
-			let this_type = &types[id].0;
+			names.insert((&*types[id].0.name).into(), Thing::Type(TypeId(id)));
+		}
+
+		// The new types may refer to each other, so references are only counted once all of them exist
+		for id in new_type_map.values() {
+			let this_type = &types[*id].0;
 			for id in this_type
 				.type_parameters
 				.iter()
@@ -226,8 +341,8 @@ impl Context {
 					panic!("Reference count overflow!");
 				}
 			}
-
-			names.insert((&*this_type.name).into(), Thing::Type(TypeId(id)));
 		}
+
+		Ok(())
 	}
 }
@@ -2,9 +2,11 @@ use std::collections::hash_map::HashMap;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
-use crate::types::{MemberFunction, TypeKind, Variant};
+use crate::error::{RuntimeError, TypeRegistrationError};
+use crate::types::{NativeMemberFunction, TypeKind, Variant};
+use crate::Value;
 
-use super::super::{Context, TypeId};
+use super::super::{Context, Thing, TypeId};
 
 #[derive(Eq, PartialEq)]
 pub struct TypeHandle {
@@ -21,14 +23,102 @@ impl TypeHandle {
 		self.context.0.types.read().unwrap()[self.type_id.0].0.kind
 	}
 
-	pub(crate) fn type_parameters(&self) -> Arc<[TypeId]> {
+	pub(crate) fn type_parameter_ids(&self) -> Arc<[TypeId]> {
 		self.context.0.types.read().unwrap()[self.type_id.0]
 			.0
 			.type_parameters
 			.clone()
 	}
 
-	pub fn get_function(&self, name: impl AsRef<str>) -> Option<MemberFunction> {
+	pub(crate) fn type_parameter(&self, index: usize) -> TypeHandle {
+		TypeHandle::from_type_id(self.context.clone(), self.type_parameter_ids()[index])
+	}
+
+	/// The types that this type was instantiated with, e.g., `Integer` for `[Integer]`.
+	pub fn type_parameters(&self) -> impl Iterator<Item = TypeHandle> {
+		let context = self.context.clone();
+		self
+			.type_parameter_ids()
+			.iter()
+			.map(|id| TypeHandle::from_type_id(context.clone(), *id))
+			.collect::<Vec<_>>()
+			.into_iter()
+	}
+
+	/// All member functions of this type, including operators such as `[op]::Add`, ordered by name.
+	pub fn member_functions(&self) -> impl Iterator<Item = (String, NativeMemberFunction)> {
+		let mut functions = self.context.0.types.read().unwrap()[self.type_id.0]
+			.0
+			.functions
+			.iter()
+			.map(|(name, function)| (name.to_string(), function.clone()))
+			.collect::<Vec<_>>();
+		functions.sort_unstable_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+		functions.into_iter()
+	}
+
+	/// All associated functions of this type, i.e., those that are called without a receiver, ordered by name.
+	pub fn associated_functions(&self) -> impl Iterator<Item = (String, NativeMemberFunction)> {
+		let mut functions = self.context.0.types.read().unwrap()[self.type_id.0]
+			.0
+			.associated_functions
+			.iter()
+			.map(|(name, function)| (name.to_string(), function.clone()))
+			.collect::<Vec<_>>();
+		functions.sort_unstable_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+		functions.into_iter()
+	}
+
+	/// The names of the fields of a struct type in declaration order.
+	pub fn field_names(&self) -> impl Iterator<Item = String> {
+		sorted_names(&self.fields().0).into_iter()
+	}
+
+	/// The names of the variants of an enum type in declaration order.
+	pub fn variants(&self) -> impl Iterator<Item = String> {
+		self
+			.variant_table()
+			.1
+			.iter()
+			.map(|variant| variant.name().to_owned())
+			.collect::<Vec<_>>()
+			.into_iter()
+	}
+
+	/// The names of the interfaces that this type implements, ordered by name.
+	pub fn interfaces(&self) -> impl Iterator<Item = String> {
+		self.context.0.types.read().unwrap()[self.type_id.0]
+			.0
+			.interfaces
+			.iter()
+			.map(|name| name.to_string())
+			.collect::<Vec<_>>()
+			.into_iter()
+	}
+
+	/// Checks whether this type implements the interface `name`, e.g., to test whether its values can be sorted with
+	/// `implements("Ord")`.
+	pub fn implements(&self, name: impl AsRef<str>) -> bool {
+		self.context.0.types.read().unwrap()[self.type_id.0]
+			.0
+			.interfaces
+			.binary_search_by(|interface| (**interface).cmp(name.as_ref()))
+			.is_ok()
+	}
+
+	/// Returns an error that names the type and the interface if this type does not implement the interface `name`.
+	pub(crate) fn check_implements(&self, name: &str) -> Result<(), RuntimeError> {
+		if self.implements(name) {
+			Ok(())
+		} else {
+			Err(RuntimeError::MissingInterface {
+				type_name: (*self.name()).into(),
+				interface_name: name.into(),
+			})
+		}
+	}
+
+	pub fn get_function(&self, name: impl AsRef<str>) -> Option<NativeMemberFunction> {
 		self.context.0.types.read().unwrap()[self.type_id.0]
 			.0
 			.functions
@@ -36,6 +126,158 @@ impl TypeHandle {
 			.cloned()
 	}
 
+	pub fn get_associated_function(&self, name: impl AsRef<str>) -> Option<NativeMemberFunction> {
+		self.context.0.types.read().unwrap()[self.type_id.0]
+			.0
+			.associated_functions
+			.get(name.as_ref())
+			.cloned()
+	}
+
+	/// Adds member and associated functions to this type, e.g., those of an `impl` block, and declares that it implements
+	/// the given interfaces. Fails without changing the type if it already provides any of the functions, or if it does
+	/// not provide all functions that the interfaces require.
+	pub(crate) fn extend(
+		&self,
+		functions: HashMap<String, NativeMemberFunction>,
+		associated_functions: HashMap<String, NativeMemberFunction>,
+		interfaces: Vec<String>,
+	) -> Result<(), TypeRegistrationError> {
+		let type_name = self.name();
+		let mut types = self.context.0.types.write().unwrap();
+		let r#type = &mut types[self.type_id.0].0;
+
+		let mut all_functions = r#type
+			.functions
+			.iter()
+			.map(|(name, function)| (name.to_string(), function.clone()))
+			.collect::<HashMap<_, _>>();
+		for (name, function) in functions {
+			if all_functions.contains_key(&name) {
+				return Err(TypeRegistrationError::FunctionInUse {
+					type_name: (*type_name).into(),
+					function_name: name,
+				});
+			}
+			all_functions.insert(name, function);
+		}
+		let mut all_associated_functions = r#type
+			.associated_functions
+			.iter()
+			.map(|(name, function)| (name.to_string(), function.clone()))
+			.collect::<HashMap<_, _>>();
+		for (name, function) in associated_functions {
+			if all_associated_functions.contains_key(&name) {
+				return Err(TypeRegistrationError::FunctionInUse {
+					type_name: (*type_name).into(),
+					function_name: name,
+				});
+			}
+			all_associated_functions.insert(name, function);
+		}
+		let interfaces = interfaces
+			.into_iter()
+			.filter(|name| !r#type.interfaces.iter().any(|interface| **interface == **name))
+			.collect::<Vec<_>>();
+		self
+			.context
+			.implement_interfaces_for(&type_name, &interfaces, &mut all_functions, &all_associated_functions)?;
+
+		r#type.functions = Arc::new(
+			all_functions
+				.into_iter()
+				.map(|(name, function)| (name.into_boxed_str(), function))
+				.collect(),
+		);
+		r#type.associated_functions = Arc::new(
+			all_associated_functions
+				.into_iter()
+				.map(|(name, function)| (name.into_boxed_str(), function))
+				.collect(),
+		);
+		let mut all_interfaces = r#type
+			.interfaces
+			.iter()
+			.cloned()
+			.chain(interfaces.into_iter().map(String::into_boxed_str))
+			.collect::<Vec<_>>();
+		all_interfaces.sort_unstable();
+		r#type.interfaces = all_interfaces.into();
+		Ok(())
+	}
+
+	pub(crate) fn call_function(
+		&self,
+		context: &Context,
+		name: &str,
+		arguments: &[Value],
+	) -> Result<Value, RuntimeError> {
+		let function = self
+			.get_function(name)
+			.ok_or_else(|| RuntimeError::MissingMemberFunction {
+				type_name: (*self.name()).into(),
+				function_name: name.into(),
+			})?;
+		function.call(context, self, arguments)
+	}
+
+	pub(crate) fn call_associated_function(
+		&self,
+		context: &Context,
+		name: &str,
+		arguments: &[Value],
+	) -> Result<Value, RuntimeError> {
+		let function = self
+			.get_associated_function(name)
+			.ok_or_else(|| RuntimeError::MissingAssociatedFunction {
+				type_name: (*self.name()).into(),
+				function_name: name.into(),
+			})?;
+		function.call(context, self, arguments)
+	}
+
+	pub(crate) fn call_predicate(
+		&self,
+		context: &Context,
+		name: &str,
+		arguments: &[Value],
+	) -> Result<bool, RuntimeError> {
+		let result = self.call_function(context, name, arguments)?;
+		result.as_bool().ok_or_else(|| RuntimeError::InvalidReturnType {
+			type_name: (*self.name()).into(),
+			function_name: name.into(),
+			actual_type_name: (*result.get_type().name()).into(),
+			expected_type_name: crate::types::make_bool_name().into(),
+		})
+	}
+
+	pub(crate) fn call_string_function(
+		&self,
+		context: &Context,
+		name: &str,
+		arguments: &[Value],
+	) -> Result<String, RuntimeError> {
+		let result = self.call_function(context, name, arguments)?;
+		match result.as_string() {
+			Some(result) => Ok(result.to_owned()),
+			None => Err(RuntimeError::InvalidReturnType {
+				type_name: (*self.name()).into(),
+				function_name: name.into(),
+				actual_type_name: (*result.get_type().name()).into(),
+				expected_type_name: crate::types::make_string_name().into(),
+			}),
+		}
+	}
+
+	/// Calls `[op]::Debug`, which takes the value and whether the alternate form was requested.
+	pub(crate) fn call_debug(&self, context: &Context, value: &Value, alternate: bool) -> Result<String, RuntimeError> {
+		self.call_string_function(
+			context,
+			crate::ops::DEBUG,
+			&[value.clone(), Value::new_bool(context, alternate)?],
+		)
+	}
+
 	pub(crate) fn fields(&self) -> (Arc<HashMap<Box<str>, usize>>, Arc<[TypeId]>) {
 		let types = self.context.0.types.read().unwrap();
 		(
@@ -44,7 +286,7 @@ impl TypeHandle {
 		)
 	}
 
-	pub(crate) fn variants(&self) -> (Arc<HashMap<Box<str>, usize>>, Arc<[Variant]>) {
+	pub(crate) fn variant_table(&self) -> (Arc<HashMap<Box<str>, usize>>, Arc<[Variant]>) {
 		let types = self.context.0.types.read().unwrap();
 		(
 			types[self.type_id.0].0.variant_names.clone(),
@@ -52,6 +294,28 @@ impl TypeHandle {
 		)
 	}
 
+	/// Whether this type is the instantiation of the option generic of its context, e.g., `Option<Integer>`.
+	pub(crate) fn is_option(&self) -> bool {
+		let has_option_generic = matches!(
+			self.context.0.names.read().unwrap().get(super::OPTION_GENERIC),
+			Some(Thing::Generic(_))
+		);
+		if !has_option_generic || self.kind() != TypeKind::Enum {
+			return false;
+		}
+		match &self.type_parameter_ids()[..] {
+			[element_type] => {
+				let element_type = TypeHandle::from_type_id(self.context.clone(), *element_type);
+				let name = crate::types::make_option_name(&element_type.name());
+				self
+					.context
+					.get_type(name)
+					.is_some_and(|option_type| option_type.is_same(self))
+			}
+			_ => false,
+		}
+	}
+
 	pub fn is_same(&self, other: &Self) -> bool {
 		assert!(Arc::ptr_eq(&self.context.0, &other.context.0));
 		self.type_id.0 == other.type_id.0
@@ -62,6 +326,13 @@ impl TypeHandle {
 	}
 }
 
+/// The names of the fields that are given by `field_names`, ordered by their index.
+fn sorted_names(field_names: &HashMap<Box<str>, usize>) -> Vec<String> {
+	let mut field_names = field_names.iter().collect::<Vec<_>>();
+	field_names.sort_by_key(|(_name, id)| **id);
+	field_names.into_iter().map(|(name, _id)| name.to_string()).collect()
+}
+
 impl std::fmt::Debug for TypeHandle {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		write!(f, "<Type {}>", self.type_id.0)
@@ -70,7 +341,8 @@ impl std::fmt::Debug for TypeHandle {
 
 impl TypeHandle {
 	pub(crate) fn from_type_id(context: impl Into<Context>, type_id: TypeId) -> Self {
-		let context = context.into();
+		// types must not keep the values of local variables alive
+		let context = context.into().without_locals();
 		{
 			let types = context.0.types.read().unwrap();
 			let previous = types[type_id.0].1.fetch_add(1, Ordering::SeqCst);
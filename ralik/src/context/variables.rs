@@ -1,24 +1,126 @@
 use std::collections::hash_map::Entry;
+use std::sync::Arc;
 
+use crate::error::VariableInsertionError;
 use crate::Value;
 
 use super::{Context, Thing};
 
+/// A local variable that is bound by `let`, which shadows the global variables and the locals of enclosing scopes.
+pub(crate) struct Scope {
+	name: String,
+	value: Value,
+	parent: Option<Arc<Scope>>,
+}
+
 impl Context {
+	/// Looks up a local variable that is visible in this context, or a global variable otherwise.
 	pub fn get_variable(&self, key: &str) -> Option<Value> {
+		let mut scope = &self.1;
+		while let Some(local) = scope {
+			if local.name == key {
+				return Some(local.value.clone());
+			}
+			scope = &local.parent;
+		}
+
 		match self.0.names.read().unwrap().get(key) {
 			Some(Thing::Variable(value)) => Some(value.clone()),
 			_ => None,
 		}
 	}
 
+	/// All global variables in this context, ordered by name.
+	pub fn variables(&self) -> impl Iterator<Item = (String, Value)> {
+		let mut variables = self
+			.0
+			.names
+			.read()
+			.unwrap()
+			.iter()
+			.filter_map(|(name, thing)| match thing {
+				Thing::Variable(value) => Some((name.clone(), value.clone())),
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+		variables.sort_unstable_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+		variables.into_iter()
+	}
+
+	/// Creates a context in which the local variable `name` is visible in addition to all variables of this context.
+	pub(crate) fn with_local(&self, name: impl Into<String>, value: Value) -> Context {
+		Context(
+			self.0.clone(),
+			Some(Arc::new(Scope {
+				name: name.into(),
+				value,
+				parent: self.1.clone(),
+			})),
+		)
+	}
+
+	/// The same context without any local variables.
+	pub(crate) fn without_locals(&self) -> Context {
+		Context(self.0.clone(), None)
+	}
+
+	/// Inserts a global variable. Panics if the name is already in use, see `try_insert_variable`.
 	pub fn insert_variable(&self, key: impl Into<String>, value: impl Into<Value>) {
+		if let Err(err) = self.try_insert_variable(key, value) {
+			panic!("{}", err);
+		}
+	}
+
+	/// Inserts a global variable, which is visible to all expressions that are evaluated in this context afterwards.
+	///
+	/// ```rust
+	/// # use ralik::error::VariableInsertionError;
+	/// # use ralik::Value;
+	/// let context = ralik::Context::new();
+	/// context.try_insert_variable("answer", Value::new_integer(&context, 42).unwrap()).unwrap();
+	/// let err = context.try_insert_variable("answer", Value::new_integer(&context, 23).unwrap()).unwrap_err();
+	/// assert!(matches!(err, VariableInsertionError::NameInUse { name } if name == "answer"));
+	/// ```
+	pub fn try_insert_variable(
+		&self,
+		key: impl Into<String>,
+		value: impl Into<Value>,
+	) -> Result<(), VariableInsertionError> {
 		let mut names = self.0.names.write().unwrap();
 		match names.entry(key.into()) {
-			Entry::Occupied(entry) => panic!("The name `{}` is defined multiple times", entry.key()),
+			Entry::Occupied(entry) => Err(VariableInsertionError::NameInUse {
+				name: entry.key().clone(),
+			}),
 			Entry::Vacant(entry) => {
 				entry.insert(Thing::Variable(value.into()));
+				Ok(())
+			}
+		}
+	}
+
+	/// Inserts the given global variables. If any of them cannot be inserted, e.g., because its name is already in use,
+	/// none of them are.
+	///
+	/// ```rust
+	/// # use ralik::error::VariableInsertionError;
+	/// # use ralik::Value;
+	/// let context = ralik::Context::new();
+	/// let one = Value::new_integer(&context, 1).unwrap();
+	/// context.try_insert_variables(vec![("a".into(), one.clone())]).unwrap();
+	/// let err = context.try_insert_variables(vec![("b".into(), one.clone()), ("a".into(), one)]).unwrap_err();
+	/// assert!(matches!(err, VariableInsertionError::NameInUse { name } if name == "a"));
+	/// assert!(context.get_variable("b").is_none());
+	/// ```
+	pub fn try_insert_variables(&self, variables: Vec<(String, Value)>) -> Result<(), VariableInsertionError> {
+		let mut names = self.0.names.write().unwrap();
+		for (index, (name, _value)) in variables.iter().enumerate() {
+			if names.contains_key(name) || variables[..index].iter().any(|(previous, _value)| previous == name) {
+				return Err(VariableInsertionError::NameInUse { name: name.clone() });
 			}
 		}
+		for (name, value) in variables {
+			names.insert(name, Thing::Variable(value));
+		}
+		Ok(())
 	}
 }
@@ -1,8 +1,5 @@
-use proc_macro2::Span;
 use thiserror::Error;
 
-use std::convert::TryInto;
-
 use super::{Location, RuntimeError};
 
 #[derive(Error, Debug)]
@@ -26,6 +23,22 @@ pub enum EvalError {
 		at: Location,
 	},
 
+	#[error("Path `{path}` does not refer to a type, variant or function when referenced at {at}")]
+	UnknownPath { path: String, at: Location },
+
+	#[error("Path `{path}` does not refer to a {expected} when referenced at {at}")]
+	InvalidPath {
+		path: String,
+		expected: &'static str,
+		at: Location,
+	},
+
+	#[error("Type `{name}` does not exist when referenced at {at}")]
+	UnknownType { name: String, at: Location },
+
+	#[error("Type `{name}` is generic and its generic arguments cannot be inferred, so they must be given when referenced at {at}")]
+	MissingGenericArguments { name: String, at: Location },
+
 	#[error("Macro `{name}!` does not exist when referenced at {at}")]
 	UnknownMacro { name: String, at: Location },
 
@@ -55,7 +68,7 @@ pub enum EvalError {
 	FunctionRuntimeError {
 		name: String,
 		#[source]
-		source: RuntimeError,
+		source: Box<RuntimeError>,
 		at: Location,
 	},
 
@@ -63,7 +76,7 @@ pub enum EvalError {
 	MacroRuntimeError {
 		name: String,
 		#[source]
-		source: RuntimeError,
+		source: Box<RuntimeError>,
 		at: Location,
 	},
 
@@ -72,32 +85,95 @@ pub enum EvalError {
 		name: String,
 		type_name: String,
 		#[source]
-		source: RuntimeError,
+		source: Box<RuntimeError>,
 		at: Location,
 	},
 
 	#[error("Could not create object at {at}")]
 	ObjectCreationError {
 		#[source]
-		source: crate::error::ValueCreationError,
+		source: Box<crate::error::ValueCreationError>,
 		at: Location,
 	},
 
 	#[error("Could not create object of core type at {at}")]
 	InvalidCoreType {
 		#[source]
-		source: crate::error::InvalidCoreType,
+		source: Box<crate::error::InvalidCoreType>,
+		at: Location,
+	},
+
+	#[error("Could not instantiate generic type at {at}")]
+	InvalidGenericType {
+		#[source]
+		source: Box<crate::error::InvalidGenericType>,
+		at: Location,
+	},
+
+	#[error("Expected a value of type `{expected_type_name}`, but got a value of type `{actual_type_name}` at {at}")]
+	MismatchedType {
+		expected_type_name: String,
+		actual_type_name: String,
 		at: Location,
 	},
 
+	#[error("Cannot cast a value of type `{type_name}` to `{target_type_name}` at {at}")]
+	InvalidCast {
+		type_name: String,
+		target_type_name: String,
+		at: Location,
+	},
+
+	#[error("The name `{name}` is already in use when declared at {at}")]
+	DuplicateName { name: String, at: Location },
+
+	#[error("Could not declare type `{name}` at {at}")]
+	InvalidTypeDeclaration {
+		name: String,
+		#[source]
+		source: Box<crate::error::TypeRegistrationError>,
+		at: Location,
+	},
+
+	#[error("`{name}` cannot be derived at {at}")]
+	UnknownDerive { name: String, at: Location },
+
+	#[error("`{name}` is neither an operator trait nor an interface at {at}")]
+	UnknownTrait { name: String, at: Location },
+
+	#[error("The function `{name}` is not a member of the trait `{trait_name}` at {at}")]
+	NotATraitMember {
+		name: String,
+		trait_name: String,
+		at: Location,
+	},
+
+	#[error("Could not implement functions for type `{type_name}` at {at}")]
+	InvalidImpl {
+		type_name: String,
+		#[source]
+		source: Box<crate::error::TypeRegistrationError>,
+		at: Location,
+	},
+
+	#[error("The value `{value}` does not fit into the type `{type_name}` at {at}")]
+	IntegerOutOfRange {
+		value: String,
+		type_name: String,
+		at: Location,
+	},
+
+	#[error("The fixed-size integer type `{type_name}` cannot be used for fields, use `Integer` instead at {at}")]
+	SizedIntegerField { type_name: String, at: Location },
+
 	#[error("Expressions creating empty arrays are not currently supported.")]
 	EmptyArray { at: Location },
 }
 
 impl EvalError {
-	pub fn span(&self) -> Option<Span> {
+	pub fn span(&self) -> Option<Location> {
 		match self {
-			EvalError::ParseError { cause: err } => Some(err.span()),
+			EvalError::ParseError { cause: err } => Some(err.span().into()),
 			EvalError::UnknownVariable { name: _, at: loc }
 			| EvalError::UnknownFunction { name: _, at: loc }
 			| EvalError::UnknownMemberFunction {
@@ -105,6 +181,14 @@ impl EvalError {
 				type_name: _,
 				at: loc,
 			}
+			| EvalError::UnknownPath { path: _, at: loc }
+			| EvalError::InvalidPath {
+				path: _,
+				expected: _,
+				at: loc,
+			}
+			| EvalError::UnknownType { name: _, at: loc }
+			| EvalError::MissingGenericArguments { name: _, at: loc }
 			| EvalError::UnknownMacro { name: _, at: loc }
 			| EvalError::InvalidFieldAccess {
 				member_name: _,
@@ -138,7 +222,42 @@ impl EvalError {
 			}
 			| EvalError::ObjectCreationError { source: _, at: loc }
 			| EvalError::InvalidCoreType { source: _, at: loc }
-			| EvalError::EmptyArray { at: loc } => loc.try_into().ok(),
+			| EvalError::InvalidGenericType { source: _, at: loc }
+			| EvalError::MismatchedType {
+				expected_type_name: _,
+				actual_type_name: _,
+				at: loc,
+			}
+			| EvalError::InvalidCast {
+				type_name: _,
+				target_type_name: _,
+				at: loc,
+			}
+			| EvalError::DuplicateName { name: _, at: loc }
+			| EvalError::InvalidTypeDeclaration {
+				name: _,
+				source: _,
+				at: loc,
+			}
+			| EvalError::UnknownDerive { name: _, at: loc }
+			| EvalError::UnknownTrait { name: _, at: loc }
+			| EvalError::NotATraitMember {
+				name: _,
+				trait_name: _,
+				at: loc,
+			}
+			| EvalError::InvalidImpl {
+				type_name: _,
+				source: _,
+				at: loc,
+			}
+			| EvalError::IntegerOutOfRange {
+				value: _,
+				type_name: _,
+				at: loc,
+			}
+			| EvalError::SizedIntegerField { type_name: _, at: loc }
+			| EvalError::EmptyArray { at: loc } => Some(*loc),
 		}
 	}
 }
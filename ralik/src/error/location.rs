@@ -1,51 +1,47 @@
-use proc_macro2::Span;
+use proc_macro2::{LineColumn, Span};
 
 use std::convert::From;
 
-#[derive(Copy, Clone, Debug)]
-pub enum Location {
-	Spanned(Span),
+/// A range of the evaluated source, given by the line and column of its start and end. Unlike a `Span`, it can be sent
+/// to other threads, which allows sharing the syntax tree and the errors that refer to it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Location {
+	start: LineColumn,
+	end: LineColumn,
 }
 
-impl From<Span> for Location {
-	fn from(span: Span) -> Location {
-		Location::Spanned(span)
+impl Location {
+	pub fn start(&self) -> LineColumn {
+		self.start
 	}
-}
 
-impl From<&Span> for Location {
-	fn from(span: &Span) -> Location {
-		Location::Spanned(*span)
+	pub fn end(&self) -> LineColumn {
+		self.end
 	}
 }
 
-impl std::convert::TryFrom<Location> for Span {
-	type Error = ();
-	fn try_from(value: Location) -> Result<Self, Self::Error> {
-		match value {
-			Location::Spanned(span) => Ok(span),
+impl From<Span> for Location {
+	fn from(span: Span) -> Location {
+		Location {
+			start: span.start(),
+			end: span.end(),
 		}
 	}
 }
 
-impl std::convert::TryFrom<&Location> for Span {
-	type Error = ();
-	fn try_from(value: &Location) -> Result<Self, Self::Error> {
-		Span::try_from(*value)
+impl From<&Span> for Location {
+	fn from(span: &Span) -> Location {
+		Location::from(*span)
 	}
 }
 
 impl std::fmt::Display for Location {
 	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-		match self {
-			Location::Spanned(span) => {
-				let (start, end) = (&span.start(), &span.end());
-				if start.line == end.line && end.column - start.column <= 1 {
-					write!(f, "{}:{}", start.line, start.column)
-				} else {
-					write!(f, "{}:{} to {}:{}", start.line, start.column, end.line, end.column)
-				}
-			}
+		let (start, end) = (&self.start, &self.end);
+		if start.line == end.line && end.column - start.column <= 1 {
+			write!(f, "{}:{}", start.line, start.column)
+		} else {
+			write!(f, "{}:{} to {}:{}", start.line, start.column, end.line, end.column)
 		}
 	}
 }
@@ -6,10 +6,16 @@ pub use eval_error::EvalError;
 
 mod runtime_error;
 pub use runtime_error::{
-	ArrayCreationError, BoolCreationError, CharCreationError, EnumStructVariantCreationError,
-	EnumTupleVariantCreationError, EnumUnitVariantCreationError, IntegerCreationError, InvalidArrayType, InvalidBoolType,
-	InvalidCharType, InvalidCoreType, InvalidEnumType, InvalidIntegerType, InvalidOptionType, InvalidStringType,
-	InvalidStructType, InvalidTupleStructType, InvalidTupleType, InvalidUnitStructType, Overflow, RuntimeError,
-	StringCreationError, StructCreationError, TupleCreationError, TupleStructCreationError, UnitStructCreationError,
-	ValueCreationError,
+	ArrayCreationError, AssertionFailed, BoolCreationError, CharCreationError, ConversionError,
+	EnumStructVariantCreationError, EnumTupleVariantCreationError, EnumUnitVariantCreationError, FunctionCreationError,
+	IntegerCreationError, InvalidArrayType, InvalidBoolType, InvalidCharType, InvalidCoreType, InvalidEnumType,
+	InvalidFunctionType, InvalidGenericType, InvalidIntegerType, InvalidOpaqueType, InvalidOptionType, InvalidResultType,
+	InvalidStringType, InvalidStructType, InvalidTupleStructType, InvalidTupleType, InvalidUnitStructType,
+	OpaqueCreationError, Overflow, RuntimeError, StringCreationError, StructCreationError, TupleCreationError,
+	TupleStructCreationError, TypeRegistrationError, UnitStructCreationError, ValueCreationError, VariableInsertionError,
 };
+
+pub use crate::value::SourceError;
+
+#[cfg(feature = "serde")]
+pub use crate::value::{DeserializerError, SerializerError};
@@ -16,9 +16,36 @@ pub enum RuntimeError {
 	#[error("Invalid number of arguments: {actual} (expected {expected} arguments, including `self`)")]
 	InvalidNumberOfArguments { actual: usize, expected: usize },
 
+	#[error("Argument {argument_number} could not be converted")]
+	InvalidArgumentValue {
+		argument_number: usize,
+		#[source]
+		source: ConversionError,
+	},
+
+	#[error("Type `{type_name}` does not provide the member function `{function_name}`")]
+	MissingMemberFunction { type_name: String, function_name: String },
+
+	#[error("Type `{type_name}` does not provide the associated function `{function_name}`")]
+	MissingAssociatedFunction { type_name: String, function_name: String },
+
+	#[error("Type `{type_name}` does not implement the interface `{interface_name}`")]
+	MissingInterface { type_name: String, interface_name: String },
+
+	#[error("Member function `{function_name}` of type `{type_name}` returned a value of type `{actual_type_name}`, but type `{expected_type_name}` was expected instead")]
+	InvalidReturnType {
+		type_name: String,
+		function_name: String,
+		actual_type_name: String,
+		expected_type_name: String,
+	},
+
 	#[error("Access out of bounds at {index} (len was {len})")]
 	OutOfBounds { index: usize, len: usize },
 
+	#[error("Values of type `{type_name}` cannot be formatted with `{{:{format_spec}}}`")]
+	UnsupportedFormat { type_name: String, format_spec: String },
+
 	#[error("An operation overflowed")]
 	Overflow(#[from] Overflow),
 
@@ -28,6 +55,15 @@ pub enum RuntimeError {
 	#[error("Could not create object")]
 	ValueCreationError(#[from] ValueCreationError),
 
+	#[error(transparent)]
+	TypeRegistrationError(#[from] TypeRegistrationError),
+
+	#[error(transparent)]
+	AssertionFailed(#[from] AssertionFailed),
+
+	#[error("The function `{function_name}` that was declared by a script failed: {message}")]
+	ScriptFunctionFailed { function_name: String, message: String },
+
 	#[error("Panic!")]
 	Panic(#[from] anyhow::Error),
 }
@@ -104,6 +140,111 @@ impl From<ArrayCreationError> for RuntimeError {
 	}
 }
 
+impl From<OpaqueCreationError> for RuntimeError {
+	fn from(value: OpaqueCreationError) -> Self {
+		RuntimeError::ValueCreationError(value.into())
+	}
+}
+
+impl From<FunctionCreationError> for RuntimeError {
+	fn from(value: FunctionCreationError) -> Self {
+		RuntimeError::ValueCreationError(value.into())
+	}
+}
+
+/**
+The failure of one of the assertion macros. The condition of `assert!` is recorded as it was written, while the operands
+of `assert_eq!` and `assert_ne!` are recorded using the `Debug` representation of `Value`. The message is only formatted
+if the assertion fails.
+
+```rust
+# use ralik::eval_str;
+let context = ralik::Context::new();
+assert!(eval_str(r#"assert_eq!((1, "a"), (1, "a"), "{}", does_not_exist())"#, &context).is_ok());
+
+let error = eval_str("assert!([1, 2].len() == 3)", &context).unwrap_err();
+let failure = std::error::Error::source(&error).unwrap().to_string();
+assert_eq!(failure, "Assertion `[1, 2].len() == 3` failed");
+let error = eval_str(r#"assert!(false, "{} is {}", "it", false)"#, &context).unwrap_err();
+let failure = std::error::Error::source(&error).unwrap().to_string();
+assert_eq!(failure, "Assertion `false` failed: it is false");
+
+let error = eval_str(r#"assert_eq!((1, "a"), (1, "b"), "{} differs", "the second element")"#, &context).unwrap_err();
+let failure = std::error::Error::source(&error).unwrap().to_string();
+let expected = "Assertion `left == right` failed: the second element differs\n  left: (1, \"a\")\n right: (1, \"b\")";
+assert_eq!(failure, expected);
+```
+*/
+#[derive(Error, Debug)]
+pub enum AssertionFailed {
+	#[error("Assertion `{condition}` failed{}", message_suffix(.message))]
+	Assertion { condition: String, message: Option<String> },
+
+	#[error("Assertion `left == right` failed{}\n  left: {left}\n right: {right}", message_suffix(.message))]
+	Equal {
+		left: String,
+		right: String,
+		message: Option<String>,
+	},
+
+	#[error("Assertion `left != right` failed{}\n  left: {left}\n right: {right}", message_suffix(.message))]
+	NotEqual {
+		left: String,
+		right: String,
+		message: Option<String>,
+	},
+}
+
+fn message_suffix(message: &Option<String>) -> String {
+	match message {
+		Some(message) => format!(": {}", message),
+		None => String::new(),
+	}
+}
+
+/// The failure to convert a `Value` into a Rust value via `FromValue`.
+#[derive(Error, Debug)]
+pub enum ConversionError {
+	#[error("A value of type `{actual_type_name}` cannot be converted to type `{expected_type_name}`")]
+	TypeMismatch {
+		actual_type_name: String,
+		expected_type_name: String,
+	},
+
+	#[error("The integer {value} does not fit into a `{target_type_name}`")]
+	OutOfRange {
+		value: String,
+		target_type_name: &'static str,
+	},
+}
+
+impl ConversionError {
+	pub fn type_mismatch(value: &crate::Value, expected_type_name: impl Into<String>) -> Self {
+		ConversionError::TypeMismatch {
+			actual_type_name: (*value.get_type().name()).into(),
+			expected_type_name: expected_type_name.into(),
+		}
+	}
+
+	/// Attributes the failed conversion to the argument `argument_number` of a function call.
+	pub fn into_argument_error(self, argument_number: usize) -> RuntimeError {
+		match self {
+			ConversionError::TypeMismatch {
+				actual_type_name,
+				expected_type_name,
+			} => RuntimeError::InvalidArgumentType {
+				argument_number,
+				actual_type_name,
+				expected_type_name,
+			},
+			source => RuntimeError::InvalidArgumentValue {
+				argument_number,
+				source,
+			},
+		}
+	}
+}
+
 #[derive(Error, Debug)]
 pub enum Overflow {
 	#[error("Negative shifts overflow by definition")]
@@ -156,6 +297,12 @@ pub enum ValueCreationError {
 
 	#[error("Could not create object of array type")]
 	ArrayCreationError(#[from] ArrayCreationError),
+
+	#[error("Could not create object of opaque type")]
+	OpaqueCreationError(#[from] OpaqueCreationError),
+
+	#[error("Could not create object of type `{}`", crate::types::make_function_name())]
+	FunctionCreationError(#[from] FunctionCreationError),
 }
 
 #[derive(Error, Debug)]
@@ -252,6 +399,18 @@ pub enum UnitStructCreationError {
 	FieldProvided { r#type: TypeHandle, field_name: String },
 }
 
+#[derive(Error, Debug)]
+pub enum OpaqueCreationError {
+	#[error("Type is not a valid opaque type")]
+	InvalidType(#[from] InvalidOpaqueType),
+}
+
+#[derive(Error, Debug)]
+pub enum FunctionCreationError {
+	#[error("Core type `{}` is invalid", crate::types::make_function_name())]
+	InvalidType(#[from] InvalidFunctionType),
+}
+
 #[derive(Error, Debug)]
 pub enum EnumUnitVariantCreationError {
 	#[error("Type is not a valid struct type")]
@@ -336,6 +495,9 @@ pub enum InvalidCoreType {
 	InvalidStringType(#[from] InvalidStringType),
 	InvalidTupleType(#[from] InvalidTupleType),
 	InvalidArrayType(#[from] InvalidArrayType),
+	InvalidOptionType(#[from] InvalidOptionType),
+	InvalidResultType(#[from] InvalidResultType),
+	InvalidFunctionType(#[from] InvalidFunctionType),
 }
 
 #[derive(Error, Debug)]
@@ -374,12 +536,21 @@ pub enum InvalidStringType {
 	Missing,
 }
 
+#[derive(Error, Debug)]
+pub enum InvalidFunctionType {
+	#[error(
+		"The given context does not have a type `{}` registered",
+		crate::types::make_function_name()
+	)]
+	Missing,
+}
+
 #[derive(Error, Debug)]
 pub enum InvalidTupleType {
-	#[error("No array generic is registered to the given context")]
+	#[error("No tuple generic is registered to the given context")]
 	MissingGeneric,
 
-	#[error("No array generic was not successful in creating the tuple `{}`", .name)]
+	#[error("The tuple generic was not successful in creating the tuple `{}`", .name)]
 	GenericFailed {
 		name: String,
 		#[source]
@@ -410,14 +581,107 @@ pub enum InvalidTupleStructType {
 
 #[derive(Error, Debug)]
 pub enum InvalidOptionType {
-	#[error("The given context does not have the type `{element_type_name}` registered to make an array out of")]
-	MissingSubtype { element_type_name: String },
+	#[error("No option generic is registered to the given context")]
+	MissingGeneric,
 
-	#[error("The value `{value:?}` does not have the right type to be used in an array of type `{type_name}` (error occurred at index {index})")]
-	InvalidElement {
-		value: crate::Value,
-		index: usize,
+	#[error("The option generic was not successful in creating the option `{}`", .name)]
+	GenericFailed {
+		name: String,
+		#[source]
+		error: anyhow::Error,
+	},
+}
+
+#[derive(Error, Debug)]
+pub enum InvalidResultType {
+	#[error("No result generic is registered to the given context")]
+	MissingGeneric,
+
+	#[error("The result generic was not successful in creating the result `{}`", .name)]
+	GenericFailed {
+		name: String,
+		#[source]
+		error: anyhow::Error,
+	},
+}
+
+#[derive(Error, Debug)]
+pub enum TypeRegistrationError {
+	#[error("The name `{name}` is already in use")]
+	NameInUse { name: String },
+
+	#[error("The type `{dependent_type_name}` that is used by the type `{type_name}` could not be resolved")]
+	UnresolvedType {
+		type_name: String,
+		dependent_type_name: String,
+	},
+
+	#[error(
+		"The name `{dependent_type_name}` that is used as a type by the type `{type_name}` does not refer to a type"
+	)]
+	NotAType {
 		type_name: String,
+		dependent_type_name: String,
+	},
+
+	#[error("The type `{type_name}` implements the interface `{interface_name}`, which is not registered")]
+	MissingInterface { type_name: String, interface_name: String },
+
+	#[error("The type `{type_name}` does not provide the member function `{function_name}`, which is required by the interface `{interface_name}`")]
+	MissingRequiredFunction {
+		type_name: String,
+		interface_name: String,
+		function_name: String,
+	},
+
+	#[error("The type `{type_name}` already provides the function `{function_name}`")]
+	FunctionInUse { type_name: String, function_name: String },
+}
+
+impl TypeRegistrationError {
+	/// The name of the type that could not be registered or extended.
+	pub fn type_name(&self) -> &str {
+		match self {
+			TypeRegistrationError::NameInUse { name } => name,
+			TypeRegistrationError::UnresolvedType { type_name, .. }
+			| TypeRegistrationError::NotAType { type_name, .. }
+			| TypeRegistrationError::MissingInterface { type_name, .. }
+			| TypeRegistrationError::MissingRequiredFunction { type_name, .. }
+			| TypeRegistrationError::FunctionInUse { type_name, .. } => type_name,
+		}
+	}
+}
+
+#[derive(Error, Debug)]
+pub enum VariableInsertionError {
+	#[error("The name `{name}` is defined multiple times")]
+	NameInUse { name: String },
+}
+
+#[derive(Error, Debug)]
+pub enum InvalidGenericType {
+	#[error("No generic `{generic_name}` is registered to the given context")]
+	MissingGeneric { generic_name: String },
+
+	#[error("The given context does not have the type `{type_parameter}` registered to instantiate the generic `{generic_name}` with")]
+	MissingSubtype {
+		generic_name: String,
+		type_parameter: String,
+	},
+
+	#[error("The generic `{generic_name}` was not successful in creating the type `{name}`")]
+	GenericFailed {
+		generic_name: String,
+		name: String,
+		#[source]
+		error: anyhow::Error,
+	},
+
+	#[error("The type `{name}` created by a generic could not be registered")]
+	RegistrationFailed {
+		name: String,
+		#[source]
+		error: TypeRegistrationError,
 	},
 }
 
@@ -426,7 +690,7 @@ pub enum InvalidArrayType {
 	#[error("No array generic is registered to the given context")]
 	MissingGeneric,
 
-	#[error("No array generic was not successful in creating the array `{}`", .name)]
+	#[error("The array generic was not successful in creating the array `{}`", .name)]
 	GenericFailed {
 		name: String,
 		#[source]
@@ -453,6 +717,18 @@ pub enum InvalidUnitStructType {
 	NotUnitStructType { r#type: TypeHandle },
 }
 
+#[derive(Error, Debug)]
+pub enum InvalidOpaqueType {
+	#[error("The given context does not have the opaque type `{type_name}` registered")]
+	Missing { type_name: String },
+
+	#[error("The type `{}` does not have the kind `TypeKind::Opaque`", .r#type.name())]
+	NotOpaqueType { r#type: TypeHandle },
+
+	#[error("The opaque type `{}` wraps a different Rust type", .r#type.name())]
+	WrongRustType { r#type: TypeHandle },
+}
+
 #[derive(Error, Debug)]
 pub enum InvalidStructType {
 	#[error("The given context does not have the structure type `{type_name}` registered")]
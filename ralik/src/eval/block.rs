@@ -0,0 +1,226 @@
+use std::collections::HashSet;
+
+use crate::types::TypeKind;
+use crate::{Context, Value};
+
+use super::super::ast::{AtomicExpression, Block, Expression, Item, Pattern, Statement, Suffix, Type};
+use super::global::declare_globals;
+use super::implementation::implement_types;
+use super::item::declare_types;
+use super::types::{check_integer_ranges, resolve_type};
+use super::{Eval, EvalError};
+
+/**
+Declares the items of the given expressions before they are evaluated: first their types, then the functions of their
+`impl` blocks and finally their constants and statics. As in Rust, this includes the items of nested blocks and of
+function bodies, which are thus declared once, no matter how often the blocks that contain them are evaluated.
+
+```rust
+# use ralik::eval_str;
+let context = ralik::Context::new();
+let source = "{ struct Q; impl Q { fn f(self) -> Meters { struct Meters(Integer); Meters(1) } } (Q.f(), Q.f()) }";
+assert_eq!(eval_str(source, &context).unwrap().to_string(), "(Meters(1), Meters(1))");
+let source = "{ struct R; impl R { fn g(self) -> Integer { { struct Unit; 2 } } } R.g() + R.g() }";
+assert_eq!(eval_str(source, &context).unwrap().as_i32(), Some(4));
+
+// evaluating the same items again reuses their declarations, but a different item of the same name is an error
+let source = "{ struct P; impl P { fn one(self) -> Integer { 1 } } const ONE: Integer = P.one(); dbg!({ struct D; ONE }) }";
+assert_eq!(eval_str(source, &context).unwrap().as_i32(), Some(1));
+assert_eq!(eval_str(source, &context).unwrap().as_i32(), Some(1));
+let err = eval_str("{ struct P(Integer); 1 }", &context).unwrap_err();
+assert_eq!(err.to_string(), "The name `P` is already in use when declared at 1:9");
+```
+*/
+pub(crate) fn declare_items<'a>(
+	context: &Context,
+	expressions: impl IntoIterator<Item = &'a Expression>,
+) -> Result<(), EvalError> {
+	let mut items = Vec::new();
+	for expression in expressions {
+		collect_items(context, expression, &mut items);
+	}
+	// an item that was declared before, e.g., by evaluating the same block again, is not declared another time
+	let mut sources = HashSet::new();
+	items
+		.retain(|item| item.source().is_empty() || (!context.is_declared(item.source()) && sources.insert(item.source())));
+	let sources = |is_kind: fn(&Item) -> bool| items.iter().filter(move |item| is_kind(item)).map(|item| item.source());
+
+	declare_types(context, &items)?;
+	context.add_declared(sources(|item| matches!(item, Item::Struct(_) | Item::Enum(_))));
+	implement_types(context, &items)?;
+	context.add_declared(sources(|item| matches!(item, Item::Impl(_))));
+	declare_globals(context, &items)?;
+	context.add_declared(sources(|item| matches!(item, Item::Const(_) | Item::Static(_))));
+	Ok(())
+}
+
+/// Collects the items of an expression. The items that are nested in an item come first, so that constants can use the
+/// constants that are declared in their initializer.
+fn collect_items<'a>(context: &Context, expression: &'a Expression, items: &mut Vec<&'a Item>) {
+	match expression {
+		Expression::Atomic(expression) => collect_atomic_items(context, expression, items),
+		Expression::Suffix(expression, suffix) => {
+			collect_items(context, expression, items);
+			match suffix {
+				Suffix::ArrayIndex(index, _span) => collect_items(context, index, items),
+				Suffix::FunctionCall(_name, _name_span, _generic_arguments, arguments, _arguments_span) => arguments
+					.arguments
+					.iter()
+					.for_each(|argument| collect_items(context, argument, items)),
+				Suffix::Unwrap(_) | Suffix::Field(_, _) | Suffix::TupleIndex(_, _) => (),
+			}
+		}
+		Expression::Prefix(expression, _prefix) => collect_items(context, expression, items),
+		Expression::Binary(lhs, rhs, _op) => {
+			collect_items(context, lhs, items);
+			collect_items(context, rhs, items);
+		}
+		Expression::Range(start, _limits, end) => start
+			.iter()
+			.chain(end)
+			.for_each(|bound| collect_items(context, bound, items)),
+		Expression::Cast(expression, _as_span, _type) => collect_items(context, expression, items),
+		Expression::Block(block) | Expression::Loop(_, _, block) => collect_block_items(context, block, items),
+		Expression::If(_, condition, block)
+		| Expression::Else(condition, _, block)
+		| Expression::While(_, condition, block) => {
+			collect_items(context, condition, items);
+			collect_block_items(context, block, items);
+		}
+	}
+}
+
+fn collect_atomic_items<'a>(context: &Context, expression: &'a AtomicExpression, items: &mut Vec<&'a Item>) {
+	let expressions: Box<dyn Iterator<Item = &Expression>> = match expression {
+		AtomicExpression::Parenthesized(expression, _span) => Box::new(std::iter::once(&**expression)),
+		AtomicExpression::Tuple(elements, _span) | AtomicExpression::Array(elements, _span) => Box::new(elements.iter()),
+		AtomicExpression::FunctionCall(_, _, arguments, _arguments_span)
+		| AtomicExpression::PathCall(_, arguments, _arguments_span) => Box::new(arguments.arguments.iter()),
+		AtomicExpression::StructLiteral(_path, fields, _span) => Box::new(fields.iter().map(|field| &field.expression)),
+		// the arguments of lazy macros and of macros with a format string are only parsed when the macro is called
+		AtomicExpression::MacroCall(name, _name_span, arguments, _arguments_span)
+			if context.get_lazy_macro(name).is_none() && context.get_format_string_index(name).is_none() =>
+		{
+			Box::new(
+				arguments
+					.arguments
+					.iter()
+					.flat_map(|arguments| arguments.arguments.iter()),
+			)
+		}
+		AtomicExpression::MacroCall(_, _, _, _) => Box::new(std::iter::empty()),
+		AtomicExpression::Unit(_)
+		| AtomicExpression::LitBool(_, _)
+		| AtomicExpression::LitInt(_, _)
+		| AtomicExpression::LitByte(_, _)
+		| AtomicExpression::LitByteStr(_, _)
+		| AtomicExpression::LitChar(_, _)
+		| AtomicExpression::LitStr(_, _)
+		| AtomicExpression::Dollar(_)
+		| AtomicExpression::Path(_) => Box::new(std::iter::empty()),
+	};
+	expressions.for_each(|expression| collect_items(context, expression, items));
+}
+
+fn collect_block_items<'a>(context: &Context, block: &'a Block, items: &mut Vec<&'a Item>) {
+	for statement in &block.statements {
+		match statement {
+			Statement::Expression(expression) => collect_items(context, expression, items),
+			Statement::Let(_let_span, _is_mut, _pattern, _type, expression) => expression
+				.iter()
+				.for_each(|expression| collect_items(context, expression, items)),
+			Statement::Item(item) => {
+				match item {
+					Item::Impl(item) => item
+						.functions
+						.iter()
+						.for_each(|function| collect_block_items(context, &function.body, items)),
+					Item::Const(item) | Item::Static(item) => collect_items(context, &item.expression, items),
+					Item::Struct(_) | Item::Enum(_) => (),
+				}
+				items.push(item);
+			}
+		}
+	}
+	block
+		.expression
+		.iter()
+		.for_each(|expression| collect_items(context, expression, items));
+}
+
+/**
+Evaluates a block, in which each `let` statement binds a local variable that is visible in the remainder of the block.
+Its items have already been declared by `declare_items`. The value of the block is the value of its final expression, or
+`()` if there is none.
+
+```rust
+# use ralik::eval_str;
+let context = ralik::Context::new();
+let value = eval_str("{ let x = 20; let x: Integer = x + 1; x * 2 }", &context).unwrap();
+assert_eq!(value.as_i32(), Some(42));
+assert_eq!(eval_str("{ let _ = 1; }", &context).unwrap(), ralik::Value::new_unit(&context).unwrap());
+assert!(eval_str("{ let x: char = 1; x }", &context).is_err());
+assert!(eval_str("({ let x = 1; x }, x)", &context).is_err());
+```
+*/
+pub(crate) fn eval_block(context: &Context, block: &Block) -> Result<Value, EvalError> {
+	let mut context = context.clone();
+	for statement in &block.statements {
+		match statement {
+			Statement::Item(_item) => (),
+			Statement::Expression(expression) => {
+				expression.eval(&context)?;
+			}
+			Statement::Let(_let_span, _is_mut, pattern, r#type, expression) => {
+				// the parser ensures that `let` statements always have an initializer
+				let expression = expression.as_ref().unwrap();
+				let value = match r#type {
+					Some(r#type) => eval_with_type(&context, expression, r#type)?,
+					None => expression.eval(&context)?,
+				};
+				match &**pattern {
+					Pattern::Identifier(name, _span) => context = context.with_local(name, value),
+					Pattern::Wildcard(_span) => (),
+					pattern => unreachable!("`let` cannot bind {:?}", pattern),
+				}
+			}
+		}
+	}
+
+	match &block.expression {
+		Some(expression) => expression.eval(&context),
+		None => Value::new_unit(&context).map_err(|err| EvalError::ObjectCreationError {
+			source: Box::new(err.into()),
+			at: block.span,
+		}),
+	}
+}
+
+/// Evaluates an expression whose type is given by an ascription, e.g., in `let x: Integer = 1;`.
+pub(crate) fn eval_with_type(context: &Context, expression: &Expression, r#type: &Type) -> Result<Value, EvalError> {
+	let written_type = r#type;
+	let r#type = resolve_type(context, r#type)?;
+	let value = match expression {
+		// the element type of an empty array literal is given by the ascription
+		Expression::Atomic(AtomicExpression::Array(elements, span))
+			if elements.is_empty() && r#type.kind() == TypeKind::Array =>
+		{
+			Value::new_array(context, &r#type.type_parameter(0), Vec::new()).map_err(|err| {
+				EvalError::ObjectCreationError {
+					source: Box::new(err.into()),
+					at: *span,
+				}
+			})?
+		}
+		expression => expression.eval(context)?,
+	};
+	if !value.has_type(&r#type) {
+		return Err(EvalError::MismatchedType {
+			expected_type_name: (*r#type.name()).into(),
+			actual_type_name: (*value.get_type().name()).into(),
+			at: expression.span(),
+		});
+	}
+	check_integer_ranges(context, &value, written_type, expression.span())?;
+	Ok(value)
+}
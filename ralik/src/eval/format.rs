@@ -0,0 +1,245 @@
+use num::{BigInt, Signed, ToPrimitive};
+
+use super::super::ast::{FormatAlign, FormatCount, FormatPiece, FormatSpec, FormatString, FormatTrait};
+use crate::error::{Overflow, RuntimeError};
+use crate::types::TypeKind;
+use crate::{Context, Value};
+
+/**
+The member function that a type can provide to take over its formatting.
+
+It is called with the value and the format spec without width, fill and alignment (e.g., `""` for `{}`, `"#?"` for
+`{:#?}` or `"+.3"` for `{:>+8.3}`) and must return a `std::string::String`, which is then padded as requested.
+*/
+const FMT: &str = "fmt";
+
+pub(super) fn format(
+	context: &Context,
+	format_string: &FormatString,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	let mut result = String::new();
+	for piece in &format_string.pieces {
+		match piece {
+			FormatPiece::Literal(literal) => result.push_str(literal),
+			FormatPiece::Placeholder { argument, spec } => {
+				let value = &arguments[*argument];
+				let width = match spec.width {
+					Some(count) => Some(resolve_count(count, arguments)?),
+					None => None,
+				};
+				let precision = match spec.precision {
+					Some(count) => Some(resolve_count(count, arguments)?),
+					None => None,
+				};
+				let body = render(context, value, spec, precision)?;
+				pad(&mut result, value, &body, spec, width);
+			}
+		}
+	}
+	Ok(Value::new_string(context, result)?)
+}
+
+fn resolve_count(count: FormatCount, arguments: &[Value]) -> Result<usize, RuntimeError> {
+	match count {
+		FormatCount::Is(value) => Ok(value),
+		FormatCount::Argument(index) => match arguments[index].as_integer() {
+			Some(value) => value.to_usize().ok_or_else(|| Overflow::USize.into()),
+			None => Err(RuntimeError::InvalidArgumentType {
+				argument_number: index,
+				actual_type_name: (*arguments[index].get_type().name()).into(),
+				expected_type_name: crate::types::make_integer_name().into(),
+			}),
+		},
+	}
+}
+
+fn render(
+	context: &Context,
+	value: &Value,
+	spec: &FormatSpec,
+	precision: Option<usize>,
+) -> Result<String, RuntimeError> {
+	let r#type = value.get_type();
+	if r#type.get_function(FMT).is_some() {
+		let spec = Value::new_string(context, hook_spec(spec, precision))?;
+		return r#type.call_string_function(context, FMT, &[value.clone(), spec]);
+	}
+
+	match (spec.r#trait, r#type.kind()) {
+		(FormatTrait::Debug, _) => {
+			if r#type.implements("Debug") {
+				r#type.call_debug(context, value, spec.alternate)
+			} else if spec.alternate {
+				Ok(format!("{:#?}", value))
+			} else {
+				Ok(format!("{:?}", value))
+			}
+		}
+		(_, TypeKind::Integer) => Ok(format_integer(value.as_integer().unwrap(), spec, precision)),
+		(FormatTrait::Display, TypeKind::Bool) => Ok(truncate(&value.as_bool().unwrap().to_string(), precision)),
+		(FormatTrait::Display, TypeKind::Char) => Ok(truncate(&value.as_char().unwrap().to_string(), precision)),
+		(FormatTrait::Display, TypeKind::String) => Ok(truncate(value.as_string().unwrap(), precision)),
+		(FormatTrait::Display, _) => {
+			if r#type.implements("Display") {
+				r#type.call_string_function(context, "to_string", std::slice::from_ref(value))
+			} else {
+				Ok(value.to_string())
+			}
+		}
+		_ => Err(RuntimeError::UnsupportedFormat {
+			type_name: (*r#type.name()).into(),
+			format_spec: hook_spec(spec, precision),
+		}),
+	}
+}
+
+fn hook_spec(spec: &FormatSpec, precision: Option<usize>) -> String {
+	let mut result = String::new();
+	if spec.sign_plus {
+		result.push('+');
+	} else if spec.sign_minus {
+		result.push('-');
+	}
+	if spec.alternate {
+		result.push('#');
+	}
+	if let Some(precision) = precision {
+		result.push('.');
+		result.push_str(&precision.to_string());
+	}
+	result.push_str(match spec.r#trait {
+		FormatTrait::Display => "",
+		FormatTrait::Debug => "?",
+		FormatTrait::LowerHex => "x",
+		FormatTrait::UpperHex => "X",
+		FormatTrait::Binary => "b",
+		FormatTrait::Octal => "o",
+		FormatTrait::LowerExp => "e",
+		FormatTrait::UpperExp => "E",
+	});
+	result
+}
+
+fn truncate(value: &str, precision: Option<usize>) -> String {
+	match precision {
+		Some(precision) => value.chars().take(precision).collect(),
+		None => value.to_owned(),
+	}
+}
+
+fn format_integer(value: &BigInt, spec: &FormatSpec, precision: Option<usize>) -> String {
+	let result = match (spec.r#trait, spec.alternate) {
+		(FormatTrait::Display, _) | (FormatTrait::Debug, _) => value.to_string(),
+		(FormatTrait::LowerHex, false) => format!("{:x}", value),
+		(FormatTrait::LowerHex, true) => format!("{:#x}", value),
+		(FormatTrait::UpperHex, false) => format!("{:X}", value),
+		(FormatTrait::UpperHex, true) => format!("{:#X}", value),
+		(FormatTrait::Binary, false) => format!("{:b}", value),
+		(FormatTrait::Binary, true) => format!("{:#b}", value),
+		(FormatTrait::Octal, false) => format!("{:o}", value),
+		(FormatTrait::Octal, true) => format!("{:#o}", value),
+		(FormatTrait::LowerExp, _) => format_exponential(value, precision, 'e'),
+		(FormatTrait::UpperExp, _) => format_exponential(value, precision, 'E'),
+	};
+	if spec.sign_plus && !value.is_negative() {
+		format!("+{}", result)
+	} else {
+		result
+	}
+}
+
+/// Formats an integer in scientific notation, e.g., `1.5e3`, rounding half to even when a precision is given.
+fn format_exponential(value: &BigInt, precision: Option<usize>, exponent_marker: char) -> String {
+	let mut mantissa = value
+		.abs()
+		.to_string()
+		.bytes()
+		.map(|digit| digit - b'0')
+		.collect::<Vec<u8>>();
+	let mut exponent = mantissa.len() - 1;
+	match precision {
+		None => {
+			while mantissa.len() > 1 && mantissa.last() == Some(&0) {
+				mantissa.pop();
+			}
+		}
+		Some(precision) if mantissa.len() > precision + 1 => {
+			let dropped = mantissa.split_off(precision + 1);
+			let round_up = match dropped[0] {
+				0..=4 => false,
+				5 => dropped[1..].iter().any(|&digit| digit != 0) || mantissa[precision] % 2 == 1,
+				_ => true,
+			};
+			if round_up {
+				match mantissa.iter().rposition(|&digit| digit != 9) {
+					Some(position) => {
+						mantissa[position] += 1;
+						mantissa[position + 1..].iter_mut().for_each(|digit| *digit = 0);
+					}
+					None => {
+						mantissa.iter_mut().for_each(|digit| *digit = 0);
+						mantissa[0] = 1;
+						exponent += 1;
+					}
+				}
+			}
+		}
+		Some(precision) => mantissa.resize(precision + 1, 0),
+	}
+
+	let mut result = String::new();
+	if value.is_negative() {
+		result.push('-');
+	}
+	result.push(char::from(b'0' + mantissa[0]));
+	if mantissa.len() > 1 {
+		result.push('.');
+		result.extend(mantissa[1..].iter().map(|&digit| char::from(b'0' + digit)));
+	}
+	result.push(exponent_marker);
+	result.push_str(&exponent.to_string());
+	result
+}
+
+fn pad(result: &mut String, value: &Value, body: &str, spec: &FormatSpec, width: Option<usize>) {
+	let padding = match width {
+		Some(width) => width.saturating_sub(body.chars().count()),
+		None => 0,
+	};
+	if padding == 0 {
+		result.push_str(body);
+		return;
+	}
+
+	let numeric = value.get_type().kind() == TypeKind::Integer;
+	if spec.zero && numeric {
+		// sign-aware zero padding goes between the sign and radix prefix, and the digits
+		let mut prefix_len = if body.starts_with('+') || body.starts_with('-') {
+			1
+		} else {
+			0
+		};
+		if spec.alternate && body[prefix_len..].starts_with('0') && body.len() > prefix_len + 1 {
+			if let Some(b'x' | b'X' | b'b' | b'o') = body.as_bytes().get(prefix_len + 1) {
+				prefix_len += 2;
+			}
+		}
+		result.push_str(&body[..prefix_len]);
+		result.extend((0..padding).map(|_| '0'));
+		result.push_str(&body[prefix_len..]);
+		return;
+	}
+
+	let fill = spec.fill.unwrap_or(' ');
+	let (before, after) = match spec.align {
+		Some(FormatAlign::Left) => (0, padding),
+		Some(FormatAlign::Center) => (padding / 2, padding - padding / 2),
+		Some(FormatAlign::Right) => (padding, 0),
+		None if numeric => (padding, 0),
+		None => (0, padding),
+	};
+	result.extend((0..before).map(|_| fill));
+	result.push_str(body);
+	result.extend((0..after).map(|_| fill));
+}
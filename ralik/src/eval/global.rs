@@ -0,0 +1,78 @@
+use crate::error::VariableInsertionError;
+use crate::Context;
+
+use super::super::ast::Item;
+use super::block::eval_with_type;
+use super::EvalError;
+
+/**
+Evaluates the `const` and `static` items of a block in order and registers them as global variables, which remain
+visible to all expressions that are evaluated in the same context afterwards. Their initializers cannot refer to local
+variables. If any item fails to evaluate or its name is in use, none of the items are registered. Items may also be
+declared at the top level of the evaluated source.
+
+```rust
+# use ralik::eval_str;
+let context = ralik::Context::new();
+let value = eval_str(
+r#"{
+const LIMIT: Integer = 10;
+static NAME: &str = "x";
+static LIMITS: Vec<Integer> = [LIMIT, 2 * LIMIT];
+format!("{}{}", NAME, LIMITS[1])
+}"#,
+&context,
+)
+.unwrap();
+assert_eq!(value.as_string(), Some("x20"));
+assert_eq!(eval_str("LIMIT + 1", &context).unwrap().as_i32(), Some(11));
+
+let err = eval_str("{ const LIMIT: Integer = 5; }", &context).unwrap_err();
+assert_eq!(err.to_string(), "The name `LIMIT` is already in use when declared at 1:8 to 1:13");
+assert!(eval_str("{ const EMPTY: String = 1; }", &context).is_err());
+assert!(eval_str("{ let x = 1; { const X: Integer = x; } }", &context).is_err());
+assert!(eval_str("{ const B1: Integer = 1; const B2: char = 1; 0 }", &context).is_err());
+assert!(context.get_variable("B1").is_none());
+
+let value = eval_str("const TOP: Integer = 1; let x = TOP + 1; x", &context).unwrap();
+assert_eq!(value.as_i32(), Some(2));
+assert_eq!(eval_str("TOP", &context).unwrap().as_i32(), Some(1));
+
+// the items in the bodies of functions are declared once with the function
+let value = eval_str(
+	"{ struct Q; impl Q { fn f(self) -> Integer { const A: Integer = 1; A } } (Q.f(), Q.f()) }",
+	&context,
+)
+.unwrap();
+assert_eq!(value.to_string(), "(1, 1)");
+```
+*/
+pub(crate) fn declare_globals(context: &Context, items: &[&Item]) -> Result<(), EvalError> {
+	// the items are only inserted once all of them were evaluated, until then the initializers see the previous items as
+	// local variables
+	let mut global_context = context.without_locals();
+	let mut globals = Vec::new();
+	for item in items {
+		let item = match item {
+			Item::Const(item) | Item::Static(item) => item,
+			_ => continue,
+		};
+		let value = eval_with_type(&global_context, &item.expression, &item.r#type)?;
+		global_context = global_context.with_local(&item.name, value.clone());
+		globals.push((item, value));
+	}
+
+	let variables = globals
+		.iter()
+		.map(|(item, value)| (item.name.clone(), value.clone()))
+		.collect();
+	context
+		.try_insert_variables(variables)
+		.map_err(|VariableInsertionError::NameInUse { name }| {
+			let (item, _value) = globals.iter().find(|(item, _value)| item.name == name).unwrap();
+			EvalError::DuplicateName {
+				name,
+				at: item.name_span,
+			}
+		})
+}
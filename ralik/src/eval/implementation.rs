@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::Arc;
+
+use crate::error::RuntimeError;
+use crate::types::{Arguments, NativeMemberFunction};
+use crate::{Context, TypeHandle, Value};
+
+use super::super::ast::{FunctionItem, ImplItem, Item};
+use super::block::eval_block;
+use super::types::{check_integer_ranges, resolve_type};
+use super::EvalError;
+
+/// The operator traits that `impl` blocks can implement, each given by its name, the name of its function and the
+/// member function that implements the operator.
+const OPERATOR_TRAITS: &[(&str, &str, &str)] = &[
+	("Add", "add", crate::ops::ADD),
+	("Sub", "sub", crate::ops::SUB),
+	("Mul", "mul", crate::ops::MUL),
+	("Div", "div", crate::ops::DIV),
+	("Rem", "rem", crate::ops::REM),
+	("Neg", "neg", crate::ops::NEGATE),
+	("Not", "not", crate::ops::NOT),
+	("BitAnd", "bitand", crate::ops::BIT_AND),
+	("BitOr", "bitor", crate::ops::BIT_OR),
+	("BitXor", "bitxor", crate::ops::BIT_XOR),
+	("Shl", "shl", crate::ops::SHL),
+	("Shr", "shr", crate::ops::SHR),
+	("Index", "index", crate::ops::INDEX),
+];
+
+/// The functions of the comparison interfaces, which are named as in the `PartialEq` and `PartialOrd` traits of Rust.
+const COMPARISONS: &[(&str, &str)] = &[
+	("eq", crate::ops::EQUAL),
+	("ne", crate::ops::NOT_EQUAL),
+	("lt", crate::ops::LESS),
+	("le", crate::ops::LESS_OR_EQUAL),
+	("gt", crate::ops::GREATER),
+	("ge", crate::ops::GREATER_OR_EQUAL),
+];
+
+/**
+Adds the functions of all `impl` blocks of a block to their types. Functions that take `self` become member functions,
+while the others become associated functions. Implementing an operator trait such as `Add` overloads the corresponding
+operator, and implementing an interface declares that the type implements it.
+
+```rust
+# use ralik::eval_str;
+let context = ralik::Context::new();
+let value = eval_str(
+r#"{
+struct Point { x: Integer, y: Integer }
+impl Point {
+fn origin() -> Point { Point { x: 0, y: 0 } }
+fn len2(&self) -> Integer { self.x * self.x + self.y * self.y }
+}
+impl Add for Point {
+fn add(self, other: Point) -> Point { Point { x: self.x + other.x, y: self.y + other.y } }
+}
+impl PartialEq for Point {
+fn eq(&self, other: Point) -> bool { self.x == other.x && self.y == other.y }
+}
+let p = Point { x: 1, y: 2 } + Point::origin() + Point { x: 2, y: 0 };
+(p.len2(), Point { x: 3, y: 2 } != p)
+}"#,
+&context,
+)
+.unwrap();
+assert_eq!(value.to_string(), "(13, false)");
+assert!(context.get_type("Point").unwrap().implements("PartialEq"));
+
+// the functions may be called on other threads, also from a different context
+let other_context = context.clone();
+let value = std::thread::spawn(move || eval_str("Point { x: 2, y: 1 }.len2()", &other_context).unwrap().to_string());
+assert_eq!(value.join().unwrap(), "5");
+
+let value = eval_str(
+	"{ struct Zero(Integer); impl Default for Zero { fn default() -> Zero { Zero(0) } } Zero::default().0 }",
+	&context,
+)
+.unwrap();
+assert_eq!(value.to_string(), "0");
+
+let err = eval_str("{ impl Point { fn len2(self) -> Integer { 0 } } }", &context).unwrap_err();
+assert_eq!(err.to_string(), "The name `len2` is already in use when declared at 1:18 to 1:22");
+let err = eval_str("{ impl Sub for Point { fn add(self, other: Point) -> Point { self } } }", &context).unwrap_err();
+assert_eq!(err.to_string(), "The function `add` is not a member of the trait `Sub` at 1:26 to 1:29");
+let err = eval_str("Point::origin().len2(1)", &context).unwrap_err();
+assert!(std::error::Error::source(&err).unwrap().to_string().starts_with("Invalid number of arguments"));
+```
+*/
+pub(crate) fn implement_types(context: &Context, items: &[&Item]) -> Result<(), EvalError> {
+	for item in items {
+		if let Item::Impl(item) = item {
+			implement_type(context, item)?;
+		}
+	}
+	Ok(())
+}
+
+fn implement_type(context: &Context, item: &ImplItem) -> Result<(), EvalError> {
+	let self_type = resolve_type(context, &item.self_type)?;
+
+	let (interfaces, trait_functions) = match &item.r#trait {
+		None => (Vec::new(), None),
+		Some((name, span)) => {
+			if let Some((_name, _function_name, operator)) =
+				OPERATOR_TRAITS.iter().find(|(trait_name, _, _)| trait_name == name)
+			{
+				(Vec::new(), Some(vec![(*operator).to_owned()]))
+			} else if let Some(interface) = context.get_interface(name) {
+				let functions = interface
+					.required_functions
+					.iter()
+					.chain(interface.required_associated_functions.iter())
+					.chain(interface.default_functions.keys())
+					.cloned()
+					.collect();
+				(vec![name.clone()], Some(functions))
+			} else {
+				return Err(EvalError::UnknownTrait {
+					name: name.clone(),
+					at: *span,
+				});
+			}
+		}
+	};
+
+	let mut functions = HashMap::new();
+	let mut associated_functions = HashMap::new();
+	for function in &item.functions {
+		let name = match &trait_functions {
+			None => function.name.clone(),
+			Some(trait_functions) => {
+				let name = OPERATOR_TRAITS
+					.iter()
+					.map(|(_trait_name, function_name, operator)| (function_name, operator))
+					.chain(
+						COMPARISONS
+							.iter()
+							.map(|(function_name, operator)| (function_name, operator)),
+					)
+					.find(|(function_name, _operator)| **function_name == function.name)
+					.map_or_else(
+						|| function.name.clone(),
+						|(_function_name, operator)| (*operator).to_owned(),
+					);
+				if !trait_functions.contains(&name) {
+					return Err(EvalError::NotATraitMember {
+						name: function.name.clone(),
+						trait_name: item.r#trait.as_ref().unwrap().0.clone(),
+						at: function.name_span,
+					});
+				}
+				name
+			}
+		};
+		if functions.contains_key(&name)
+			|| associated_functions.contains_key(&name)
+			|| self_type.get_function(&name).is_some()
+			|| self_type.get_associated_function(&name).is_some()
+		{
+			return Err(EvalError::DuplicateName {
+				name: function.name.clone(),
+				at: function.name_span,
+			});
+		}
+
+		// resolve the types once, so that they are reported when the function is declared instead of when it is called
+		for (_name, _span, r#type) in &function.parameters {
+			resolve_type(context, r#type)?;
+		}
+		if let Some(r#type) = &function.return_type {
+			resolve_type(context, r#type)?;
+		}
+
+		if function.receiver.is_some() {
+			functions.insert(name, new_script_function(function));
+		} else {
+			associated_functions.insert(name, new_script_function(function));
+		}
+	}
+
+	self_type
+		.extend(functions, associated_functions, interfaces)
+		.map_err(|source| EvalError::InvalidImpl {
+			type_name: (*self_type.name()).into(),
+			source: Box::new(source),
+			at: item.self_type.span,
+		})
+}
+
+fn new_script_function(function: &FunctionItem) -> NativeMemberFunction {
+	let function = Arc::new(function.clone());
+	NativeMemberFunction::Closure(Arc::new(move |context, this_type, arguments| {
+		call_script_function(context, this_type, arguments, &function)
+	}))
+}
+
+fn call_script_function(
+	context: &Context,
+	this_type: &TypeHandle,
+	arguments: &[Value],
+	function: &FunctionItem,
+) -> Result<Value, RuntimeError> {
+	let receivers = usize::from(function.receiver.is_some());
+	arguments.check_len(receivers + function.parameters.len())?;
+	// the body of the function cannot refer to the local variables of its caller
+	let mut local_context = context.without_locals();
+	if function.receiver.is_some() {
+		arguments.check_type(0, this_type)?;
+		local_context = local_context.with_local("self", arguments[0].clone());
+	}
+	for (index, (name, span, r#type)) in function.parameters.iter().enumerate() {
+		let written_type = r#type;
+		let r#type = resolve_type(context, r#type).map_err(|err| script_function_failed(function, err))?;
+		arguments.check_type(receivers + index, &r#type)?;
+		check_integer_ranges(context, &arguments[receivers + index], written_type, *span)
+			.map_err(|err| script_function_failed(function, err))?;
+		local_context = local_context.with_local(name, arguments[receivers + index].clone());
+	}
+
+	eval_function_body(&local_context, function).map_err(|err| script_function_failed(function, err))
+}
+
+fn eval_function_body(context: &Context, function: &FunctionItem) -> Result<Value, EvalError> {
+	let value = eval_block(context, &function.body)?;
+	let value_span = function
+		.body
+		.expression
+		.as_ref()
+		.map_or(function.body.span, |expression| expression.span());
+	let return_type = match &function.return_type {
+		Some(r#type) => resolve_type(context, r#type)?,
+		None => context.get_unit_type().map_err(|err| EvalError::InvalidCoreType {
+			source: Box::new(err.into()),
+			at: function.body.span,
+		})?,
+	};
+	if !value.has_type(&return_type) {
+		return Err(EvalError::MismatchedType {
+			expected_type_name: (*return_type.name()).into(),
+			actual_type_name: (*value.get_type().name()).into(),
+			at: value_span,
+		});
+	}
+	if let Some(r#type) = &function.return_type {
+		check_integer_ranges(context, &value, r#type, value_span)?;
+	}
+	Ok(value)
+}
+
+/// Converts an error that occurred while evaluating a script function into the error of a member function call.
+fn script_function_failed(function: &FunctionItem, err: EvalError) -> RuntimeError {
+	let mut message = err.to_string();
+	let mut source = err.source();
+	while let Some(err) = source {
+		message.push_str(": ");
+		message.push_str(&err.to_string());
+		source = err.source();
+	}
+	RuntimeError::ScriptFunctionFailed {
+		function_name: function.name.clone(),
+		message,
+	}
+}
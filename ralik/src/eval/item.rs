@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+
+use crate::error::{Location, TypeRegistrationError};
+use crate::types::{
+	new_enum_type, new_struct_type, new_tuple_struct_type, new_unit_struct_type, Derive, TypeBuilder, VariantBuilder,
+};
+use crate::Context;
+
+use super::super::ast::{Fields, Item, Type, TypeItem, TypeShape};
+use super::types::{core_type_error, is_sized_integer, resolve_type};
+use super::EvalError;
+
+/**
+Declares the types of all `struct` and `enum` items at once, so that they may refer to each other, also through arrays,
+tuples and generic types. The types remain registered in the context once the block has been evaluated.
+
+```rust
+# use ralik::eval_str;
+let context = ralik::Context::new();
+let value = eval_str(
+r#"{
+#[derive(Debug, Clone)]
+enum Shape { Circle(Meters), Rect { w: Meters, h: Meters } }
+#[derive(Debug, Clone)]
+struct Meters(Integer);
+format!("{:?}", Shape::Rect { w: Meters(2), h: Meters(3) }.clone())
+}"#,
+&context,
+)
+.unwrap();
+assert_eq!(value.as_string(), Some("Rect { w: Meters(2), h: Meters(3) }"));
+assert!(eval_str("Meters(4)", &context).is_ok());
+let value = eval_str("{ struct W(Vec<W2>, Option<(W2, Integer)>); struct W2; 1 }", &context).unwrap();
+assert_eq!(value.as_i32(), Some(1));
+let value = eval_str("{ struct S { r#type: Integer } S { r#type: 1 }.r#type }", &context).unwrap();
+assert_eq!(value.as_i32(), Some(1));
+
+let err = eval_str("{ struct Meters; }", &context).unwrap_err();
+assert_eq!(err.to_string(), "The name `Meters` is already in use when declared at 1:9 to 1:15");
+let err = eval_str("{ struct Point { x: Integer, x: Integer } }", &context).unwrap_err();
+assert_eq!(err.to_string(), "The name `x` is already in use when declared at 1:29");
+```
+*/
+pub(crate) fn declare_types(context: &Context, items: &[&Item]) -> Result<(), EvalError> {
+	let items = items
+		.iter()
+		.filter(|item| matches!(item, Item::Struct(_) | Item::Enum(_)))
+		.collect::<Vec<_>>();
+	if items.is_empty() {
+		return Ok(());
+	}
+
+	let mut names = Vec::new();
+	for item in &items {
+		let (name, name_span) = item_name(item);
+		check_unique(&names, name, name_span)?;
+		names.push(name);
+	}
+
+	let mut instantiated = Vec::new();
+	let mut type_builders = items
+		.iter()
+		.map(|item| type_builder(context, item, &names, &mut instantiated))
+		.collect::<Result<Vec<TypeBuilder>, EvalError>>()?;
+	type_builders.extend(instantiated);
+	context.try_register_types(type_builders).map_err(|err| {
+		// errors of the instantiated types are reported at the first declaration
+		let (name, name_span) = items
+			.iter()
+			.map(|item| item_name(item))
+			.find(|(name, _name_span)| *name == err.type_name())
+			.unwrap_or_else(|| item_name(items[0]));
+		match err {
+			TypeRegistrationError::NameInUse { name } => EvalError::DuplicateName { name, at: name_span },
+			err => EvalError::InvalidTypeDeclaration {
+				name: name.into(),
+				source: Box::new(err),
+				at: name_span,
+			},
+		}
+	})
+}
+
+fn item_name(item: &Item) -> (&str, Location) {
+	match item {
+		Item::Struct(item) => (&item.name, item.name_span),
+		Item::Enum(item) => (&item.name, item.name_span),
+		Item::Impl(_) | Item::Const(_) | Item::Static(_) => unreachable!("only `struct` and `enum` items declare types"),
+	}
+}
+
+fn check_unique(names: &[&str], name: &str, name_span: Location) -> Result<(), EvalError> {
+	if names.contains(&name) {
+		Err(EvalError::DuplicateName {
+			name: name.into(),
+			at: name_span,
+		})
+	} else {
+		Ok(())
+	}
+}
+
+fn type_builder(
+	context: &Context,
+	item: &Item,
+	declared_names: &[&str],
+	instantiated: &mut Vec<TypeBuilder>,
+) -> Result<TypeBuilder, EvalError> {
+	let type_builder = match item {
+		Item::Struct(TypeItem { name, body, .. }) => match body {
+			Fields::Unit => new_unit_struct_type(name),
+			Fields::Tuple(field_types) => {
+				new_tuple_struct_type(name, type_names(context, field_types, declared_names, instantiated)?)
+			}
+			Fields::Struct(fields) => {
+				let (field_names, field_types) = named_fields(context, fields, declared_names, instantiated)?;
+				new_struct_type(name, field_names.into_iter().zip(field_types))
+			}
+		},
+		Item::Enum(TypeItem { name, body, .. }) => {
+			let mut variant_names = Vec::new();
+			let mut variants = Vec::new();
+			for variant in body {
+				check_unique(&variant_names, &variant.name, variant.name_span)?;
+				variant_names.push(&variant.name);
+				variants.push(match &variant.fields {
+					Fields::Unit => VariantBuilder::Unit(variant.name.clone()),
+					Fields::Tuple(field_types) => VariantBuilder::Tuple(
+						variant.name.clone(),
+						type_names(context, field_types, declared_names, instantiated)?,
+					),
+					Fields::Struct(fields) => {
+						let (field_names, field_types) = named_fields(context, fields, declared_names, instantiated)?;
+						VariantBuilder::Struct(
+							variant.name.clone(),
+							field_names
+								.into_iter()
+								.enumerate()
+								.map(|(index, name)| (name.to_owned(), index))
+								.collect::<HashMap<String, usize>>(),
+							field_types,
+						)
+					}
+				});
+			}
+			new_enum_type(name, variants)
+		}
+		Item::Impl(_) | Item::Const(_) | Item::Static(_) => unreachable!("only `struct` and `enum` items declare types"),
+	};
+
+	let derives = match item {
+		Item::Struct(item) => &item.derives,
+		Item::Enum(item) => &item.derives,
+		Item::Impl(_) | Item::Const(_) | Item::Static(_) => unreachable!("only `struct` and `enum` items declare types"),
+	};
+	let derives = derives
+		.iter()
+		.map(|(name, span)| match name.as_str() {
+			"PartialEq" => Ok(Derive::PartialEq),
+			"PartialOrd" => Ok(Derive::PartialOrd),
+			"Clone" => Ok(Derive::Clone),
+			"Debug" => Ok(Derive::Debug),
+			"Display" => Ok(Derive::Display),
+			"Default" => Ok(Derive::Default),
+			_ => Err(EvalError::UnknownDerive {
+				name: name.clone(),
+				at: *span,
+			}),
+		})
+		.collect::<Result<Vec<Derive>, EvalError>>()?;
+	Ok(type_builder.derive(derives))
+}
+
+fn named_fields<'a>(
+	context: &Context,
+	fields: &'a [(String, Location, Type)],
+	declared_names: &[&str],
+	instantiated: &mut Vec<TypeBuilder>,
+) -> Result<(Vec<&'a str>, Vec<String>), EvalError> {
+	let mut field_names = Vec::new();
+	for (name, name_span, _type) in fields {
+		check_unique(&field_names, name, *name_span)?;
+		field_names.push(name.as_str());
+	}
+	let field_types = fields
+		.iter()
+		.map(|(_name, _name_span, r#type)| r#type.clone())
+		.collect::<Vec<Type>>();
+	Ok((
+		field_names,
+		type_names(context, &field_types, declared_names, instantiated)?,
+	))
+}
+
+/// Resolves the names of the given field types. Types that are declared alongside the field are referred to by name, as
+/// they are only registered together with it. So are the arrays, tuples and generic types that contain them, which are
+/// instantiated without resolving their type parameters and added to `instantiated` to be registered as well.
+fn type_names(
+	context: &Context,
+	types: &[Type],
+	declared_names: &[&str],
+	instantiated: &mut Vec<TypeBuilder>,
+) -> Result<Vec<String>, EvalError> {
+	types
+		.iter()
+		.map(|r#type| type_name(context, r#type, declared_names, instantiated))
+		.collect()
+}
+
+fn type_name(
+	context: &Context,
+	r#type: &Type,
+	declared_names: &[&str],
+	instantiated: &mut Vec<TypeBuilder>,
+) -> Result<String, EvalError> {
+	if let Some(sized_integer) = find_sized_integer(context, r#type, declared_names) {
+		return Err(EvalError::SizedIntegerField {
+			type_name: sized_integer.name.clone(),
+			at: sized_integer.span,
+		});
+	}
+	if !refers_to_declared(r#type, declared_names) {
+		return Ok(resolve_type(context, r#type)?.name().to_string());
+	}
+
+	let type_builder = match &r#type.shape {
+		TypeShape::Path(name, generic_arguments) if generic_arguments.is_empty() => return Ok(name.clone()),
+		TypeShape::Path(name, generic_arguments) => {
+			let generic_arguments = type_names(context, generic_arguments, declared_names, instantiated)?;
+			match (name.as_str(), generic_arguments.as_slice()) {
+				("Vec", [element_type]) => context
+					.array_type_builder(element_type)
+					.map_err(|err| core_type_error(err, r#type.span))?,
+				_ => {
+					let generic_arguments = generic_arguments.iter().map(String::as_str).collect::<Vec<_>>();
+					context
+						.generic_type_builder(name, &generic_arguments)
+						.map_err(|source| EvalError::InvalidGenericType {
+							source: Box::new(source),
+							at: r#type.span,
+						})?
+				}
+			}
+		}
+		TypeShape::Array(element_type) => {
+			let element_type = type_name(context, element_type, declared_names, instantiated)?;
+			context
+				.array_type_builder(&element_type)
+				.map_err(|err| core_type_error(err, r#type.span))?
+		}
+		TypeShape::Tuple(element_types) => {
+			let element_types = type_names(context, element_types, declared_names, instantiated)?;
+			context
+				.tuple_type_builder(element_types)
+				.map_err(|err| core_type_error(err, r#type.span))?
+		}
+	};
+	let name = type_builder.name.clone();
+	if !instantiated.iter().any(|previous| previous.name == name) {
+		instantiated.push(type_builder);
+	}
+	Ok(name)
+}
+
+/// Whether the type is, or contains, one of the types that are declared alongside it.
+/// Finds a fixed-size integer type such as `u32` in a field type. The values of fields are not checked against the range
+/// of such a type, so fields must use `Integer` instead.
+fn find_sized_integer<'a>(context: &Context, r#type: &'a Type, declared_names: &[&str]) -> Option<&'a Type> {
+	match &r#type.shape {
+		TypeShape::Path(name, generic_arguments) if generic_arguments.is_empty() => {
+			Some(r#type).filter(|_| !declared_names.contains(&name.as_str()) && is_sized_integer(context, r#type))
+		}
+		TypeShape::Path(_, element_types) | TypeShape::Tuple(element_types) => element_types
+			.iter()
+			.find_map(|element_type| find_sized_integer(context, element_type, declared_names)),
+		TypeShape::Array(element_type) => find_sized_integer(context, element_type, declared_names),
+	}
+}
+
+fn refers_to_declared(r#type: &Type, declared_names: &[&str]) -> bool {
+	match &r#type.shape {
+		TypeShape::Path(name, generic_arguments) => {
+			(generic_arguments.is_empty() && declared_names.contains(&name.as_str()))
+				|| generic_arguments
+					.iter()
+					.any(|generic_argument| refers_to_declared(generic_argument, declared_names))
+		}
+		TypeShape::Array(element_type) => refers_to_declared(element_type, declared_names),
+		TypeShape::Tuple(element_types) => element_types
+			.iter()
+			.any(|element_type| refers_to_declared(element_type, declared_names)),
+	}
+}
@@ -1,4 +1,4 @@
-use proc_macro2::Span;
+use crate::error::Location;
 
 use super::{Eval, EvalError};
 use crate::{Context, Value};
@@ -7,7 +7,7 @@ pub(crate) fn call_member_function_0(
 	context: &Context,
 	name: &str,
 	value: Value,
-	span: &Span,
+	span: &Location,
 ) -> Result<Value, EvalError> {
 	let r#type = value.get_type().clone();
 	let function = r#type
@@ -15,14 +15,16 @@ pub(crate) fn call_member_function_0(
 		.ok_or_else(|| EvalError::UnknownMemberFunction {
 			name: name.into(),
 			type_name: (&*r#type.name()).into(),
-			at: span.into(),
+			at: *span,
 		})?;
-	function(context, &r#type, &[value]).map_err(|source| EvalError::MemberRuntimeError {
-		name: name.into(),
-		type_name: (&*r#type.name()).into(),
-		source,
-		at: span.into(),
-	})
+	function
+		.call(context, &r#type, &[value])
+		.map_err(|source| EvalError::MemberRuntimeError {
+			name: name.into(),
+			type_name: (&*r#type.name()).into(),
+			source: Box::new(source),
+			at: *span,
+		})
 }
 
 pub(crate) fn call_member_function_1<T: Eval>(
@@ -30,7 +32,7 @@ pub(crate) fn call_member_function_1<T: Eval>(
 	name: &str,
 	value: Value,
 	argument: &T,
-	span: &Span,
+	span: &Location,
 ) -> Result<Value, EvalError> {
 	let argument = argument.eval(context)?;
 	let r#type = value.get_type().clone();
@@ -39,14 +41,16 @@ pub(crate) fn call_member_function_1<T: Eval>(
 		.ok_or_else(|| EvalError::UnknownMemberFunction {
 			name: name.into(),
 			type_name: (&*r#type.name()).into(),
-			at: span.into(),
+			at: *span,
 		})?;
-	function(context, &r#type, &[value, argument]).map_err(|source| EvalError::MemberRuntimeError {
-		name: name.into(),
-		type_name: (&*r#type.name()).into(),
-		source,
-		at: span.into(),
-	})
+	function
+		.call(context, &r#type, &[value, argument])
+		.map_err(|source| EvalError::MemberRuntimeError {
+			name: name.into(),
+			type_name: (&*r#type.name()).into(),
+			source: Box::new(source),
+			at: *span,
+		})
 }
 
 pub(crate) fn call_member_function_n<T: Eval>(
@@ -54,7 +58,7 @@ pub(crate) fn call_member_function_n<T: Eval>(
 	name: &str,
 	value: Value,
 	arguments: &[T],
-	span: &Span,
+	span: &Location,
 ) -> Result<Value, EvalError> {
 	let r#type = value.get_type().clone();
 	let function = r#type
@@ -62,15 +66,17 @@ pub(crate) fn call_member_function_n<T: Eval>(
 		.ok_or_else(|| EvalError::UnknownMemberFunction {
 			name: name.into(),
 			type_name: (&*r#type.name()).into(),
-			at: span.into(),
+			at: *span,
 		})?;
 	let arguments = std::iter::once(Ok(value))
 		.chain(arguments.iter().map(|argument| argument.eval(context)))
 		.collect::<Result<Vec<Value>, EvalError>>()?;
-	function(context, &r#type, &arguments).map_err(|source| EvalError::MemberRuntimeError {
-		name: name.into(),
-		type_name: (&*r#type.name()).into(),
-		source,
-		at: span.into(),
-	})
+	function
+		.call(context, &r#type, &arguments)
+		.map_err(|source| EvalError::MemberRuntimeError {
+			name: name.into(),
+			type_name: (&*r#type.name()).into(),
+			source: Box::new(source),
+			at: *span,
+		})
 }
@@ -1,12 +1,24 @@
 use std::convert::TryFrom;
 
-use crate::error::EvalError;
+use crate::error::{EvalError, Location};
 
-use super::ast::{AtomicExpression, BinaryOperator, Expression, Prefix, Suffix};
-use super::{Context, Value};
+use super::ast::{
+	Arguments, AtomicExpression, BinaryOperator, Expression, FormatArguments, Prefix, RangeLimits, Suffix,
+};
+use super::{Context, LazyMacroCall, Value};
 
+mod block;
+mod format;
+mod global;
+mod implementation;
+mod item;
 mod member_function;
+mod path;
+mod pattern;
+mod types;
+pub(crate) use block::declare_items;
 use member_function::{call_member_function_0, call_member_function_1, call_member_function_n};
+pub(crate) use pattern::matches;
 
 pub trait Eval {
 	fn eval(&self, context: &Context) -> Result<Value, EvalError>;
@@ -30,13 +42,13 @@ impl Eval for Expression {
 					Suffix::Field(name, span) => value.field(name).cloned().ok_or_else(|| EvalError::InvalidFieldAccess {
 						member_name: name.clone(),
 						type_name: (&*value.get_type().name()).into(),
-						at: span.into(),
+						at: *span,
 					}),
 					Suffix::TupleIndex(index, span) => {
 						let index = usize::try_from(*index).map_err(|_| EvalError::InvalidFieldAccess {
 							member_name: index.to_string(),
 							type_name: (&*value.get_type().name()).into(),
-							at: span.into(),
+							at: *span,
 						})?;
 						value
 							.tuple_field(index)
@@ -44,12 +56,21 @@ impl Eval for Expression {
 							.ok_or_else(|| EvalError::InvalidFieldAccess {
 								member_name: index.to_string(),
 								type_name: (&*value.get_type().name()).into(),
-								at: span.into(),
+								at: *span,
 							})
 					}
 					Suffix::ArrayIndex(index, span) => call_member_function_1(context, crate::ops::INDEX, value, index, span),
-					Suffix::FunctionCall(name, name_span, arguments, _arguments_span) => {
-						call_member_function_n(context, name, value, &arguments.arguments, name_span)
+					Suffix::FunctionCall(name, name_span, generic_arguments, arguments, _arguments_span) => {
+						if generic_arguments.is_empty() {
+							call_member_function_n(context, name, value, &arguments.arguments, name_span)
+						} else {
+							// Generic member functions are registered once per instantiation, e.g., as `parse::<Integer>`.
+							let r#type = value.get_type();
+							let name = types::generic_function_name(context, name, generic_arguments, |name| {
+								r#type.get_function(name).is_some()
+							})?;
+							call_member_function_n(context, &name, value, &arguments.arguments, name_span)
+						}
 					}
 				}
 			}
@@ -80,14 +101,14 @@ impl Eval for Expression {
 					}
 					BinaryOperator::LazyAnd(span) => {
 						let bool_type = context.get_bool_type().map_err(|err| EvalError::InvalidCoreType {
-							source: err.into(),
-							at: span.into(),
+							source: Box::new(err.into()),
+							at: *span,
 						})?;
 
 						if !lhs_value.has_type(&bool_type) {
 							return Err(EvalError::NotBoolInLazyAnd {
 								type_name: (&*lhs_value.get_type().name()).into(),
-								at: span.into(), // TODO: use the lhs span instead of the operator span here
+								at: *span, // TODO: use the lhs span instead of the operator span here
 							});
 						}
 
@@ -101,21 +122,21 @@ impl Eval for Expression {
 							} else {
 								Err(EvalError::NotBoolInLazyAnd {
 									type_name: (&*rhs_value.get_type().name()).into(),
-									at: span.into(), // TODO: use the lhs span instead of the operator span here
+									at: *span, // TODO: use the lhs span instead of the operator span here
 								})
 							}
 						}
 					}
 					BinaryOperator::LazyOr(span) => {
 						let bool_type = context.get_bool_type().map_err(|err| EvalError::InvalidCoreType {
-							source: err.into(),
-							at: span.into(),
+							source: Box::new(err.into()),
+							at: *span,
 						})?;
 
 						if !lhs_value.has_type(&bool_type) {
 							return Err(EvalError::NotBoolInLazyAnd {
 								type_name: (&*lhs_value.get_type().name()).into(),
-								at: span.into(), // TODO: use the lhs span instead of the operator span here
+								at: *span, // TODO: use the lhs span instead of the operator span here
 							});
 						}
 
@@ -129,14 +150,33 @@ impl Eval for Expression {
 							} else {
 								Err(EvalError::NotBoolInLazyAnd {
 									type_name: (&*rhs_value.get_type().name()).into(),
-									at: span.into(), // TODO: use the lhs span instead of the operator span here
+									at: *span, // TODO: use the lhs span instead of the operator span here
 								})
 							}
 						}
 					}
 				}
 			}
-			Expression::Block(_) => unimplemented!(),
+			Expression::Range(start, limits, end) => {
+				let start = match start {
+					Some(start) => Some(start.eval(context)?),
+					None => None,
+				};
+				let end = match end {
+					Some(end) => Some(end.eval(context)?),
+					None => None,
+				};
+				let (inclusive, span) = match limits {
+					RangeLimits::HalfOpen(span) => (false, span),
+					RangeLimits::Closed(span) => (true, span),
+				};
+				crate::types::new_range(context, start, end, inclusive).map_err(|err| EvalError::ObjectCreationError {
+					source: Box::new(err),
+					at: *span,
+				})
+			}
+			Expression::Cast(expression, as_span, r#type) => types::eval_cast(context, expression, as_span, r#type),
+			Expression::Block(block) => block::eval_block(context, block),
 			Expression::If(_, _, _) => unimplemented!(),
 			Expression::Else(_, _, _) => unimplemented!(),
 			Expression::While(_, _, _) => unimplemented!(),
@@ -149,8 +189,8 @@ impl Eval for AtomicExpression {
 	fn eval(&self, context: &Context) -> Result<Value, EvalError> {
 		match self {
 			AtomicExpression::Unit(span) => Value::new_unit(context).map_err(|err| EvalError::ObjectCreationError {
-				source: err.into(),
-				at: span.into(),
+				source: Box::new(err.into()),
+				at: *span,
 			}),
 			AtomicExpression::Parenthesized(expression, _span) => expression.eval(context),
 			AtomicExpression::Tuple(expressions, span) => {
@@ -159,8 +199,8 @@ impl Eval for AtomicExpression {
 					.map(|expression| expression.eval(context))
 					.collect::<Result<Vec<Value>, EvalError>>()?;
 				Value::new_tuple(context, values).map_err(|err| EvalError::ObjectCreationError {
-					source: err.into(),
-					at: span.into(),
+					source: Box::new(err.into()),
+					at: *span,
 				})
 			}
 			AtomicExpression::Array(expressions, span) => {
@@ -170,7 +210,7 @@ impl Eval for AtomicExpression {
 					.collect::<Result<Vec<Value>, EvalError>>()?;
 
 				if values.is_empty() {
-					return Err(EvalError::EmptyArray { at: span.into() });
+					return Err(EvalError::EmptyArray { at: *span });
 				}
 
 				let type_0 = values[0].get_type();
@@ -184,89 +224,115 @@ impl Eval for AtomicExpression {
 						type_1: (&*type_0.name()).to_owned(),
 						index_2: index + 1,
 						type_2: (&*value.get_type().name()).to_owned(),
-						at: span.into(),
+						at: *span,
 					});
 				}
 
 				Value::new_array(context, &type_0.clone(), values).map_err(|err| EvalError::ObjectCreationError {
-					source: err.into(),
-					at: span.into(),
+					source: Box::new(err.into()),
+					at: *span,
 				})
 			}
 			AtomicExpression::LitBool(value, span) => {
 				Value::new_bool(context, *value).map_err(|err| EvalError::ObjectCreationError {
-					source: err.into(),
-					at: span.into(),
+					source: Box::new(err.into()),
+					at: *span,
 				})
 			}
 			AtomicExpression::LitInt(value, span) => {
 				Value::new_integer(context, value.clone()).map_err(|err| EvalError::ObjectCreationError {
-					source: err.into(),
-					at: span.into(),
+					source: Box::new(err.into()),
+					at: *span,
 				})
 			}
 			AtomicExpression::LitByte(value, span) => {
 				Value::new_integer(context, *value).map_err(|err| EvalError::ObjectCreationError {
-					source: err.into(),
-					at: span.into(),
+					source: Box::new(err.into()),
+					at: *span,
 				})
 			}
 			AtomicExpression::LitByteStr(value, span) => {
 				let integer_type = context
 					.get_integer_type()
 					.map_err(|err| EvalError::ObjectCreationError {
-						source: crate::error::IntegerCreationError::from(err).into(),
-						at: span.into(),
+						source: Box::new(crate::error::IntegerCreationError::from(err).into()),
+						at: *span,
 					})?;
 				let values = value
 					.iter()
 					.map(|byte| Value::new_integer(context, *byte))
 					.collect::<Result<Vec<Value>, crate::error::IntegerCreationError>>()
 					.map_err(|err| EvalError::ObjectCreationError {
-						source: err.into(),
-						at: span.into(),
+						source: Box::new(err.into()),
+						at: *span,
 					})?;
 				Value::new_array(context, &integer_type, values).map_err(|err| EvalError::ObjectCreationError {
-					source: err.into(),
-					at: span.into(),
+					source: Box::new(err.into()),
+					at: *span,
 				})
 			}
 			AtomicExpression::LitChar(value, span) => {
 				Value::new_char(context, *value).map_err(|err| EvalError::ObjectCreationError {
-					source: err.into(),
-					at: span.into(),
+					source: Box::new(err.into()),
+					at: *span,
 				})
 			}
 			AtomicExpression::LitStr(value, span) => {
 				Value::new_string(context, value.clone().into_boxed_str()).map_err(|err| EvalError::ObjectCreationError {
-					source: err.into(),
-					at: span.into(),
+					source: Box::new(err.into()),
+					at: *span,
 				})
 			}
 			AtomicExpression::Dollar(span) => context.get_variable("$").ok_or_else(|| EvalError::UnknownVariable {
 				name: "$".into(),
-				at: span.into(),
+				at: *span,
 			}),
+			AtomicExpression::Path(path) => path::eval_path(context, path),
+			AtomicExpression::PathCall(path, arguments, _arguments_span) => path::eval_path_call(context, path, arguments),
+			AtomicExpression::StructLiteral(path, fields, _fields_span) => path::eval_struct_literal(context, path, fields),
 			AtomicExpression::FunctionCall(name, name_span, arguments, _arguments_span) => {
-				let function = context.get_function(name).ok_or_else(|| EvalError::UnknownFunction {
-					name: name.clone(),
-					at: name_span.into(),
-				})?;
+				// Variables that hold a function value shadow functions of the same name
+				let variable = context.get_variable(name).filter(|value| value.is_function());
+				let function = context.get_function(name);
+				if variable.is_none() && function.is_none() {
+					return path::eval_tuple_struct_call(context, name, name_span, arguments);
+				}
 				let arguments = arguments
 					.arguments
 					.iter()
 					.map(|argument| argument.eval(context))
 					.collect::<Result<Vec<Value>, EvalError>>()?;
-				function(context, &arguments).map_err(|source| EvalError::FunctionRuntimeError {
+				match variable {
+					Some(variable) => variable.call(context, &arguments),
+					None => function.unwrap().call(context, &arguments),
+				}
+				.map_err(|source| EvalError::FunctionRuntimeError {
 					name: name.into(),
-					source,
-					at: name_span.into(),
+					source: Box::new(source),
+					at: *name_span,
 				})
 			}
 			AtomicExpression::MacroCall(name, name_span, arguments, _arguments_span) => {
+				if let Some(lazy_macro) = context.get_lazy_macro(name) {
+					return lazy_macro(context, &LazyMacroCall::new(name, *name_span, &arguments.tokens));
+				}
+				if let Some(index) = context.get_format_string_index(name) {
+					let arguments = crate::syntax::parse_format_arguments(arguments.tokens.to_token_stream(), name, index)?;
+					declare_items(context, arguments.leading_arguments.iter().chain(&arguments.arguments))?;
+					return eval_format_macro_call(context, name, *name_span, &arguments);
+				}
+				let parsed;
+				let arguments = match &arguments.arguments {
+					Some(arguments) => arguments,
+					None => {
+						parsed = syn::parse2::<Arguments>(arguments.tokens.to_token_stream())?;
+						declare_items(context, &parsed.arguments)?;
+						&parsed
+					}
+				};
 				let macro_function = context.get_macro(name).ok_or_else(|| EvalError::UnknownMacro {
 					name: name.clone(),
-					at: name_span.into(),
+					at: *name_span,
 				})?;
 				let arguments = arguments
 					.arguments
@@ -275,8 +341,8 @@ impl Eval for AtomicExpression {
 					.collect::<Result<Vec<Value>, EvalError>>()?;
 				macro_function(context, &arguments).map_err(|source| EvalError::MacroRuntimeError {
 					name: name.into(),
-					source,
-					at: name_span.into(),
+					source: Box::new(source),
+					at: *name_span,
 				})
 			}
 		}
@@ -288,3 +354,54 @@ impl<T: Eval> Eval for Box<T> {
 		(**self).eval(context)
 	}
 }
+
+/// Evaluates the format string of a call to the macro `name` and its arguments to the formatted string, or to `None` if
+/// the call has no format string. The leading arguments are not evaluated.
+pub(crate) fn eval_format_string(
+	context: &Context,
+	name: &str,
+	arguments: &FormatArguments,
+) -> Result<Option<Value>, EvalError> {
+	let format_string = match &arguments.format_string {
+		Some(format_string) => format_string,
+		None => return Ok(None),
+	};
+	let mut format_arguments = Vec::with_capacity(arguments.arguments.len());
+	for argument in &arguments.arguments {
+		format_arguments.push(argument.eval(context)?);
+	}
+	let formatted =
+		format::format(context, format_string, &format_arguments).map_err(|source| EvalError::MacroRuntimeError {
+			name: name.into(),
+			source: Box::new(source),
+			at: format_string.span(),
+		})?;
+	Ok(Some(formatted))
+}
+
+fn eval_format_macro_call(
+	context: &Context,
+	name: &str,
+	name_span: Location,
+	arguments: &FormatArguments,
+) -> Result<Value, EvalError> {
+	let macro_function = match context.get_macro(name) {
+		Some(macro_function) => macro_function,
+		None => {
+			return Err(EvalError::UnknownMacro {
+				name: name.into(),
+				at: name_span,
+			})
+		}
+	};
+	let mut values = Vec::with_capacity(arguments.leading_arguments.len() + 1);
+	for argument in &arguments.leading_arguments {
+		values.push(argument.eval(context)?);
+	}
+	values.extend(eval_format_string(context, name, arguments)?);
+	macro_function(context, &values).map_err(|source| EvalError::MacroRuntimeError {
+		name: name.into(),
+		source: Box::new(source),
+		at: name_span,
+	})
+}
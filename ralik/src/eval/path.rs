@@ -0,0 +1,402 @@
+use crate::error::{Location, ValueCreationError};
+use crate::types::{Callable, TypeKind};
+use crate::{Context, TypeHandle, Value};
+
+use super::super::ast::{Arguments, FieldValue, Path, PathSegment, Type, TypeShape};
+use super::types::check_integer_ranges;
+use super::{Eval, EvalError};
+
+/**
+Evaluates a path that names a unit struct or a unit variant.
+
+```rust
+# use ralik::eval_str;
+let context = ralik::Context::new();
+context.register_types(vec![ralik::types::new_unit_struct_type("Marker")]);
+assert_eq!(eval_str("Marker", &context).unwrap().get_type().name().as_ref(), "Marker");
+
+let value = eval_str("std::option::Option::<Integer>::None", &context).unwrap();
+assert_eq!(value, eval_str("\"x\".parse::<Integer>().ok()", &context).unwrap());
+```
+
+A path that names a function evaluates to a function value, which can be called or passed to other functions.
+
+```rust
+# use ralik::eval_str;
+let context = ralik::Context::new();
+let value = eval_str("[\"a\", \"bc\"].map(String::len)", &context).unwrap();
+assert_eq!(value, eval_str("[1, 2]", &context).unwrap());
+
+let value = eval_str("{ let len = str::len; len(\"abc\") }", &context).unwrap();
+assert_eq!(value.as_i32(), Some(3));
+```
+*/
+pub(crate) fn eval_path(context: &Context, path: &Path) -> Result<Value, EvalError> {
+	if let [segment] = path.segments.as_slice() {
+		if let Some(value) = context
+			.get_variable(&segment.name)
+			.filter(|_| segment.generic_arguments.is_empty())
+		{
+			return Ok(value);
+		}
+	}
+
+	if let Some(r#type) = resolve_type(context, &path.segments)? {
+		return match r#type.kind() {
+			TypeKind::UnitStruct => create(path, Value::new_unit_struct(context, &*r#type.name())),
+			_ => Err(invalid_path(path, "unit struct or unit variant")),
+		};
+	}
+
+	if let Some((enum_type, variant_name)) = resolve_variant(context, path)? {
+		return create(
+			path,
+			Value::new_enum_unit_variant(context, &*enum_type.name(), variant_name),
+		);
+	}
+
+	match resolve_function(context, path)? {
+		Some(callable) => create(path, Value::new_callable(context, callable)),
+		None => Err(unknown_path(context, path)),
+	}
+}
+
+/**
+Evaluates a call through a path, which creates a tuple struct or tuple variant, or calls a function of a type. In the
+latter case, the arguments are passed to the function as they are, e.g., `Vec::<Integer>::default()` calls `default`
+without a receiver, while `Integer::abs(-3)` passes `-3` as the receiver of the member function `abs`.
+
+```rust
+# use ralik::eval_str;
+let context = ralik::Context::new();
+let value = eval_str("std::option::Option::<char>::Some('x')", &context).unwrap();
+assert_eq!(value.tuple_field(0).unwrap().as_char(), Some('x'));
+
+let value = eval_str("Vec::<char>::default()", &context).unwrap();
+assert_eq!(value.as_array().map(|elements| elements.len()), Some(0));
+assert_eq!(value.get_type().name().as_ref(), "[char]");
+
+let value = eval_str("Integer::abs(-3) + String::len(\"abc\")", &context).unwrap();
+assert_eq!(value.as_i32(), Some(6));
+
+let err = eval_str("Vec::new()", &context).unwrap_err();
+assert_eq!(
+	err.to_string(),
+	"Type `Vec` is generic and its generic arguments cannot be inferred, so they must be given when referenced at 1:0 \
+	 to 1:3",
+);
+assert!(matches!(
+	eval_str("std::option::Option::None", &context),
+	Err(ralik::error::EvalError::MissingGenericArguments { .. }),
+));
+```
+*/
+pub(crate) fn eval_path_call(context: &Context, path: &Path, arguments: &Arguments) -> Result<Value, EvalError> {
+	let values = arguments
+		.arguments
+		.iter()
+		.map(|argument| argument.eval(context))
+		.collect::<Result<Vec<Value>, EvalError>>()?;
+
+	if let Some(r#type) = resolve_type(context, &path.segments)? {
+		return match r#type.kind() {
+			TypeKind::TupleStruct => create(path, Value::new_tuple_struct(context, &*r#type.name(), values)),
+			_ => Err(invalid_path(path, "tuple struct, tuple variant or function")),
+		};
+	}
+
+	if let Some((enum_type, variant_name)) = resolve_variant(context, path)? {
+		let value = create(
+			path,
+			Value::new_enum_tuple_variant(context, &*enum_type.name(), variant_name, values),
+		)?;
+		return check_generic_value(context, path, &enum_type, value);
+	}
+
+	let (last, prefix) = path.segments.split_last().unwrap();
+	if prefix.is_empty() {
+		return eval_generic_function_call(context, last, &values);
+	}
+	let r#type = match resolve_type(context, prefix)? {
+		Some(r#type) => r#type,
+		None => return Err(unknown_path(context, path)),
+	};
+	let name = function_name(context, last, |name| {
+		r#type.get_associated_function(name).is_some() || r#type.get_function(name).is_some()
+	})?;
+	let function = r#type
+		.get_associated_function(&name)
+		.or_else(|| r#type.get_function(&name))
+		.ok_or_else(|| EvalError::UnknownMemberFunction {
+			name: name.clone(),
+			type_name: (&*r#type.name()).into(),
+			at: last.span,
+		})?;
+	let value = function
+		.call(context, &r#type, &values)
+		.map_err(|source| EvalError::MemberRuntimeError {
+			name,
+			type_name: (&*r#type.name()).into(),
+			source: Box::new(source),
+			at: last.span,
+		})?;
+	check_generic_value(context, path, &r#type, value)
+}
+
+/// Checks a value of the type `r#type` that was created through `path` against the fixed-size integer types among the
+/// generic arguments of the type, e.g., in `Option::<u8>::Some(1)`.
+fn check_generic_value(context: &Context, path: &Path, r#type: &TypeHandle, value: Value) -> Result<Value, EvalError> {
+	let (_last, prefix) = path.segments.split_last().unwrap();
+	match prefix.last() {
+		Some(type_segment) if !type_segment.generic_arguments.is_empty() && value.has_type(r#type) => {
+			let written_type = Type {
+				name: prefix.iter().map(segment_name).collect::<Vec<String>>().join("::"),
+				shape: TypeShape::Path(
+					prefix
+						.iter()
+						.map(|segment| segment.name.as_str())
+						.collect::<Vec<&str>>()
+						.join("::"),
+					type_segment.generic_arguments.clone(),
+				),
+				span: type_segment.span,
+			};
+			check_integer_ranges(context, &value, &written_type, path.span())?;
+			Ok(value)
+		}
+		_ => Ok(value),
+	}
+}
+
+/// Resolves a path that names a free function or a function of a type, e.g., `to_upper`, `String::new` or
+/// `String::len`. Associated functions take precedence over member functions of the same name.
+fn resolve_function(context: &Context, path: &Path) -> Result<Option<Callable>, EvalError> {
+	let (last, prefix) = path.segments.split_last().unwrap();
+	if prefix.is_empty() {
+		let name = function_name(context, last, |name| context.get_function(name).is_some())?;
+		return Ok(
+			context
+				.get_function(&name)
+				.map(|function| Callable::Function(name, function)),
+		);
+	}
+
+	let r#type = match resolve_type(context, prefix)? {
+		Some(r#type) => r#type,
+		None => return Ok(None),
+	};
+	let name = function_name(context, last, |name| {
+		r#type.get_associated_function(name).is_some() || r#type.get_function(name).is_some()
+	})?;
+	let function = r#type
+		.get_associated_function(&name)
+		.or_else(|| r#type.get_function(&name));
+	Ok(function.map(|function| Callable::TypeFunction(name, r#type, function)))
+}
+
+/**
+Calls a free function with generic arguments, which is registered once per instantiation, e.g., as `f::<Integer>`.
+
+```rust
+# use ralik::eval_str;
+let context = ralik::Context::new();
+context.insert_function("zero::<Integer>", |context, _arguments| Ok(ralik::Value::new_integer(context, 0)?));
+assert_eq!(eval_str("zero::<Integer>()", &context).unwrap().as_i32(), Some(0));
+assert!(eval_str("zero::<char>()", &context).is_err());
+```
+*/
+fn eval_generic_function_call(context: &Context, segment: &PathSegment, values: &[Value]) -> Result<Value, EvalError> {
+	let name = function_name(context, segment, |name| context.get_function(name).is_some())?;
+	let function = context.get_function(&name).ok_or_else(|| EvalError::UnknownFunction {
+		name: name.clone(),
+		at: segment.span,
+	})?;
+	function
+		.call(context, values)
+		.map_err(|source| EvalError::FunctionRuntimeError {
+			name,
+			source: Box::new(source),
+			at: segment.span,
+		})
+}
+
+/**
+Evaluates a call of a single identifier that does not name a function, which creates a tuple struct.
+
+```rust
+# use ralik::eval_str;
+let context = ralik::Context::new();
+context.register_types(vec![ralik::types::new_tuple_struct_type("Meters", vec!["Integer".to_string()])]);
+let value = eval_str("Meters(3)", &context).unwrap();
+assert_eq!(value.tuple_field(0).unwrap().as_i32(), Some(3));
+assert!(eval_str("Feet(3)", &context).is_err());
+```
+*/
+pub(crate) fn eval_tuple_struct_call(
+	context: &Context,
+	name: &str,
+	name_span: &Location,
+	arguments: &Arguments,
+) -> Result<Value, EvalError> {
+	match context.get_type(name) {
+		Some(r#type) if r#type.kind() == TypeKind::TupleStruct => {
+			let path = Path {
+				segments: vec![PathSegment {
+					name: name.into(),
+					generic_arguments: Vec::new(),
+					span: *name_span,
+				}],
+			};
+			eval_path_call(context, &path, arguments)
+		}
+		_ => Err(EvalError::UnknownFunction {
+			name: name.into(),
+			at: *name_span,
+		}),
+	}
+}
+
+/**
+Evaluates a struct literal, which creates a struct or a struct variant.
+
+```rust
+# use ralik::eval_str;
+let context = ralik::Context::new();
+let fields = vec![("x", "Integer"), ("y", "Integer")];
+context.register_types(vec![ralik::types::new_struct_type("Point", fields.into_iter())]);
+let value = eval_str("Point { y: 2, x: 1 }", &context).unwrap();
+assert_eq!(value.field("x").unwrap().as_i32(), Some(1));
+assert!(eval_str("Point { x: 1 }", &context).is_err());
+```
+*/
+pub(crate) fn eval_struct_literal(context: &Context, path: &Path, fields: &[FieldValue]) -> Result<Value, EvalError> {
+	let values = fields
+		.iter()
+		.map(|field| Ok((field.name.as_str(), field.expression.eval(context)?)))
+		.collect::<Result<Vec<(&str, Value)>, EvalError>>()?;
+
+	if let Some(r#type) = resolve_type(context, &path.segments)? {
+		return match r#type.kind() {
+			TypeKind::Struct => create(path, Value::new_struct(context, &*r#type.name(), values.into_iter())),
+			_ => Err(invalid_path(path, "struct or struct variant")),
+		};
+	}
+
+	match resolve_variant(context, path)? {
+		Some((enum_type, variant_name)) => create(
+			path,
+			Value::new_enum_struct_variant(context, &*enum_type.name(), variant_name, values.into_iter()),
+		),
+		None => Err(unknown_path(context, path)),
+	}
+}
+
+/// Resolves the type named by `segments`, where generic arguments are written as `<A, B>` after the last segment.
+/// `Vec::<T>` refers to the array type `[T]`, and generic types are instantiated on demand.
+fn resolve_type(context: &Context, segments: &[PathSegment]) -> Result<Option<TypeHandle>, EvalError> {
+	let (last, prefix) = match segments.split_last() {
+		Some(split) => split,
+		None => return Ok(None),
+	};
+	if prefix.iter().any(|segment| !segment.generic_arguments.is_empty()) {
+		return Ok(None);
+	}
+
+	let base_name = segments
+		.iter()
+		.map(|segment| segment.name.as_str())
+		.collect::<Vec<&str>>()
+		.join("::");
+	let generic_arguments = last
+		.generic_arguments
+		.iter()
+		.map(|generic_argument| super::types::resolve_type(context, generic_argument))
+		.collect::<Result<Vec<TypeHandle>, EvalError>>()?;
+	super::types::resolve_named_type(context, &base_name, &generic_arguments, last.span)
+}
+
+/// Resolves a path of the form `Enum::Variant` to the enum type and the name of the variant.
+fn resolve_variant<'a>(context: &Context, path: &'a Path) -> Result<Option<(TypeHandle, &'a str)>, EvalError> {
+	let (last, prefix) = path.segments.split_last().unwrap();
+	if !last.generic_arguments.is_empty() {
+		return Ok(None);
+	}
+	match resolve_type(context, prefix)? {
+		Some(r#type) if r#type.kind() == TypeKind::Enum && r#type.variant_table().0.contains_key(last.name.as_str()) => {
+			Ok(Some((r#type, &last.name)))
+		}
+		_ => Ok(None),
+	}
+}
+
+/// The name under which a function is registered, e.g., `parse::<Integer>` for generic functions, where `exists` tells
+/// whether a function of a given name exists.
+fn function_name(context: &Context, segment: &PathSegment, exists: impl Fn(&str) -> bool) -> Result<String, EvalError> {
+	if segment.generic_arguments.is_empty() {
+		return Ok(segment.name.clone());
+	}
+	super::types::generic_function_name(context, &segment.name, &segment.generic_arguments, exists)
+}
+
+/// The name of a segment as written in the source, which is used in error messages.
+fn segment_name(segment: &PathSegment) -> String {
+	if segment.generic_arguments.is_empty() {
+		return segment.name.clone();
+	}
+	let generic_arguments = segment
+		.generic_arguments
+		.iter()
+		.map(|generic_argument| generic_argument.name.as_str())
+		.collect::<Vec<&str>>();
+	format!("{}::<{}>", segment.name, generic_arguments.join(", "))
+}
+
+fn create<E: Into<ValueCreationError>>(path: &Path, result: Result<Value, E>) -> Result<Value, EvalError> {
+	result.map_err(|err| EvalError::ObjectCreationError {
+		source: Box::new(err.into()),
+		at: path.span(),
+	})
+}
+
+fn path_name(path: &Path) -> String {
+	path
+		.segments
+		.iter()
+		.map(segment_name)
+		.collect::<Vec<String>>()
+		.join("::")
+}
+
+/// The error for a path that does not resolve, which points out missing generic arguments if the path starts with a
+/// generic type, e.g., in `Vec::new()`.
+fn unknown_path(context: &Context, path: &Path) -> EvalError {
+	for len in 1..=path.segments.len() {
+		let segments = &path.segments[..len];
+		if segments.iter().any(|segment| !segment.generic_arguments.is_empty()) {
+			break;
+		}
+		let name = segments
+			.iter()
+			.map(|segment| segment.name.as_str())
+			.collect::<Vec<&str>>()
+			.join("::");
+		if context.is_generic(&name) {
+			return EvalError::MissingGenericArguments {
+				name,
+				at: segments[len - 1].span,
+			};
+		}
+	}
+	EvalError::UnknownPath {
+		path: path_name(path),
+		at: path.span(),
+	}
+}
+
+fn invalid_path(path: &Path, expected: &'static str) -> EvalError {
+	EvalError::InvalidPath {
+		path: path_name(path),
+		expected,
+		at: path.span(),
+	}
+}
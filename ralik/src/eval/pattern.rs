@@ -0,0 +1,189 @@
+use anyhow::anyhow;
+
+use crate::error::RuntimeError;
+use crate::types::{TypeKind, Variant};
+use crate::{Context, Value};
+
+use super::super::ast::{AtomicExpression, Pattern, RangeLimits};
+
+/**
+Checks whether `value` matches `pattern`, as used by `matches!`. Bindings match any value, but are not made available
+to the caller.
+
+```rust
+# use ralik::eval_str;
+let context = ralik::Context::new();
+let value = eval_str(r#"matches!("12".parse::<Integer>(), Ok(10..=20 | 42))"#, &context).unwrap();
+assert_eq!(value.as_bool(), Some(true));
+let value = eval_str(r#"matches!(("a", ['b', 'c']), (_, [.., 'b']))"#, &context).unwrap();
+assert_eq!(value.as_bool(), Some(false));
+```
+*/
+pub(crate) fn matches(context: &Context, pattern: &Pattern, value: &Value) -> Result<bool, RuntimeError> {
+	match pattern {
+		Pattern::Wildcard(_span) | Pattern::Rest(_span) => Ok(true),
+		Pattern::Identifier(name, _span) => Ok(path_matches(value, std::slice::from_ref(name)).unwrap_or(true)),
+		Pattern::Path(path, _span) => resolve_path(value, path),
+		Pattern::Literal(literal) => {
+			let literal = literal_value(context, literal)?;
+			value
+				.get_type()
+				.call_predicate(context, crate::ops::EQUAL, &[value.clone(), literal])
+		}
+		Pattern::Range(start, limits, end) => {
+			if let Some(start) = start {
+				let start = literal_value(context, start)?;
+				if !start
+					.get_type()
+					.call_predicate(context, crate::ops::LESS_OR_EQUAL, &[start.clone(), value.clone()])?
+				{
+					return Ok(false);
+				}
+			}
+			if let Some(end) = end {
+				let end = literal_value(context, end)?;
+				let op = match limits {
+					RangeLimits::HalfOpen(_span) => crate::ops::LESS,
+					RangeLimits::Closed(_span) => crate::ops::LESS_OR_EQUAL,
+				};
+				if !value.get_type().call_predicate(context, op, &[value.clone(), end])? {
+					return Ok(false);
+				}
+			}
+			Ok(true)
+		}
+		Pattern::Tuple(patterns, _span) => {
+			check_kind(value, TypeKind::Tuple, "tuple")?;
+			list_matches(context, patterns, value.as_array().unwrap())
+		}
+		Pattern::Array(patterns, _span) => {
+			check_kind(value, TypeKind::Array, "array")?;
+			list_matches(context, patterns, value.as_array().unwrap())
+		}
+		Pattern::TupleStruct(path, patterns, _span) => {
+			if !resolve_path(value, path)? {
+				return Ok(false);
+			}
+			list_matches(context, patterns, value.as_array().unwrap())
+		}
+		Pattern::Struct(path, fields, _rest, _span) => {
+			if !resolve_path(value, path)? {
+				return Ok(false);
+			}
+			for (name, pattern) in fields {
+				let field = value.field(name).ok_or_else(|| {
+					anyhow!(
+						"Pattern `{}` refers to the field `{}`, which values of type `{}` do not have",
+						path.join("::"),
+						name,
+						value.get_type().name()
+					)
+				})?;
+				if !matches(context, pattern, field)? {
+					return Ok(false);
+				}
+			}
+			Ok(true)
+		}
+		Pattern::Or(patterns, _span) => {
+			for pattern in patterns {
+				if matches(context, pattern, value)? {
+					return Ok(true);
+				}
+			}
+			Ok(false)
+		}
+	}
+}
+
+/// Matches a list of patterns, which may contain one `..`, against the elements of a tuple, array or variant.
+fn list_matches(context: &Context, patterns: &[Pattern], values: &[Value]) -> Result<bool, RuntimeError> {
+	let (prefix, suffix) = match patterns.iter().position(|pattern| matches!(pattern, Pattern::Rest(_))) {
+		Some(rest) => {
+			if values.len() < patterns.len() - 1 {
+				return Ok(false);
+			}
+			(&patterns[..rest], &patterns[rest + 1..])
+		}
+		None => {
+			if values.len() != patterns.len() {
+				return Ok(false);
+			}
+			(patterns, &[][..])
+		}
+	};
+
+	for (pattern, value) in prefix.iter().zip(values) {
+		if !matches(context, pattern, value)? {
+			return Ok(false);
+		}
+	}
+	for (pattern, value) in suffix.iter().zip(&values[values.len() - suffix.len()..]) {
+		if !matches(context, pattern, value)? {
+			return Ok(false);
+		}
+	}
+	Ok(true)
+}
+
+fn check_kind(value: &Value, kind: TypeKind, description: &str) -> Result<(), RuntimeError> {
+	if value.get_type().kind() == kind {
+		Ok(())
+	} else {
+		Err(
+			anyhow!(
+				"A {} pattern cannot match values of type `{}`",
+				description,
+				value.get_type().name()
+			)
+			.into(),
+		)
+	}
+}
+
+fn resolve_path(value: &Value, path: &[String]) -> Result<bool, RuntimeError> {
+	path_matches(value, path).ok_or_else(|| {
+		anyhow!(
+			"Pattern `{}` cannot match values of type `{}`",
+			path.join("::"),
+			value.get_type().name()
+		)
+		.into()
+	})
+}
+
+/// Checks whether `path` names the variant of the enum `value`, returning `None` if `path` names neither a variant of
+/// the type of `value` nor the type itself.
+fn path_matches(value: &Value, path: &[String]) -> Option<bool> {
+	let r#type = value.get_type();
+	let type_name = r#type.name();
+	let base_name = type_name.split('<').next().unwrap();
+	match r#type.kind() {
+		TypeKind::Enum => {
+			let (name, prefix) = path.split_last().unwrap();
+			let prefix = prefix.join("::");
+			if !prefix.is_empty() && base_name != prefix && !base_name.ends_with(&format!("::{}", prefix)) {
+				return None;
+			}
+			let variant_id = r#type.variant_table().1.iter().position(|variant| match variant {
+				Variant::Unit(variant_name) | Variant::Tuple(variant_name, _) | Variant::Struct(variant_name, _, _) => {
+					**variant_name == **name
+				}
+			})?;
+			Some(value.as_variant_id() == Some(variant_id))
+		}
+		TypeKind::UnitStruct | TypeKind::TupleStruct | TypeKind::Struct if base_name == path.join("::") => Some(true),
+		_ => None,
+	}
+}
+
+fn literal_value(context: &Context, literal: &AtomicExpression) -> Result<Value, RuntimeError> {
+	match literal {
+		AtomicExpression::LitBool(value, _span) => Ok(Value::new_bool(context, *value)?),
+		AtomicExpression::LitInt(value, _span) => Ok(Value::new_integer(context, value.clone())?),
+		AtomicExpression::LitByte(value, _span) => Ok(Value::new_integer(context, *value)?),
+		AtomicExpression::LitChar(value, _span) => Ok(Value::new_char(context, *value)?),
+		AtomicExpression::LitStr(value, _span) => Ok(Value::new_string(context, value.as_str())?),
+		_ => unreachable!("Invalid literal in pattern"),
+	}
+}
@@ -0,0 +1,322 @@
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+use num::{BigInt, Integer};
+
+use crate::context::{prelude_name, sized_integer_range, OPTION_GENERIC, RESULT_GENERIC};
+use crate::error::{InvalidCoreType, InvalidGenericType, Location};
+use crate::types::TypeKind;
+use crate::{Context, TypeHandle, Value};
+
+use super::super::ast::{Expression, Type, TypeShape};
+use super::{Eval, EvalError};
+
+/**
+Resolves a type as written in the source, such as the type of a `let` ascription, a cast or a turbofish. Arrays,
+tuples and generic types are instantiated on demand, and `String`, `str`, `Option` and `Result` refer to their `std` types
+and the Rust integer types such as `u32` to `Integer` unless the context defines types of the same name. Values that are
+given such a type are checked against its range, see `check_integer_ranges`.
+
+```rust
+# use ralik::eval_str;
+let context = ralik::Context::new();
+let value = eval_str("{ let x: Vec<Option<char>> = []; x }", &context).unwrap();
+assert_eq!(value.get_type().name().as_ref(), "[std::option::Option<char>]");
+let value = eval_str(r#"{ let x: Result<Integer, String> = "1".parse::<Integer>(); x }"#, &context).unwrap();
+assert_eq!(value.get_type().name().as_ref(), "std::result::Result<Integer, std::string::String>");
+let value = eval_str("{ let x: Option<u32> = Option::<u64>::None; x }", &context).unwrap();
+assert_eq!(value.get_type().name().as_ref(), "std::option::Option<Integer>");
+let value = eval_str("Vec::<u8>::new()", &context).unwrap();
+assert_eq!(value.get_type().name().as_ref(), "[Integer]");
+let source = "{ struct Meters(Integer); const LIMIT: u32 = 10; Meters(LIMIT) }";
+assert_eq!(eval_str(source, &context).unwrap().to_string(), "Meters(10)");
+let err = eval_str("{ struct Point { x: Integer, y: Option<i64> } }", &context).unwrap_err();
+assert_eq!(
+	err.to_string(),
+	"The fixed-size integer type `i64` cannot be used for fields, use `Integer` instead at 1:39 to 1:42",
+);
+let err = eval_str("{ let x: [Option<f32>] = []; x }", &context).unwrap_err();
+assert_eq!(err.to_string(), "Type `f32` does not exist when referenced at 1:17 to 1:20");
+let err = eval_str("{ let x: Option = Option::<Integer>::None; x }", &context).unwrap_err();
+assert_eq!(
+	err.to_string(),
+	"Type `Option` is generic and its generic arguments cannot be inferred, so they must be given when referenced at 1:9 \
+	 to 1:15",
+);
+```
+*/
+pub(crate) fn resolve_type(context: &Context, r#type: &Type) -> Result<TypeHandle, EvalError> {
+	match &r#type.shape {
+		TypeShape::Path(name, generic_arguments) => {
+			let generic_arguments = generic_arguments
+				.iter()
+				.map(|generic_argument| resolve_type(context, generic_argument))
+				.collect::<Result<Vec<TypeHandle>, EvalError>>()?;
+			match resolve_named_type(context, name, &generic_arguments, r#type.span)? {
+				Some(r#type) => Ok(r#type),
+				None if generic_arguments.is_empty() && context.is_generic(name) => Err(EvalError::MissingGenericArguments {
+					name: name.clone(),
+					at: r#type.span,
+				}),
+				None => Err(EvalError::UnknownType {
+					name: r#type.name.clone(),
+					at: r#type.span,
+				}),
+			}
+		}
+		TypeShape::Array(element_type) => {
+			let element_type = resolve_type(context, element_type)?;
+			context
+				.get_array_type(&element_type.name())
+				.map_err(|err| core_type_error(err, r#type.span))
+		}
+		TypeShape::Tuple(element_types) => {
+			let element_type_names = element_types
+				.iter()
+				.map(|element_type| Ok(resolve_type(context, element_type)?.name()))
+				.collect::<Result<Vec<Arc<str>>, EvalError>>()?;
+			context
+				.get_tuple_type(element_type_names)
+				.map_err(|err| core_type_error(err, r#type.span))
+		}
+	}
+}
+
+/// Resolves the type `name` instantiated with the already resolved `generic_arguments`, or returns `None` if neither the
+/// context nor the prelude define a type or generic of that name.
+pub(crate) fn resolve_named_type(
+	context: &Context,
+	name: &str,
+	generic_arguments: &[TypeHandle],
+	span: Location,
+) -> Result<Option<TypeHandle>, EvalError> {
+	if let Some(r#type) = instantiate(context, name, generic_arguments, span)? {
+		return Ok(Some(r#type));
+	}
+	match prelude_name(name) {
+		Some(name) => instantiate(context, name, generic_arguments, span),
+		None => Ok(None),
+	}
+}
+
+fn instantiate(
+	context: &Context,
+	name: &str,
+	generic_arguments: &[TypeHandle],
+	span: Location,
+) -> Result<Option<TypeHandle>, EvalError> {
+	let generic_argument_names = generic_arguments
+		.iter()
+		.map(|generic_argument| generic_argument.name())
+		.collect::<Vec<Arc<str>>>();
+	match (name, generic_argument_names.as_slice()) {
+		(_, []) => Ok(context.get_type(name)),
+		("Vec", [element_type]) => context
+			.get_array_type(element_type)
+			.map(Some)
+			.map_err(|err| core_type_error(err, span)),
+		_ => match context.get_generic_type(name, generic_argument_names.clone()) {
+			Ok(r#type) => Ok(Some(r#type)),
+			// the type may still have been registered directly, e.g., as `Matrix<Integer>`
+			Err(InvalidGenericType::MissingGeneric { generic_name: _ }) => {
+				Ok(context.get_type(format!("{}<{}>", name, generic_argument_names.join(", "))))
+			}
+			Err(err) => Err(EvalError::InvalidGenericType {
+				source: Box::new(err),
+				at: span,
+			}),
+		},
+	}
+}
+
+/// Whether `r#type` names a fixed-size integer type such as `u32` that resolves to `Integer`.
+pub(super) fn is_sized_integer(context: &Context, r#type: &Type) -> bool {
+	sized_integer(context, r#type).is_some()
+}
+
+/// The range of the fixed-size integer type that `r#type` names, unless the context defines a type of the same name.
+fn sized_integer(context: &Context, r#type: &Type) -> Option<RangeInclusive<BigInt>> {
+	match &r#type.shape {
+		TypeShape::Path(name, generic_arguments) if generic_arguments.is_empty() && context.get_type(name).is_none() => {
+			sized_integer_range(name)
+		}
+		_ => None,
+	}
+}
+
+/**
+Checks that the integers of a value fit into the fixed-size integer types that its type names as written in the source,
+which is resolved to `Integer` otherwise. The check descends into the elements of arrays and tuples, and into the values
+of `Option` and `Result`.
+
+```rust
+# use ralik::eval_str;
+let context = ralik::Context::new();
+assert_eq!(eval_str("{ let x: (u8, i8) = (255, -128); x }", &context).unwrap().to_string(), "(255, -128)");
+
+let err = eval_str("{ let x: u8 = 300; x }", &context).unwrap_err();
+assert_eq!(err.to_string(), "The value `300` does not fit into the type `u8` at 1:14 to 1:17");
+let err = eval_str("{ let v = [Option::<Integer>::Some(-1)]; let x: Vec<Option<u32>> = v; x }", &context).unwrap_err();
+assert_eq!(err.to_string(), "The value `-1` does not fit into the type `u32` at 1:67");
+let err = eval_str("Option::<u8>::Some(256)", &context).unwrap_err();
+assert_eq!(err.to_string(), "The value `256` does not fit into the type `u8` at 1:0 to 1:6");
+assert!(eval_str("{ const C: i8 = 128; C }", &context).is_err());
+assert!(eval_str("{ fn f(x: u8) -> u16 { x * 300 } f(10) }", &context).is_err());
+```
+*/
+pub(crate) fn check_integer_ranges(
+	context: &Context,
+	value: &Value,
+	r#type: &Type,
+	at: Location,
+) -> Result<(), EvalError> {
+	if let Some(range) = sized_integer(context, r#type) {
+		return match value.as_integer() {
+			Some(integer) if !range.contains(integer) => Err(EvalError::IntegerOutOfRange {
+				value: integer.to_string(),
+				type_name: r#type.name.clone(),
+				at,
+			}),
+			_ => Ok(()),
+		};
+	}
+
+	let elements = value.as_array().unwrap_or(&[]);
+	let element_types: Vec<&Type> = match (&r#type.shape, value.get_type().kind()) {
+		(TypeShape::Array(element_type), TypeKind::Array) => vec![element_type; elements.len()],
+		(TypeShape::Tuple(element_types), TypeKind::Tuple) => element_types.iter().collect(),
+		// `Vec::<T>` in a path
+		(TypeShape::Path(_name, generic_arguments), TypeKind::Array) => {
+			generic_arguments.iter().cycle().take(elements.len()).collect()
+		}
+		(TypeShape::Path(_name, generic_arguments), TypeKind::Enum) => {
+			let type_name = value.get_type().name();
+			let generic_argument = if type_name.starts_with(&format!("{}<", OPTION_GENERIC)) {
+				generic_arguments.first()
+			} else if type_name.starts_with(&format!("{}<", RESULT_GENERIC)) {
+				generic_arguments.get(value.as_variant_id().unwrap())
+			} else {
+				None
+			};
+			generic_argument.into_iter().cycle().take(elements.len()).collect()
+		}
+		_ => Vec::new(),
+	};
+	for (element, element_type) in elements.iter().zip(element_types) {
+		check_integer_ranges(context, element, element_type, at)?;
+	}
+	Ok(())
+}
+
+/// The name under which a generic function is registered for the given generic arguments, e.g., `parse::<Integer>`. A
+/// function may also be registered for fixed-size integer types such as `u8` themselves, e.g., as `parse::<u8>` to
+/// check the range of its result, and `exists` decides whether to use that name or fall back to the one for `Integer`.
+pub(crate) fn generic_function_name(
+	context: &Context,
+	name: &str,
+	generic_arguments: &[Type],
+	exists: impl Fn(&str) -> bool,
+) -> Result<String, EvalError> {
+	let resolved_names = generic_arguments
+		.iter()
+		.map(|generic_argument| Ok(resolve_type(context, generic_argument)?.name()))
+		.collect::<Result<Vec<Arc<str>>, EvalError>>()?;
+	if generic_arguments
+		.iter()
+		.any(|generic_argument| is_sized_integer(context, generic_argument))
+	{
+		let written_names = generic_arguments
+			.iter()
+			.zip(resolved_names.iter())
+			.map(|(generic_argument, resolved_name)| {
+				if is_sized_integer(context, generic_argument) {
+					generic_argument.name.as_str()
+				} else {
+					resolved_name
+				}
+			})
+			.collect::<Vec<&str>>();
+		let written_name = format!("{}::<{}>", name, written_names.join(", "));
+		if exists(&written_name) {
+			return Ok(written_name);
+		}
+	}
+	Ok(format!("{}::<{}>", name, resolved_names.join(", ")))
+}
+
+pub(super) fn core_type_error(err: impl Into<InvalidCoreType>, span: Location) -> EvalError {
+	EvalError::InvalidCoreType {
+		source: Box::new(err.into()),
+		at: span,
+	}
+}
+
+/**
+Evaluates a cast such as `'a' as Integer`. Besides casts to the type of the value itself, `bool` and `char` can be cast
+to `Integer`, and integers that are valid unicode scalar values can be cast to `char`. Casts to fixed-size integer
+types such as `u8` wrap the value around into the range of the type, as in Rust.
+
+```rust
+# use ralik::eval_str;
+let context = ralik::Context::new();
+assert_eq!(eval_str("'a' as Integer + 1", &context).unwrap().as_i32(), Some(98));
+assert_eq!(eval_str("98 as char", &context).unwrap().as_char(), Some('b'));
+assert_eq!(eval_str("-(true as Integer)", &context).unwrap().as_i32(), Some(-1));
+assert!(eval_str("-1 as char", &context).is_err());
+assert!(eval_str("\"1\" as Integer", &context).is_err());
+
+let value = eval_str("(300 as u8, -1 as u8, 200 as i8, -129 as i8, 'Ā' as u8, 5 as u64)", &context).unwrap();
+assert_eq!(value.to_string(), "(44, 255, -56, 127, 0, 5)");
+```
+*/
+pub(crate) fn eval_cast(
+	context: &Context,
+	expression: &Expression,
+	as_span: &Location,
+	r#type: &Type,
+) -> Result<Value, EvalError> {
+	let value = expression.eval(context)?;
+	let target_type = resolve_type(context, r#type)?;
+	let value = if value.has_type(&target_type) {
+		value
+	} else {
+		convert(context, value, &target_type, as_span)?
+	};
+
+	match (sized_integer(context, r#type), value.as_integer()) {
+		(Some(range), Some(integer)) => {
+			let modulus = range.end() - range.start() + 1;
+			let wrapped = (integer - range.start()).mod_floor(&modulus) + range.start();
+			Value::new_integer(context, wrapped).map_err(|source| EvalError::ObjectCreationError {
+				source: Box::new(source.into()),
+				at: *as_span,
+			})
+		}
+		_ => Ok(value),
+	}
+}
+
+fn convert(context: &Context, value: Value, target_type: &TypeHandle, as_span: &Location) -> Result<Value, EvalError> {
+	let invalid_cast = || EvalError::InvalidCast {
+		type_name: (*value.get_type().name()).into(),
+		target_type_name: (*target_type.name()).into(),
+		at: *as_span,
+	};
+	let created = match (value.get_type().kind(), target_type.kind()) {
+		(TypeKind::Bool, TypeKind::Integer) => {
+			Value::new_integer(context, value.as_bool().unwrap() as u8).map_err(Into::into)
+		}
+		(TypeKind::Char, TypeKind::Integer) => {
+			Value::new_integer(context, value.as_char().unwrap() as u32).map_err(Into::into)
+		}
+		(TypeKind::Integer, TypeKind::Char) => match value.as_u32().and_then(std::char::from_u32) {
+			Some(value) => Value::new_char(context, value).map_err(Into::into),
+			None => return Err(invalid_cast()),
+		},
+		_ => return Err(invalid_cast()),
+	};
+	created.map_err(|source| EvalError::ObjectCreationError {
+		source: Box::new(source),
+		at: *as_span,
+	})
+}
@@ -1,11 +1,14 @@
 #![forbid(unsafe_code)]
+// doc tests are indented with tabs like the rest of the code
+#![allow(clippy::tabs_in_doc_comments)]
 
 use proc_macro2::TokenStream;
+use syn::parse::Parser;
 
 pub mod error;
 
 mod context;
-pub use context::{Context, TypeHandle};
+pub use context::{Context, Function, FunctionClosure, LazyMacroCall, NativeFunction, TypeHandle, TypedFunction};
 
 mod eval;
 
@@ -15,20 +18,25 @@ mod syntax;
 pub use syntax::ast;
 
 pub mod types;
+pub use types::RalikType;
+
+#[cfg(feature = "derive")]
+pub use ralik_derive::{methods, RalikType};
 
 mod value;
-pub use value::Value;
+pub use value::{FromArgument, FromValue, FunctionResult, IntoValue, PrettyOptions, Value};
 
 pub fn eval_str(source: &str, context: &Context) -> Result<Value, error::EvalError> {
-	let expression = syn::parse_str::<syntax::ast::Expression>(source)?;
+	let expression = syntax::parse_statements.parse_str(source)?;
 	eval_expression(&expression, context)
 }
 
 pub fn eval_tokens(source: TokenStream, context: &Context) -> Result<Value, error::EvalError> {
-	let expression = syn::parse2::<syntax::ast::Expression>(source)?;
+	let expression = syntax::parse_statements.parse2(source)?;
 	eval_expression(&expression, context)
 }
 
 pub fn eval_expression(expression: &ast::Expression, context: &Context) -> Result<Value, error::EvalError> {
+	eval::declare_items(context, [expression])?;
 	Ok(eval::Eval::eval(expression, context)?)
 }
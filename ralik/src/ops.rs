@@ -24,3 +24,4 @@ op!(LESS, "Less");
 op!(LESS_OR_EQUAL, "LessOrEqual");
 op!(GREATER, "Greater");
 op!(GREATER_OR_EQUAL, "GreaterOrEqual");
+op!(DEBUG, "Debug");
@@ -21,6 +21,19 @@ impl fmt::Debug for super::AtomicExpression {
 			LitChar(value, span) => f.debug_tuple("LitChar").field(&value).field(span).finish(),
 			LitStr(value, span) => f.debug_tuple("LitStr").field(&value).field(span).finish(),
 			Dollar(span) => f.debug_tuple("Dollar").field(span).finish(),
+			Path(path) => f.debug_tuple("Path").field(path).finish(),
+			PathCall(path, arguments, arguments_span) => f
+				.debug_tuple("PathCall")
+				.field(&path)
+				.field(&arguments)
+				.field(&arguments_span)
+				.finish(),
+			StructLiteral(path, fields, fields_span) => f
+				.debug_tuple("StructLiteral")
+				.field(&path)
+				.field(&fields)
+				.field(&fields_span)
+				.finish(),
 			FunctionCall(name, name_span, arguments, arguments_span) => f
 				.debug_tuple("FunctionCall")
 				.field(&name)
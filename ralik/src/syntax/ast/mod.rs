@@ -1,5 +1,7 @@
 use num::BigInt;
-use proc_macro2::Span;
+use proc_macro2::{LineColumn, TokenStream};
+
+use crate::error::Location;
 
 mod debug;
 
@@ -9,20 +11,25 @@ pub enum Expression {
 	Suffix(Box<Expression>, Suffix),
 	Prefix(Box<Expression>, Prefix),
 	Binary(Box<Expression>, Box<Expression>, BinaryOperator),
+	Range(Option<Box<Expression>>, RangeLimits, Option<Box<Expression>>),
+	/// A cast such as `'a' as Integer`, with the span of the `as`.
+	Cast(Box<Expression>, Location, Type),
 	Block(Block),
-	If(Span, Box<Expression>, Block),
-	Else(Box<Expression>, Span, Block),
-	While(Span, Box<Expression>, Block),
-	Loop(Option<(String, Span)>, Span, Block),
+	If(Location, Box<Expression>, Block),
+	Else(Box<Expression>, Location, Block),
+	While(Location, Box<Expression>, Block),
+	Loop(Option<(String, Location)>, Location, Block),
 }
 
 impl Expression {
-	pub fn span(&self) -> Span {
+	pub fn span(&self) -> Location {
 		match self {
 			Expression::Atomic(expr) => expr.span(),
 			Expression::Suffix(_, suffix) => suffix.span(),
 			Expression::Prefix(_, prefix) => prefix.span(),
 			Expression::Binary(_lhs, _rhs, op) => op.span(),
+			Expression::Range(_start, limits, _end) => limits.span(),
+			Expression::Cast(_expression, as_span, _type) => *as_span,
 			Expression::Block(block) => block.span(),
 			Expression::If(if_span, _condition, _body) => *if_span,
 			Expression::Else(_lhs, else_span, _body) => *else_span,
@@ -36,11 +43,11 @@ impl Expression {
 pub struct Block {
 	pub statements: Vec<Statement>,
 	pub expression: Option<Box<Expression>>,
-	pub span: Span,
+	pub span: Location,
 }
 
 impl Block {
-	pub fn span(&self) -> Span {
+	pub fn span(&self) -> Location {
 		self.span
 	}
 }
@@ -48,55 +55,197 @@ impl Block {
 #[derive(Clone, Debug)]
 pub enum Statement {
 	Expression(Expression),
-	Let(Span, bool, Pattern, Option<Expression>),
+	/// A `let` statement with an optional type ascription, e.g., `let x: Integer = 1;`.
+	Let(Location, bool, Box<Pattern>, Option<Type>, Option<Box<Expression>>),
+	Item(Item),
 }
 
 impl Statement {
-	pub fn span(&self) -> Span {
+	pub fn span(&self) -> Location {
 		match self {
 			Statement::Expression(expression) => expression.span(),
-			Statement::Let(let_span, _is_mut, _pattern, _assignment) => *let_span,
+			Statement::Let(let_span, _is_mut, _pattern, _type, _assignment) => *let_span,
+			Statement::Item(item) => item.span(),
+		}
+	}
+}
+
+/// An item that is declared in a block, which is visible in the whole block and remains registered in the context
+/// afterwards.
+#[derive(Clone, Debug)]
+pub enum Item {
+	/// A struct declaration, e.g., `#[derive(Debug)] struct Meters(Integer);`.
+	Struct(TypeItem<Fields>),
+	/// An enum declaration, e.g., `enum Shape { Circle(Integer), Rect { w: Integer, h: Integer } }`.
+	Enum(TypeItem<Vec<Variant>>),
+	/// An `impl` block, e.g., `impl Add for Meters { fn add(self, other: Meters) -> Meters { Meters(self.0 + other.0) } }`.
+	Impl(ImplItem),
+	/// A constant, e.g., `const LIMIT: Integer = 10;`.
+	Const(GlobalItem),
+	/// A static, e.g., `static NAME: &str = "x";`, which behaves exactly like a constant.
+	Static(GlobalItem),
+}
+
+impl Item {
+	pub fn span(&self) -> Location {
+		match self {
+			Item::Struct(item) => item.span,
+			Item::Enum(item) => item.span,
+			Item::Impl(item) => item.span,
+			Item::Const(item) | Item::Static(item) => item.span,
+		}
+	}
+
+	pub fn source(&self) -> &str {
+		match self {
+			Item::Struct(item) => &item.source,
+			Item::Enum(item) => &item.source,
+			Item::Impl(item) => &item.source,
+			Item::Const(item) | Item::Static(item) => &item.source,
 		}
 	}
 }
 
+/// The declaration of a struct or enum type, where `T` describes its fields or variants.
+#[derive(Clone, Debug)]
+pub struct TypeItem<T> {
+	/// The names given to `#[derive(...)]` attributes, each with its span.
+	pub derives: Vec<(String, Location)>,
+	pub name: String,
+	pub name_span: Location,
+	pub body: T,
+	/// The span of the `struct` or `enum` keyword.
+	pub span: Location,
+	/// The tokens of the item, which tell whether the same item is declared again, e.g., when a block is evaluated twice.
+	pub source: String,
+}
+
+/// The fields of a struct or enum variant declaration.
+#[derive(Clone, Debug)]
+pub enum Fields {
+	Unit,
+	Tuple(Vec<Type>),
+	Struct(Vec<(String, Location, Type)>),
+}
+
+#[derive(Clone, Debug)]
+pub struct Variant {
+	pub name: String,
+	pub name_span: Location,
+	pub fields: Fields,
+}
+
+/// A `const` or `static` item, which is registered as a global variable of the context.
+#[derive(Clone, Debug)]
+pub struct GlobalItem {
+	pub name: String,
+	pub name_span: Location,
+	pub r#type: Type,
+	pub expression: Box<Expression>,
+	/// The span of the `const` or `static` keyword.
+	pub span: Location,
+	/// The tokens of the item, which tell whether the same item is declared again, e.g., when a block is evaluated twice.
+	pub source: String,
+}
+
+/// An `impl` block, which adds functions to a type that is already declared.
+#[derive(Clone, Debug)]
+pub struct ImplItem {
+	/// The name of the trait that is implemented, if any, with its span.
+	pub r#trait: Option<(String, Location)>,
+	pub self_type: Type,
+	pub functions: Vec<FunctionItem>,
+	/// The span of the `impl` keyword.
+	pub span: Location,
+	/// The tokens of the item, which tell whether the same item is declared again, e.g., when a block is evaluated twice.
+	pub source: String,
+}
+
+/// A function declared in an `impl` block, e.g., `fn len2(self) -> Integer { self.x * self.x + self.y * self.y }`.
+#[derive(Clone, Debug)]
+pub struct FunctionItem {
+	pub name: String,
+	pub name_span: Location,
+	/// The span of the `self` parameter, which is absent for associated functions.
+	pub receiver: Option<Location>,
+	pub parameters: Vec<(String, Location, Type)>,
+	pub return_type: Option<Type>,
+	pub body: Block,
+	/// The span of the `fn` keyword.
+	pub span: Location,
+}
+
+/// A pattern, as used by `matches!`. Literals are restricted to `LitBool`, `LitInt`, `LitByte`, `LitChar` and `LitStr`.
 #[derive(Clone, Debug)]
 pub enum Pattern {
-	Identifier(String, Span),
+	/// Either a binding or the name of a unit variant or unit struct, depending on the value that is matched.
+	Identifier(String, Location),
+	Wildcard(Location),
+	/// The `..` in tuple, array and tuple struct patterns.
+	Rest(Location),
+	Literal(AtomicExpression),
+	Range(
+		Option<Box<AtomicExpression>>,
+		RangeLimits,
+		Option<Box<AtomicExpression>>,
+	),
+	Tuple(Vec<Pattern>, Location),
+	Array(Vec<Pattern>, Location),
+	Path(Vec<String>, Location),
+	TupleStruct(Vec<String>, Vec<Pattern>, Location),
+	Struct(Vec<String>, Vec<(String, Pattern)>, bool, Location),
+	Or(Vec<Pattern>, Location),
 }
 
 impl Pattern {
-	pub fn span(&self) -> Span {
+	pub fn span(&self) -> Location {
 		match self {
-			Pattern::Identifier(_id, span) => *span,
+			Pattern::Identifier(_, span)
+			| Pattern::Wildcard(span)
+			| Pattern::Rest(span)
+			| Pattern::Tuple(_, span)
+			| Pattern::Array(_, span)
+			| Pattern::Path(_, span)
+			| Pattern::TupleStruct(_, _, span)
+			| Pattern::Struct(_, _, _, span)
+			| Pattern::Or(_, span) => *span,
+			Pattern::Literal(literal) => literal.span(),
+			Pattern::Range(_start, limits, _end) => limits.span(),
 		}
 	}
 }
 
+/// The arguments of `matches!`, e.g., `matches!($, Some(1 | 2))`.
+#[derive(Clone, Debug)]
+pub struct MatchesArguments {
+	pub expression: Expression,
+	pub pattern: Pattern,
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum BinaryOperator {
-	Mul(Span),
-	Div(Span),
-	Rem(Span),
-	Add(Span),
-	Sub(Span),
-	Shl(Span),
-	Shr(Span),
-	BitAnd(Span),
-	BitXor(Span),
-	BitOr(Span),
-	Equal(Span),
-	NotEqual(Span),
-	Less(Span),
-	LessOrEqual(Span),
-	Greater(Span),
-	GreaterOrEqual(Span),
-	LazyAnd(Span),
-	LazyOr(Span),
+	Mul(Location),
+	Div(Location),
+	Rem(Location),
+	Add(Location),
+	Sub(Location),
+	Shl(Location),
+	Shr(Location),
+	BitAnd(Location),
+	BitXor(Location),
+	BitOr(Location),
+	Equal(Location),
+	NotEqual(Location),
+	Less(Location),
+	LessOrEqual(Location),
+	Greater(Location),
+	GreaterOrEqual(Location),
+	LazyAnd(Location),
+	LazyOr(Location),
 }
 
 impl BinaryOperator {
-	pub fn span(&self) -> Span {
+	pub fn span(&self) -> Location {
 		match self {
 			BinaryOperator::Mul(span)
 			| BinaryOperator::Div(span)
@@ -120,25 +269,45 @@ impl BinaryOperator {
 	}
 }
 
+#[derive(Copy, Clone, Debug)]
+pub enum RangeLimits {
+	HalfOpen(Location),
+	Closed(Location),
+}
+
+impl RangeLimits {
+	pub fn span(&self) -> Location {
+		match self {
+			RangeLimits::HalfOpen(span) | RangeLimits::Closed(span) => *span,
+		}
+	}
+}
+
 #[derive(Clone)]
 pub enum AtomicExpression {
-	Unit(Span),
-	Parenthesized(Box<Expression>, Span),
-	Tuple(Vec<Expression>, Span),
-	Array(Vec<Expression>, Span),
-	LitBool(bool, Span),
-	LitInt(BigInt, Span),
-	LitByte(u8, Span),
-	LitByteStr(Vec<u8>, Span),
-	LitChar(char, Span),
-	LitStr(String, Span),
-	Dollar(Span),
-	FunctionCall(String, Span, Arguments, Span),
-	MacroCall(String, Span, Arguments, Span),
+	Unit(Location),
+	Parenthesized(Box<Expression>, Location),
+	Tuple(Vec<Expression>, Location),
+	Array(Vec<Expression>, Location),
+	LitBool(bool, Location),
+	LitInt(BigInt, Location),
+	LitByte(u8, Location),
+	LitByteStr(Vec<u8>, Location),
+	LitChar(char, Location),
+	LitStr(String, Location),
+	Dollar(Location),
+	/// A unit struct or unit variant, e.g., `std::option::Option::<Integer>::None`.
+	Path(Path),
+	FunctionCall(String, Location, Arguments, Location),
+	/// A call through a path, which creates a tuple struct or tuple variant, or calls a function of a type.
+	PathCall(Path, Arguments, Location),
+	/// A struct or struct variant, e.g., `Point { x: 1, y: 2 }`.
+	StructLiteral(Path, Vec<FieldValue>, Location),
+	MacroCall(String, Location, MacroArguments, Location),
 }
 
 impl AtomicExpression {
-	pub fn span(&self) -> Span {
+	pub fn span(&self) -> Location {
 		match self {
 			AtomicExpression::Unit(span)
 			| AtomicExpression::Parenthesized(_, span)
@@ -151,25 +320,199 @@ impl AtomicExpression {
 			| AtomicExpression::LitChar(_, span)
 			| AtomicExpression::LitStr(_, span)
 			| AtomicExpression::Dollar(span) => *span,
+			AtomicExpression::Path(path)
+			| AtomicExpression::PathCall(path, _, _)
+			| AtomicExpression::StructLiteral(path, _, _) => path.span(),
 			AtomicExpression::FunctionCall(_, name_span, _, _arguments_span)
 			| AtomicExpression::MacroCall(_, name_span, _, _arguments_span) => *name_span,
 		}
 	}
 }
 
+/// A path in an expression, e.g., `std::option::Option::<Integer>::Some`. Generic arguments may be given for any segment.
+#[derive(Clone, Debug)]
+pub struct Path {
+	pub segments: Vec<PathSegment>,
+}
+
+impl Path {
+	pub fn span(&self) -> Location {
+		self.segments.first().unwrap().span
+	}
+}
+
+#[derive(Clone, Debug)]
+pub struct PathSegment {
+	pub name: String,
+	pub generic_arguments: Vec<Type>,
+	pub span: Location,
+}
+
+#[derive(Clone, Debug)]
+pub struct FieldValue {
+	pub name: String,
+	pub expression: Expression,
+	pub span: Location,
+}
+
 #[derive(Clone, Debug)]
 pub struct Arguments {
 	pub arguments: Vec<Expression>,
 }
 
+/// Tokens that are kept as their source text, as a `TokenStream` cannot be sent to other threads. They are lexed again at
+/// their original location, so that errors still point to the right place.
+#[derive(Clone, Debug)]
+pub struct SourceTokens {
+	text: String,
+	start: LineColumn,
+}
+
+impl SourceTokens {
+	pub fn new(tokens: TokenStream) -> Self {
+		let mut spans = tokens.clone().into_iter().map(|token| token.span());
+		let first = spans.next();
+		let source = first
+			.and_then(|first| first.join(spans.last().unwrap_or(first)))
+			.and_then(|span| Some((span.source_text()?, span.start())));
+		match source {
+			Some((text, start)) => Self { text, start },
+			// tokens that were not lexed from a string, e.g., by `quote!`, have no source text
+			None => Self {
+				text: tokens.to_string(),
+				start: LineColumn { line: 1, column: 0 },
+			},
+		}
+	}
+
+	pub fn to_token_stream(&self) -> TokenStream {
+		let mut source = "\n".repeat(self.start.line - 1);
+		source.extend(std::iter::repeat_n(' ', self.start.column));
+		source.push_str(&self.text);
+		source.parse().expect("the tokens were lexed before")
+	}
+
+	/// The tokens as they were written in the source.
+	pub fn text(&self) -> &str {
+		&self.text
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.text.trim().is_empty()
+	}
+}
+
+/// The arguments of a macro call. Lazy macros receive the `tokens` verbatim, as do macros that take a format string, which
+/// parse them once they are called. All other macros evaluate the `arguments`, which are `None` if the tokens are not
+/// a list of expressions.
+#[derive(Clone, Debug)]
+pub struct MacroArguments {
+	pub tokens: SourceTokens,
+	pub arguments: Option<Arguments>,
+}
+
+/// The arguments of a macro that takes a format string, e.g., `write!(s, "{} {x:?}", 1, x = 2)`. Named arguments are
+/// resolved to positions while parsing, so that `arguments` contains all arguments after the format string in order.
+#[derive(Clone, Debug)]
+pub struct FormatArguments {
+	pub tokens: SourceTokens,
+	pub leading_arguments: Vec<Expression>,
+	pub format_string: Option<FormatString>,
+	pub arguments: Vec<Expression>,
+}
+
+#[derive(Clone, Debug)]
+pub struct FormatString {
+	pub pieces: Vec<FormatPiece>,
+	pub span: Location,
+}
+
+impl FormatString {
+	pub fn span(&self) -> Location {
+		self.span
+	}
+}
+
+#[derive(Clone, Debug)]
+pub enum FormatPiece {
+	Literal(String),
+	Placeholder { argument: usize, spec: FormatSpec },
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct FormatSpec {
+	pub fill: Option<char>,
+	pub align: Option<FormatAlign>,
+	pub sign_plus: bool,
+	pub sign_minus: bool,
+	pub alternate: bool,
+	pub zero: bool,
+	pub width: Option<FormatCount>,
+	pub precision: Option<FormatCount>,
+	pub r#trait: FormatTrait,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum FormatAlign {
+	Left,
+	Center,
+	Right,
+}
+
+/// A width or precision, given either literally or as the index of an argument that holds it.
+#[derive(Copy, Clone, Debug)]
+pub enum FormatCount {
+	Is(usize),
+	Argument(usize),
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum FormatTrait {
+	#[default]
+	Display,
+	Debug,
+	LowerHex,
+	UpperHex,
+	Binary,
+	Octal,
+	LowerExp,
+	UpperExp,
+}
+
+/// A type as written in the source, e.g., in the turbofish of `"1".parse::<Integer>()`. The `name` is normalized to
+/// the spelling used for type names in the `Context`, e.g., `(Integer, [bool])`.
+#[derive(Clone, Debug)]
+pub struct Type {
+	pub name: String,
+	pub shape: TypeShape,
+	pub span: Location,
+}
+
+impl Type {
+	pub fn span(&self) -> Location {
+		self.span
+	}
+}
+
+/// The structure of a type. References such as `&str` are treated as the type they refer to.
+#[derive(Clone, Debug)]
+pub enum TypeShape {
+	/// A named type, e.g., `Integer` or `std::option::Option<Integer>`, with the generic arguments of its last segment.
+	Path(String, Vec<Type>),
+	/// An array type, written as either `[T]` or `Vec<T>`.
+	Array(Box<Type>),
+	/// A tuple type, including the unit type `()`.
+	Tuple(Vec<Type>),
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum Prefix {
-	Not(Span),
-	Minus(Span),
+	Not(Location),
+	Minus(Location),
 }
 
 impl Prefix {
-	pub fn span(&self) -> Span {
+	pub fn span(&self) -> Location {
 		match self {
 			Prefix::Not(span) | Prefix::Minus(span) => *span,
 		}
@@ -178,20 +521,20 @@ impl Prefix {
 
 #[derive(Clone, Debug)]
 pub enum Suffix {
-	Unwrap(Span),
-	Field(String, Span),
-	TupleIndex(u32, Span),
-	ArrayIndex(Box<Expression>, Span),
-	FunctionCall(String, Span, Arguments, Span),
+	Unwrap(Location),
+	Field(String, Location),
+	TupleIndex(u32, Location),
+	ArrayIndex(Box<Expression>, Location),
+	FunctionCall(String, Location, Vec<Type>, Arguments, Location),
 }
 
 impl Suffix {
-	pub fn span(&self) -> Span {
+	pub fn span(&self) -> Location {
 		match self {
 			Suffix::Unwrap(span) | Suffix::Field(_, span) | Suffix::TupleIndex(_, span) | Suffix::ArrayIndex(_, span) => {
 				*span
 			}
-			Suffix::FunctionCall(_, name_span, _, _arguments_span) => *name_span,
+			Suffix::FunctionCall(_, name_span, _generic_arguments, _, _arguments_span) => *name_span,
 		}
 	}
 }
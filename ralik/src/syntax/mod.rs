@@ -1,2 +1,3 @@
 pub mod ast;
 mod parse;
+pub(crate) use parse::{parse_argument_tokens, parse_format_arguments, parse_statements, ArgumentTokens};
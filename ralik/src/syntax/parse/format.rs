@@ -0,0 +1,340 @@
+use proc_macro2::TokenStream;
+use syn::{parse, Ident, LitStr, Token};
+
+use std::collections::HashMap;
+
+use super::super::ast;
+use super::parse_expression;
+
+pub(super) fn parse_format_arguments(
+	input: parse::ParseStream,
+	name: &str,
+	format_string_index: usize,
+) -> parse::Result<ast::FormatArguments> {
+	let tokens = ast::SourceTokens::new(input.fork().parse::<TokenStream>()?);
+
+	let mut leading_arguments = Vec::new();
+	while leading_arguments.len() < format_string_index {
+		if input.is_empty() {
+			return Err(input.error(format!(
+				"`{}!` takes {} argument(s) before its format string",
+				name, format_string_index
+			)));
+		}
+		let (expression, lookahead) = parse_expression(input)?;
+		leading_arguments.push(expression);
+
+		if input.is_empty() {
+			break;
+		} else if lookahead.peek(Token![,]) {
+			input.parse::<Token![,]>()?;
+		} else {
+			return Err(lookahead.error());
+		}
+	}
+
+	if input.is_empty() {
+		return Ok(ast::FormatArguments {
+			tokens,
+			leading_arguments,
+			format_string: None,
+			arguments: Vec::new(),
+		});
+	}
+
+	if !input.peek(LitStr) {
+		return Err(input.error(format!("the format string of `{}!` must be a string literal", name)));
+	}
+	let format_string = input.parse::<LitStr>()?;
+
+	let mut arguments = Vec::new();
+	let mut argument_spans = Vec::new();
+	let mut names = HashMap::new();
+	while !input.is_empty() {
+		input.parse::<Token![,]>()?;
+		if input.is_empty() {
+			break;
+		}
+
+		if input.peek(Ident) && input.peek2(Token![=]) && !input.peek2(Token![==]) {
+			let name = input.parse::<Ident>()?;
+			input.parse::<Token![=]>()?;
+			if names.insert(name.to_string(), arguments.len()).is_some() {
+				return Err(parse::Error::new(
+					name.span(),
+					format!("duplicate argument named `{}`", name),
+				));
+			}
+		} else if !names.is_empty() {
+			return Err(input.error("positional arguments cannot follow named arguments"));
+		}
+
+		argument_spans.push(input.span());
+		let (expression, lookahead) = parse_expression(input)?;
+		arguments.push(expression);
+		if !input.is_empty() && !lookahead.peek(Token![,]) {
+			return Err(lookahead.error());
+		}
+	}
+
+	let mut parser = FormatStringParser {
+		chars: format_string.value().chars().collect(),
+		position: 0,
+		next_argument: 0,
+		names: &names,
+		used: vec![false; arguments.len()],
+	};
+	let pieces = parser
+		.parse()
+		.map_err(|message| parse::Error::new(format_string.span(), message))?;
+	if let Some(unused) = parser.used.iter().position(|used| !used) {
+		return Err(parse::Error::new(argument_spans[unused], "argument never used"));
+	}
+
+	Ok(ast::FormatArguments {
+		tokens,
+		leading_arguments,
+		format_string: Some(ast::FormatString {
+			pieces,
+			span: format_string.span().into(),
+		}),
+		arguments,
+	})
+}
+
+/// Parses the contents of a format string, following the grammar described in the documentation of `std::fmt`.
+struct FormatStringParser<'a> {
+	chars: Vec<char>,
+	position: usize,
+	next_argument: usize,
+	names: &'a HashMap<String, usize>,
+	used: Vec<bool>,
+}
+
+impl<'a> FormatStringParser<'a> {
+	fn parse(&mut self) -> Result<Vec<ast::FormatPiece>, String> {
+		let mut pieces = Vec::new();
+		let mut literal = String::new();
+		while let Some(c) = self.next() {
+			match c {
+				'{' if self.eat('{') => literal.push('{'),
+				'}' if self.eat('}') => literal.push('}'),
+				'{' => {
+					if !literal.is_empty() {
+						pieces.push(ast::FormatPiece::Literal(std::mem::take(&mut literal)));
+					}
+					pieces.push(self.parse_placeholder()?);
+				}
+				'}' => return Err("invalid format string: unmatched `}` found".to_owned()),
+				c => literal.push(c),
+			}
+		}
+		if !literal.is_empty() {
+			pieces.push(ast::FormatPiece::Literal(literal));
+		}
+		Ok(pieces)
+	}
+
+	fn parse_placeholder(&mut self) -> Result<ast::FormatPiece, String> {
+		self.skip_whitespace();
+		let argument = self.parse_argument()?;
+		self.skip_whitespace();
+		let spec = if self.eat(':') {
+			self.parse_spec()?
+		} else {
+			ast::FormatSpec::default()
+		};
+		self.skip_whitespace();
+		if !self.eat('}') {
+			return Err("invalid format string: expected `}`".to_owned());
+		}
+
+		let argument = match argument {
+			Some(argument) => argument,
+			None => {
+				self.next_argument += 1;
+				self.resolve_index(self.next_argument - 1)?
+			}
+		};
+		Ok(ast::FormatPiece::Placeholder { argument, spec })
+	}
+
+	fn parse_argument(&mut self) -> Result<Option<usize>, String> {
+		if let Some(index) = self.parse_integer() {
+			Ok(Some(self.resolve_index(index)?))
+		} else if let Some(name) = self.parse_identifier() {
+			Ok(Some(self.resolve_name(&name)?))
+		} else {
+			Ok(None)
+		}
+	}
+
+	fn parse_spec(&mut self) -> Result<ast::FormatSpec, String> {
+		let mut spec = ast::FormatSpec::default();
+
+		match (self.peek(0), self.peek(1).and_then(parse_align)) {
+			(Some(fill), Some(align)) => {
+				spec.fill = Some(fill);
+				spec.align = Some(align);
+				self.position += 2;
+			}
+			(Some(c), _) if parse_align(c).is_some() => {
+				spec.align = parse_align(c);
+				self.position += 1;
+			}
+			_ => (),
+		}
+
+		if self.eat('+') {
+			spec.sign_plus = true;
+		} else if self.eat('-') {
+			spec.sign_minus = true;
+		}
+		spec.alternate = self.eat('#');
+		if self.peek(0) == Some('0') && self.peek(1) != Some('$') {
+			spec.zero = true;
+			self.position += 1;
+		}
+
+		spec.width = self.parse_count()?;
+
+		if self.eat('.') {
+			spec.precision = if self.eat('*') {
+				self.next_argument += 1;
+				Some(ast::FormatCount::Argument(self.resolve_index(self.next_argument - 1)?))
+			} else {
+				match self.parse_count()? {
+					Some(count) => Some(count),
+					None => return Err("invalid format string: expected a precision after `.`".to_owned()),
+				}
+			};
+		}
+
+		let r#trait = match self.parse_identifier() {
+			None => {
+				if self.eat('?') {
+					ast::FormatTrait::Debug
+				} else {
+					ast::FormatTrait::Display
+				}
+			}
+			Some(name) => match name.as_str() {
+				"x" => ast::FormatTrait::LowerHex,
+				"X" => ast::FormatTrait::UpperHex,
+				"b" => ast::FormatTrait::Binary,
+				"o" => ast::FormatTrait::Octal,
+				"e" => ast::FormatTrait::LowerExp,
+				"E" => ast::FormatTrait::UpperExp,
+				_ => return Err(format!("unknown format trait `{}`", name)),
+			},
+		};
+		spec.r#trait = r#trait;
+
+		Ok(spec)
+	}
+
+	/// Parses a width or precision, which is either an integer or an argument followed by a `$`.
+	fn parse_count(&mut self) -> Result<Option<ast::FormatCount>, String> {
+		let start = self.position;
+		if let Some(value) = self.parse_integer() {
+			if self.eat('$') {
+				Ok(Some(ast::FormatCount::Argument(self.resolve_index(value)?)))
+			} else {
+				Ok(Some(ast::FormatCount::Is(value)))
+			}
+		} else if let Some(name) = self.parse_identifier() {
+			if self.eat('$') {
+				Ok(Some(ast::FormatCount::Argument(self.resolve_name(&name)?)))
+			} else {
+				// not a count, but the format trait
+				self.position = start;
+				Ok(None)
+			}
+		} else {
+			Ok(None)
+		}
+	}
+
+	fn resolve_index(&mut self, index: usize) -> Result<usize, String> {
+		match self.used.get_mut(index) {
+			Some(used) => {
+				*used = true;
+				Ok(index)
+			}
+			None => Err(format!(
+				"invalid reference to positional argument {} ({})",
+				index,
+				match self.used.len() {
+					0 => "no arguments were given".to_owned(),
+					1 => "there is 1 argument".to_owned(),
+					len => format!("there are {} arguments", len),
+				}
+			)),
+		}
+	}
+
+	fn resolve_name(&mut self, name: &str) -> Result<usize, String> {
+		match self.names.get(name) {
+			Some(&index) => self.resolve_index(index),
+			None => Err(format!("there is no argument named `{}`", name)),
+		}
+	}
+
+	fn parse_integer(&mut self) -> Option<usize> {
+		let start = self.position;
+		while matches!(self.peek(0), Some(c) if c.is_ascii_digit()) {
+			self.position += 1;
+		}
+		if start == self.position {
+			None
+		} else {
+			self.chars[start..self.position].iter().collect::<String>().parse().ok()
+		}
+	}
+
+	fn parse_identifier(&mut self) -> Option<String> {
+		let start = self.position;
+		if matches!(self.peek(0), Some(c) if c == '_' || c.is_alphabetic()) {
+			while matches!(self.peek(0), Some(c) if c == '_' || c.is_alphanumeric()) {
+				self.position += 1;
+			}
+			Some(self.chars[start..self.position].iter().collect())
+		} else {
+			None
+		}
+	}
+
+	fn skip_whitespace(&mut self) {
+		while matches!(self.peek(0), Some(c) if c.is_whitespace()) {
+			self.position += 1;
+		}
+	}
+
+	fn peek(&self, offset: usize) -> Option<char> {
+		self.chars.get(self.position + offset).copied()
+	}
+
+	fn next(&mut self) -> Option<char> {
+		let c = self.peek(0)?;
+		self.position += 1;
+		Some(c)
+	}
+
+	fn eat(&mut self, c: char) -> bool {
+		if self.peek(0) == Some(c) {
+			self.position += 1;
+			true
+		} else {
+			false
+		}
+	}
+}
+
+fn parse_align(c: char) -> Option<ast::FormatAlign> {
+	match c {
+		'<' => Some(ast::FormatAlign::Left),
+		'^' => Some(ast::FormatAlign::Center),
+		'>' => Some(ast::FormatAlign::Right),
+		_ => None,
+	}
+}
@@ -1,11 +1,15 @@
 use num::BigInt;
-use syn::parse::Lookahead1;
+use syn::ext::IdentExt;
+use syn::parse::{Lookahead1, Parser};
 use syn::{
 	braced, bracketed, parenthesized, parse, Ident, LitBool, LitByte, LitByteStr, LitChar, LitInt, LitStr, Token,
 };
 
 use super::ast;
 
+mod format;
+mod pattern;
+
 #[cfg(test)]
 mod test;
 
@@ -20,8 +24,74 @@ impl parse::Parse for ast::Expression {
 	}
 }
 
+/// Parses a whole source as the contents of a block without braces, so that it may declare items and bind variables
+/// before its final expression, e.g., `const LIMIT: Integer = 10; LIMIT * 2`. A source that consists of a single
+/// expression is parsed as that expression.
+pub(crate) fn parse_statements(input: parse::ParseStream) -> parse::Result<ast::Expression> {
+	if input.is_empty() {
+		return Err(input.error("unexpected end of input, expected an expression"));
+	}
+	let mut spans = input
+		.fork()
+		.parse::<proc_macro2::TokenStream>()?
+		.into_iter()
+		.map(|token| token.span());
+	let first = spans.next().unwrap_or_else(|| input.span());
+	let span = spans.last().and_then(|last| first.join(last)).unwrap_or(first);
+
+	match parse_block_contents(input)? {
+		(statements, Some(expression)) if statements.is_empty() => Ok(*expression),
+		(statements, expression) => Ok(ast::Expression::Block(ast::Block {
+			statements,
+			expression,
+			span: span.into(),
+		})),
+	}
+}
+
+/// Parses the arguments of a call to the macro `name`, which takes `format_string_index` arguments before its format
+/// string.
+pub(crate) fn parse_format_arguments(
+	tokens: proc_macro2::TokenStream,
+	name: &str,
+	format_string_index: usize,
+) -> parse::Result<ast::FormatArguments> {
+	(|input: parse::ParseStream| format::parse_format_arguments(input, name, format_string_index)).parse2(tokens)
+}
+
 fn parse_expression(input: parse::ParseStream) -> parse::Result<(ast::Expression, Lookahead1)> {
-	parse_lazy_or_expression(input)
+	parse_range_expression(input)
+}
+
+fn parse_range_expression(input: parse::ParseStream) -> parse::Result<(ast::Expression, Lookahead1)> {
+	let (start, lookahead) = if input.peek(Token![..]) {
+		(None, None)
+	} else {
+		let (expression, lookahead) = parse_lazy_or_expression(input)?;
+		(Some(Box::new(expression)), Some(lookahead))
+	};
+
+	let limits = if input.peek(Token![..=]) {
+		ast::RangeLimits::Closed(input.parse::<Token![..=]>()?.spans[0].into())
+	} else if match &lookahead {
+		Some(lookahead) => lookahead.peek(Token![..]),
+		None => true,
+	} {
+		ast::RangeLimits::HalfOpen(input.parse::<Token![..]>()?.spans[0].into())
+	} else {
+		return Ok((*start.unwrap(), lookahead.unwrap()));
+	};
+
+	// A half-open range may omit its end, which is the case iff the range is followed by something that cannot start an
+	// expression. Closing delimiters are not visible here, as the input ends in front of them.
+	if let ast::RangeLimits::HalfOpen(_) = limits {
+		if input.is_empty() || input.peek(Token![,]) || input.peek(Token![;]) {
+			return Ok((ast::Expression::Range(start, limits, None), input.lookahead1()));
+		}
+	}
+
+	let (end, lookahead) = parse_lazy_or_expression(input)?;
+	Ok((ast::Expression::Range(start, limits, Some(Box::new(end))), lookahead))
 }
 
 fn parse_lazy_or_expression(input: parse::ParseStream) -> parse::Result<(ast::Expression, Lookahead1)> {
@@ -35,7 +105,7 @@ fn parse_lazy_or_expression(input: parse::ParseStream) -> parse::Result<(ast::Ex
 			expression = ast::Expression::Binary(
 				Box::new(expression),
 				Box::new(rhs_expression),
-				ast::BinaryOperator::LazyOr(token.spans[0]),
+				ast::BinaryOperator::LazyOr(token.spans[0].into()),
 			);
 			lookahead = rhs_lookahead;
 		} else {
@@ -55,7 +125,7 @@ fn parse_lazy_and_expression(input: parse::ParseStream) -> parse::Result<(ast::E
 			expression = ast::Expression::Binary(
 				Box::new(expression),
 				Box::new(rhs_expression),
-				ast::BinaryOperator::LazyAnd(token.spans[0]),
+				ast::BinaryOperator::LazyAnd(token.spans[0].into()),
 			);
 			lookahead = rhs_lookahead;
 		} else {
@@ -75,7 +145,7 @@ fn parse_cmp_expression(input: parse::ParseStream) -> parse::Result<(ast::Expres
 			ast::Expression::Binary(
 				Box::new(expression),
 				Box::new(rhs_expression),
-				ast::BinaryOperator::Equal(token.spans[0]),
+				ast::BinaryOperator::Equal(token.spans[0].into()),
 			),
 			rhs_lookahead,
 		))
@@ -86,7 +156,7 @@ fn parse_cmp_expression(input: parse::ParseStream) -> parse::Result<(ast::Expres
 			ast::Expression::Binary(
 				Box::new(expression),
 				Box::new(rhs_expression),
-				ast::BinaryOperator::NotEqual(token.spans[0]),
+				ast::BinaryOperator::NotEqual(token.spans[0].into()),
 			),
 			rhs_lookahead,
 		))
@@ -97,7 +167,7 @@ fn parse_cmp_expression(input: parse::ParseStream) -> parse::Result<(ast::Expres
 			ast::Expression::Binary(
 				Box::new(expression),
 				Box::new(rhs_expression),
-				ast::BinaryOperator::LessOrEqual(token.spans[0]),
+				ast::BinaryOperator::LessOrEqual(token.spans[0].into()),
 			),
 			rhs_lookahead,
 		))
@@ -108,7 +178,7 @@ fn parse_cmp_expression(input: parse::ParseStream) -> parse::Result<(ast::Expres
 			ast::Expression::Binary(
 				Box::new(expression),
 				Box::new(rhs_expression),
-				ast::BinaryOperator::GreaterOrEqual(token.spans[0]),
+				ast::BinaryOperator::GreaterOrEqual(token.spans[0].into()),
 			),
 			rhs_lookahead,
 		))
@@ -119,7 +189,7 @@ fn parse_cmp_expression(input: parse::ParseStream) -> parse::Result<(ast::Expres
 			ast::Expression::Binary(
 				Box::new(expression),
 				Box::new(rhs_expression),
-				ast::BinaryOperator::Less(token.spans[0]),
+				ast::BinaryOperator::Less(token.spans[0].into()),
 			),
 			rhs_lookahead,
 		))
@@ -130,7 +200,7 @@ fn parse_cmp_expression(input: parse::ParseStream) -> parse::Result<(ast::Expres
 			ast::Expression::Binary(
 				Box::new(expression),
 				Box::new(rhs_expression),
-				ast::BinaryOperator::Greater(token.spans[0]),
+				ast::BinaryOperator::Greater(token.spans[0].into()),
 			),
 			rhs_lookahead,
 		))
@@ -149,7 +219,7 @@ fn parse_bit_or_expression(input: parse::ParseStream) -> parse::Result<(ast::Exp
 			expression = ast::Expression::Binary(
 				Box::new(expression),
 				Box::new(rhs_expression),
-				ast::BinaryOperator::BitOr(token.spans[0]),
+				ast::BinaryOperator::BitOr(token.spans[0].into()),
 			);
 			lookahead = rhs_lookahead;
 		} else {
@@ -168,7 +238,7 @@ fn parse_bit_xor_expression(input: parse::ParseStream) -> parse::Result<(ast::Ex
 			expression = ast::Expression::Binary(
 				Box::new(expression),
 				Box::new(rhs_expression),
-				ast::BinaryOperator::BitXor(token.spans[0]),
+				ast::BinaryOperator::BitXor(token.spans[0].into()),
 			);
 			lookahead = rhs_lookahead;
 		} else {
@@ -187,7 +257,7 @@ fn parse_bit_and_expression(input: parse::ParseStream) -> parse::Result<(ast::Ex
 			expression = ast::Expression::Binary(
 				Box::new(expression),
 				Box::new(rhs_expression),
-				ast::BinaryOperator::BitAnd(token.spans[0]),
+				ast::BinaryOperator::BitAnd(token.spans[0].into()),
 			);
 			lookahead = rhs_lookahead;
 		} else {
@@ -207,7 +277,7 @@ fn parse_shift_expression(input: parse::ParseStream) -> parse::Result<(ast::Expr
 			expression = ast::Expression::Binary(
 				Box::new(expression),
 				Box::new(rhs_expression),
-				ast::BinaryOperator::Shl(token.spans[0]),
+				ast::BinaryOperator::Shl(token.spans[0].into()),
 			);
 			lookahead = rhs_lookahead;
 		} else if lookahead.peek(Token![>>]) {
@@ -216,7 +286,7 @@ fn parse_shift_expression(input: parse::ParseStream) -> parse::Result<(ast::Expr
 			expression = ast::Expression::Binary(
 				Box::new(expression),
 				Box::new(rhs_expression),
-				ast::BinaryOperator::Shr(token.spans[0]),
+				ast::BinaryOperator::Shr(token.spans[0].into()),
 			);
 			lookahead = rhs_lookahead;
 		} else {
@@ -235,7 +305,7 @@ fn parse_additive_expression(input: parse::ParseStream) -> parse::Result<(ast::E
 			expression = ast::Expression::Binary(
 				Box::new(expression),
 				Box::new(rhs_expression),
-				ast::BinaryOperator::Add(token.span),
+				ast::BinaryOperator::Add(token.span.into()),
 			);
 			lookahead = rhs_lookahead;
 		} else if lookahead.peek(Token![-]) {
@@ -244,7 +314,7 @@ fn parse_additive_expression(input: parse::ParseStream) -> parse::Result<(ast::E
 			expression = ast::Expression::Binary(
 				Box::new(expression),
 				Box::new(rhs_expression),
-				ast::BinaryOperator::Sub(token.span),
+				ast::BinaryOperator::Sub(token.span.into()),
 			);
 			lookahead = rhs_lookahead;
 		} else {
@@ -254,34 +324,34 @@ fn parse_additive_expression(input: parse::ParseStream) -> parse::Result<(ast::E
 }
 
 fn parse_multiplicative_expression(input: parse::ParseStream) -> parse::Result<(ast::Expression, Lookahead1)> {
-	let (mut expression, mut lookahead) = parse_prefix_expression(input)?;
+	let (mut expression, mut lookahead) = parse_cast_expression(input)?;
 
 	loop {
 		if lookahead.peek(Token![*]) {
 			let token = input.parse::<Token![*]>()?;
-			let (rhs_expression, rhs_lookahead) = parse_prefix_expression(input)?;
+			let (rhs_expression, rhs_lookahead) = parse_cast_expression(input)?;
 			expression = ast::Expression::Binary(
 				Box::new(expression),
 				Box::new(rhs_expression),
-				ast::BinaryOperator::Mul(token.span),
+				ast::BinaryOperator::Mul(token.span.into()),
 			);
 			lookahead = rhs_lookahead;
 		} else if lookahead.peek(Token![/]) {
 			let token = input.parse::<Token![/]>()?;
-			let (rhs_expression, rhs_lookahead) = parse_prefix_expression(input)?;
+			let (rhs_expression, rhs_lookahead) = parse_cast_expression(input)?;
 			expression = ast::Expression::Binary(
 				Box::new(expression),
 				Box::new(rhs_expression),
-				ast::BinaryOperator::Div(token.span),
+				ast::BinaryOperator::Div(token.span.into()),
 			);
 			lookahead = rhs_lookahead;
 		} else if lookahead.peek(Token![%]) {
 			let token = input.parse::<Token![%]>()?;
-			let (rhs_expression, rhs_lookahead) = parse_prefix_expression(input)?;
+			let (rhs_expression, rhs_lookahead) = parse_cast_expression(input)?;
 			expression = ast::Expression::Binary(
 				Box::new(expression),
 				Box::new(rhs_expression),
-				ast::BinaryOperator::Rem(token.span),
+				ast::BinaryOperator::Rem(token.span.into()),
 			);
 			lookahead = rhs_lookahead;
 		} else {
@@ -290,23 +360,392 @@ fn parse_multiplicative_expression(input: parse::ParseStream) -> parse::Result<(
 	}
 }
 
+fn parse_cast_expression(input: parse::ParseStream) -> parse::Result<(ast::Expression, Lookahead1)> {
+	let (mut expression, mut lookahead) = parse_prefix_expression(input)?;
+
+	loop {
+		if lookahead.peek(Token![as]) {
+			let token = input.parse::<Token![as]>()?;
+			let r#type = input.parse::<ast::Type>()?;
+			expression = ast::Expression::Cast(Box::new(expression), token.span.into(), r#type);
+			lookahead = input.lookahead1();
+		} else {
+			return Ok((expression, lookahead));
+		}
+	}
+}
+
 fn parse_prefix_expression(input: parse::ParseStream) -> parse::Result<(ast::Expression, Lookahead1)> {
 	let lookahead = input.lookahead1();
 	if lookahead.peek(Token![!]) {
 		let token = input.parse::<Token![!]>()?;
-		let prefix = ast::Prefix::Not(token.span);
+		let prefix = ast::Prefix::Not(token.span.into());
 		let (expression, lookahead) = parse_prefix_expression(input)?;
 		Ok((ast::Expression::Prefix(Box::new(expression), prefix), lookahead))
 	} else if lookahead.peek(Token![-]) {
 		let token = input.parse::<Token![-]>()?;
-		let prefix = ast::Prefix::Minus(token.span);
+		let prefix = ast::Prefix::Minus(token.span.into());
 		let (expression, lookahead) = parse_prefix_expression(input)?;
 		Ok((ast::Expression::Prefix(Box::new(expression), prefix), lookahead))
+	} else if lookahead.peek(syn::token::Brace) {
+		let block = parse_block(input)?;
+		Ok((ast::Expression::Block(block), input.lookahead1()))
 	} else {
 		parse_suffix_expression(input, lookahead)
 	}
 }
 
+/// Parses a block of statements that is optionally followed by an expression, e.g., `{ let x = 1; x + 1 }`.
+fn parse_block(input: parse::ParseStream) -> parse::Result<ast::Block> {
+	let braced;
+	braced!(braced in input);
+	let (statements, expression) = parse_block_contents(&braced)?;
+	Ok(ast::Block {
+		statements,
+		expression,
+		span: braced.span().into(),
+	})
+}
+
+/// Parses the statements and the optional final expression of a block without its braces.
+fn parse_block_contents(
+	input: parse::ParseStream,
+) -> parse::Result<(Vec<ast::Statement>, Option<Box<ast::Expression>>)> {
+	let mut statements = Vec::new();
+	let mut expression = None;
+	while !input.is_empty() {
+		if input.peek(Token![;]) {
+			input.parse::<Token![;]>()?;
+		} else if input.peek(Token![let]) {
+			statements.push(parse_let_statement(input)?);
+		} else if input.peek(Token![#])
+			|| input.peek(Token![struct])
+			|| input.peek(Token![enum])
+			|| input.peek(Token![const])
+			|| input.peek(Token![static])
+			|| input.peek(Token![impl])
+		{
+			statements.push(ast::Statement::Item(parse_block_item(input)?));
+		} else {
+			let (parsed, lookahead) = parse_expression(input)?;
+			if input.is_empty() {
+				expression = Some(Box::new(parsed));
+			} else if lookahead.peek(Token![;]) {
+				input.parse::<Token![;]>()?;
+				statements.push(ast::Statement::Expression(parsed));
+			} else if let ast::Expression::Block(_) = parsed {
+				statements.push(ast::Statement::Expression(parsed));
+			} else {
+				return Err(lookahead.error());
+			}
+		}
+	}
+	Ok((statements, expression))
+}
+
+/// Parses an item of a block and records its tokens as its source.
+fn parse_block_item(input: parse::ParseStream) -> parse::Result<ast::Item> {
+	let fork = input.fork();
+	let mut item = if input.peek(Token![const]) {
+		let token = input.parse::<Token![const]>()?;
+		ast::Item::Const(parse_global_item(input, token.span)?)
+	} else if input.peek(Token![static]) {
+		let token = input.parse::<Token![static]>()?;
+		if input.peek(Token![mut]) {
+			return Err(input.error("Mutable statics are not supported"));
+		}
+		ast::Item::Static(parse_global_item(input, token.span)?)
+	} else if input.peek(Token![impl]) {
+		ast::Item::Impl(parse_impl_item(input)?)
+	} else {
+		parse_item(input)?
+	};
+
+	let mut tokens = proc_macro2::TokenStream::new();
+	while fork.cursor() != input.cursor() {
+		tokens.extend(std::iter::once(fork.parse::<proc_macro2::TokenTree>()?));
+	}
+	let source = match &mut item {
+		ast::Item::Struct(item) => &mut item.source,
+		ast::Item::Enum(item) => &mut item.source,
+		ast::Item::Impl(item) => &mut item.source,
+		ast::Item::Const(item) | ast::Item::Static(item) => &mut item.source,
+	};
+	*source = tokens.to_string();
+	Ok(item)
+}
+
+/// Parses a `let` statement, e.g., `let x: Integer = 1;`. Only identifiers and `_` can be bound.
+fn parse_let_statement(input: parse::ParseStream) -> parse::Result<ast::Statement> {
+	let token = input.parse::<Token![let]>()?;
+	let is_mut = input.parse::<Option<Token![mut]>>()?.is_some();
+	let pattern_span = input.span();
+	let pattern = input.parse::<ast::Pattern>()?;
+	match &pattern {
+		ast::Pattern::Identifier(_, _) | ast::Pattern::Wildcard(_) => (),
+		_ => {
+			return Err(syn::Error::new(
+				pattern_span,
+				"Only identifiers and `_` can be bound by `let`",
+			))
+		}
+	}
+
+	let r#type = if input.peek(Token![:]) {
+		input.parse::<Token![:]>()?;
+		Some(input.parse::<ast::Type>()?)
+	} else {
+		None
+	};
+	if !input.peek(Token![=]) {
+		return Err(input.error("`let` statements require an initializer"));
+	}
+	input.parse::<Token![=]>()?;
+	let (expression, lookahead) = parse_expression(input)?;
+	if !lookahead.peek(Token![;]) {
+		return Err(lookahead.error());
+	}
+	input.parse::<Token![;]>()?;
+	Ok(ast::Statement::Let(
+		token.span.into(),
+		is_mut,
+		Box::new(pattern),
+		r#type,
+		Some(Box::new(expression)),
+	))
+}
+
+/// Parses a `struct` or `enum` declaration, e.g., `#[derive(Debug)] struct Meters(Integer);`. Neither generic
+/// parameters nor attributes other than `derive` are supported.
+fn parse_item(input: parse::ParseStream) -> parse::Result<ast::Item> {
+	let mut derives = Vec::new();
+	while input.peek(Token![#]) {
+		input.parse::<Token![#]>()?;
+		let attribute;
+		bracketed!(attribute in input);
+		let id = attribute.parse::<Ident>()?;
+		if id != "derive" {
+			return Err(syn::Error::new(id.span(), "Only `derive` attributes are supported"));
+		}
+		let names;
+		parenthesized!(names in attribute);
+		while !names.is_empty() {
+			let name = names.parse::<Ident>()?;
+			derives.push((name.to_string(), name.span().into()));
+			if !names.is_empty() {
+				names.parse::<Token![,]>()?;
+			}
+		}
+	}
+
+	let lookahead = input.lookahead1();
+	if lookahead.peek(Token![struct]) {
+		let token = input.parse::<Token![struct]>()?;
+		let name = parse_item_name(input)?;
+		let lookahead = input.lookahead1();
+		let body = if lookahead.peek(syn::token::Brace) {
+			parse_fields(input)?
+		} else if lookahead.peek(syn::token::Paren) || lookahead.peek(Token![;]) {
+			let fields = parse_fields(input)?;
+			input.parse::<Token![;]>()?;
+			fields
+		} else {
+			return Err(lookahead.error());
+		};
+		Ok(ast::Item::Struct(ast::TypeItem {
+			derives,
+			name: name.unraw().to_string(),
+			name_span: name.span().into(),
+			body,
+			span: token.span.into(),
+			source: String::new(),
+		}))
+	} else if lookahead.peek(Token![enum]) {
+		let token = input.parse::<Token![enum]>()?;
+		let name = parse_item_name(input)?;
+		let variants;
+		braced!(variants in input);
+		let mut body = Vec::new();
+		while !variants.is_empty() {
+			let variant_name = variants.parse::<Ident>()?;
+			body.push(ast::Variant {
+				name: variant_name.unraw().to_string(),
+				name_span: variant_name.span().into(),
+				fields: parse_fields(&variants)?,
+			});
+			if !variants.is_empty() {
+				variants.parse::<Token![,]>()?;
+			}
+		}
+		Ok(ast::Item::Enum(ast::TypeItem {
+			derives,
+			name: name.unraw().to_string(),
+			name_span: name.span().into(),
+			body,
+			span: token.span.into(),
+			source: String::new(),
+		}))
+	} else {
+		Err(lookahead.error())
+	}
+}
+
+/// Parses the remainder of a `const` or `static` item after its keyword, e.g., the `LIMIT: Integer = 10;` in
+/// `const LIMIT: Integer = 10;`.
+fn parse_global_item(input: parse::ParseStream, span: proc_macro2::Span) -> parse::Result<ast::GlobalItem> {
+	let name = input.parse::<Ident>()?;
+	input.parse::<Token![:]>()?;
+	let r#type = input.parse::<ast::Type>()?;
+	input.parse::<Token![=]>()?;
+	let (expression, lookahead) = parse_expression(input)?;
+	if !lookahead.peek(Token![;]) {
+		return Err(lookahead.error());
+	}
+	input.parse::<Token![;]>()?;
+	Ok(ast::GlobalItem {
+		name: name.unraw().to_string(),
+		name_span: name.span().into(),
+		r#type,
+		expression: Box::new(expression),
+		span: span.into(),
+		source: String::new(),
+	})
+}
+
+/// Parses an `impl` block, e.g., `impl Add for Meters { fn add(self, other: Meters) -> Meters { ... } }`. Neither generic
+/// parameters nor items other than functions are supported.
+fn parse_impl_item(input: parse::ParseStream) -> parse::Result<ast::ImplItem> {
+	let token = input.parse::<Token![impl]>()?;
+	if input.peek(Token![<]) {
+		return Err(input.error("Generic `impl` blocks are not supported"));
+	}
+	let self_type_span = input.span();
+	let mut self_type = input.parse::<ast::Type>()?;
+	let r#trait = if input.peek(Token![for]) {
+		input.parse::<Token![for]>()?;
+		let r#trait = match &self_type.shape {
+			ast::TypeShape::Path(name, generic_arguments) if generic_arguments.is_empty() => (name.clone(), self_type.span),
+			_ => return Err(syn::Error::new(self_type_span, "Expected the name of a trait")),
+		};
+		self_type = input.parse::<ast::Type>()?;
+		Some(r#trait)
+	} else {
+		None
+	};
+
+	let body;
+	braced!(body in input);
+	let mut functions = Vec::new();
+	while !body.is_empty() {
+		functions.push(parse_function_item(&body)?);
+	}
+	Ok(ast::ImplItem {
+		r#trait,
+		self_type,
+		functions,
+		span: token.span.into(),
+		source: String::new(),
+	})
+}
+
+/// Parses a function of an `impl` block, e.g., `fn scale(&self, factor: Integer) -> Meters { Meters(self.0 * factor) }`.
+fn parse_function_item(input: parse::ParseStream) -> parse::Result<ast::FunctionItem> {
+	input.parse::<Option<Token![pub]>>()?;
+	let token = input.parse::<Token![fn]>()?;
+	let name = input.parse::<Ident>()?;
+	if input.peek(Token![<]) {
+		return Err(input.error("Generic functions are not supported"));
+	}
+
+	let parameters;
+	parenthesized!(parameters in input);
+	let receiver = if parameters.peek(Token![self]) || (parameters.peek(Token![&]) && parameters.peek2(Token![self])) {
+		parameters.parse::<Option<Token![&]>>()?;
+		let receiver = parameters.parse::<Token![self]>()?;
+		if !parameters.is_empty() {
+			parameters.parse::<Token![,]>()?;
+		}
+		Some(receiver.span.into())
+	} else {
+		None
+	};
+	let mut named_parameters = Vec::new();
+	while !parameters.is_empty() {
+		let name = parameters.parse::<Ident>()?;
+		parameters.parse::<Token![:]>()?;
+		named_parameters.push((
+			name.unraw().to_string(),
+			name.span().into(),
+			parameters.parse::<ast::Type>()?,
+		));
+		if !parameters.is_empty() {
+			parameters.parse::<Token![,]>()?;
+		}
+	}
+
+	let return_type = if input.peek(Token![->]) {
+		input.parse::<Token![->]>()?;
+		Some(input.parse::<ast::Type>()?)
+	} else {
+		None
+	};
+	let body = parse_block(input)?;
+	Ok(ast::FunctionItem {
+		name: name.unraw().to_string(),
+		name_span: name.span().into(),
+		receiver,
+		parameters: named_parameters,
+		return_type,
+		body,
+		span: token.span.into(),
+	})
+}
+
+/// Parses the name of a declared type, which must not be followed by generic parameters.
+fn parse_item_name(input: parse::ParseStream) -> parse::Result<Ident> {
+	let name = input.parse::<Ident>()?;
+	if input.peek(Token![<]) {
+		return Err(input.error("Generic type declarations are not supported"));
+	}
+	Ok(name)
+}
+
+/// Parses the fields of a struct or variant declaration, e.g., the `(Integer)` in `Circle(Integer)` or the
+/// `{ w: Integer, h: Integer }` in `Rect { w: Integer, h: Integer }`. Unit structs and variants have no fields.
+fn parse_fields(input: parse::ParseStream) -> parse::Result<ast::Fields> {
+	if input.peek(syn::token::Brace) {
+		let fields;
+		braced!(fields in input);
+		let mut named_fields = Vec::new();
+		while !fields.is_empty() {
+			let name = fields.parse::<Ident>()?;
+			fields.parse::<Token![:]>()?;
+			named_fields.push((
+				name.unraw().to_string(),
+				name.span().into(),
+				fields.parse::<ast::Type>()?,
+			));
+			if !fields.is_empty() {
+				fields.parse::<Token![,]>()?;
+			}
+		}
+		Ok(ast::Fields::Struct(named_fields))
+	} else if input.peek(syn::token::Paren) {
+		let fields;
+		parenthesized!(fields in input);
+		let mut field_types = Vec::new();
+		while !fields.is_empty() {
+			field_types.push(fields.parse::<ast::Type>()?);
+			if !fields.is_empty() {
+				fields.parse::<Token![,]>()?;
+			}
+		}
+		Ok(ast::Fields::Tuple(field_types))
+	} else {
+		Ok(ast::Fields::Unit)
+	}
+}
+
 fn parse_suffix_expression<'a>(
 	input: parse::ParseStream<'a>,
 	lookahead: Lookahead1,
@@ -317,7 +756,7 @@ fn parse_suffix_expression<'a>(
 		let lookahead = input.lookahead1();
 		if lookahead.peek(Token![?]) {
 			let token = input.parse::<Token![?]>()?;
-			expression = ast::Expression::Suffix(Box::new(expression), ast::Suffix::Unwrap(token.span))
+			expression = ast::Expression::Suffix(Box::new(expression), ast::Suffix::Unwrap(token.span.into()))
 		} else if lookahead.peek(Token![.]) && !input.peek(Token![..]) {
 			input.parse::<Token![.]>()?;
 			expression = parse_field_expression(input, expression)?;
@@ -327,7 +766,7 @@ fn parse_suffix_expression<'a>(
 			let index = bracketed.parse::<ast::Expression>()?;
 			expression = ast::Expression::Suffix(
 				Box::new(expression),
-				ast::Suffix::ArrayIndex(Box::new(index), bracketed.span()),
+				ast::Suffix::ArrayIndex(Box::new(index), bracketed.span().into()),
 			)
 		} else {
 			return Ok((expression, lookahead));
@@ -341,25 +780,47 @@ fn parse_field_expression(input: parse::ParseStream, expression: ast::Expression
 		let id = input.parse::<Ident>()?;
 
 		let lookahead = input.lookahead1();
-		if lookahead.peek(syn::token::Paren) {
+		if lookahead.peek(Token![::]) {
+			input.parse::<Token![::]>()?;
+			let generic_arguments = parse_generic_arguments(input)?;
+			let parenthesized;
+			parenthesized!(parenthesized in input);
+			let arguments = parenthesized.parse::<ast::Arguments>()?;
+			Ok(ast::Expression::Suffix(
+				Box::new(expression),
+				ast::Suffix::FunctionCall(
+					id.unraw().to_string(),
+					id.span().into(),
+					generic_arguments,
+					arguments,
+					parenthesized.span().into(),
+				),
+			))
+		} else if lookahead.peek(syn::token::Paren) {
 			let parenthesized;
 			parenthesized!(parenthesized in input);
 			let arguments = parenthesized.parse::<ast::Arguments>()?;
 			Ok(ast::Expression::Suffix(
 				Box::new(expression),
-				ast::Suffix::FunctionCall(id.to_string(), id.span(), arguments, parenthesized.span()),
+				ast::Suffix::FunctionCall(
+					id.unraw().to_string(),
+					id.span().into(),
+					Vec::new(),
+					arguments,
+					parenthesized.span().into(),
+				),
 			))
 		} else {
 			Ok(ast::Expression::Suffix(
 				Box::new(expression),
-				ast::Suffix::Field(id.to_string(), id.span()),
+				ast::Suffix::Field(id.unraw().to_string(), id.span().into()),
 			))
 		}
 	} else if lookahead.peek(LitInt) {
 		let tuple_index = input.parse::<LitInt>()?;
 		Ok(ast::Expression::Suffix(
 			Box::new(expression),
-			ast::Suffix::TupleIndex(tuple_index.base10_parse()?, tuple_index.span()),
+			ast::Suffix::TupleIndex(tuple_index.base10_parse()?, tuple_index.span().into()),
 		))
 	} else {
 		Err(lookahead.error())
@@ -371,7 +832,7 @@ fn parse_atomic_expression(input: parse::ParseStream, lookahead: Lookahead1) ->
 		let parenthesized;
 		parenthesized!(parenthesized in input);
 		if parenthesized.is_empty() {
-			return Ok(ast::AtomicExpression::Unit(parenthesized.span()));
+			return Ok(ast::AtomicExpression::Unit(parenthesized.span().into()));
 		}
 
 		let (expression, lookahead) = parse_expression(&parenthesized)?;
@@ -379,7 +840,7 @@ fn parse_atomic_expression(input: parse::ParseStream, lookahead: Lookahead1) ->
 		if parenthesized.is_empty() {
 			Ok(ast::AtomicExpression::Parenthesized(
 				Box::new(expression),
-				parenthesized.span(),
+				parenthesized.span().into(),
 			))
 		} else if lookahead.peek(Token![,]) {
 			parenthesized.parse::<Token![,]>()?;
@@ -398,7 +859,7 @@ fn parse_atomic_expression(input: parse::ParseStream, lookahead: Lookahead1) ->
 					return Err(lookahead.error());
 				}
 			}
-			Ok(ast::AtomicExpression::Tuple(tuple, parenthesized.span()))
+			Ok(ast::AtomicExpression::Tuple(tuple, parenthesized.span().into()))
 		} else {
 			Err(lookahead.error())
 		}
@@ -418,10 +879,10 @@ fn parse_atomic_expression(input: parse::ParseStream, lookahead: Lookahead1) ->
 				return Err(lookahead.error());
 			}
 		}
-		Ok(ast::AtomicExpression::Array(array, bracketed.span()))
+		Ok(ast::AtomicExpression::Array(array, bracketed.span().into()))
 	} else if lookahead.peek(LitBool) {
 		let lit_bool = input.parse::<LitBool>()?;
-		Ok(ast::AtomicExpression::LitBool(lit_bool.value, lit_bool.span))
+		Ok(ast::AtomicExpression::LitBool(lit_bool.value, lit_bool.span.into()))
 	} else if lookahead.peek(LitInt) {
 		let lit_int = input.parse::<LitInt>()?;
 		if lit_int.suffix() != "" {
@@ -432,28 +893,29 @@ fn parse_atomic_expression(input: parse::ParseStream, lookahead: Lookahead1) ->
 		}
 		Ok(ast::AtomicExpression::LitInt(
 			lit_int.base10_parse::<BigInt>()?,
-			lit_int.span(),
+			lit_int.span().into(),
 		))
 	} else if lookahead.peek(LitByte) {
 		let lit_byte = input.parse::<LitByte>()?;
-		Ok(ast::AtomicExpression::LitByte(lit_byte.value(), lit_byte.span()))
+		Ok(ast::AtomicExpression::LitByte(lit_byte.value(), lit_byte.span().into()))
 	} else if lookahead.peek(LitByteStr) {
 		let lit_byte_str = input.parse::<LitByteStr>()?;
 		Ok(ast::AtomicExpression::LitByteStr(
 			lit_byte_str.value(),
-			lit_byte_str.span(),
+			lit_byte_str.span().into(),
 		))
 	} else if lookahead.peek(LitChar) {
 		let lit_char = input.parse::<LitChar>()?;
-		Ok(ast::AtomicExpression::LitChar(lit_char.value(), lit_char.span()))
+		Ok(ast::AtomicExpression::LitChar(lit_char.value(), lit_char.span().into()))
 	} else if lookahead.peek(LitStr) {
 		let lit_str = input.parse::<LitStr>()?;
-		Ok(ast::AtomicExpression::LitStr(lit_str.value(), lit_str.span()))
+		Ok(ast::AtomicExpression::LitStr(lit_str.value(), lit_str.span().into()))
 	} else if lookahead.peek(Token![$]) {
 		let token = input.parse::<Token![$]>()?;
-		Ok(ast::AtomicExpression::Dollar(token.spans[0]))
-	} else if lookahead.peek(Ident) {
-		let id = input.parse::<Ident>()?;
+		Ok(ast::AtomicExpression::Dollar(token.spans[0].into()))
+	} else if lookahead.peek(Ident) || lookahead.peek(Token![self]) {
+		// `self` can only be bound as the receiver of a function in an `impl` block
+		let id = input.call(Ident::parse_any)?;
 
 		let lookahead = input.lookahead1();
 		if lookahead.peek(syn::token::Paren) {
@@ -462,57 +924,307 @@ fn parse_atomic_expression(input: parse::ParseStream, lookahead: Lookahead1) ->
 			let (arguments, arguments_span) = (arguments.parse::<ast::Arguments>()?, arguments.span());
 			Ok(ast::AtomicExpression::FunctionCall(
 				id.to_string(),
-				id.span(),
+				id.span().into(),
 				arguments,
-				arguments_span,
+				arguments_span.into(),
 			))
 		} else if lookahead.peek(Token![!]) {
 			input.parse::<Token![!]>()?;
 			let lookahead = input.lookahead1();
-			let (arguments, arguments_span) = if lookahead.peek(syn::token::Paren) {
-				let arguments;
+			let arguments;
+			if lookahead.peek(syn::token::Paren) {
 				parenthesized!(arguments in input);
-				(arguments.parse::<ast::Arguments>()?, arguments.span())
 			} else if lookahead.peek(syn::token::Brace) {
-				let arguments;
 				braced!(arguments in input);
-				(arguments.parse::<ast::Arguments>()?, arguments.span())
 			} else if lookahead.peek(syn::token::Bracket) {
-				let arguments;
 				bracketed!(arguments in input);
-				(arguments.parse::<ast::Arguments>()?, arguments.span())
 			} else {
 				return Err(lookahead.error());
-			};
+			}
+			let name = id.to_string();
+			let arguments_span = arguments.span();
+			let tokens = ast::SourceTokens::new(arguments.fork().parse::<proc_macro2::TokenStream>()?);
+			// the arguments of most macros are expressions, while the others parse their arguments once they are called
+			let fork = arguments.fork();
+			let parsed = fork.parse::<ast::Arguments>().ok().filter(|_| fork.is_empty());
+			arguments.parse::<proc_macro2::TokenStream>()?;
 			Ok(ast::AtomicExpression::MacroCall(
-				id.to_string(),
-				id.span(),
-				arguments,
-				arguments_span,
+				name,
+				id.span().into(),
+				ast::MacroArguments {
+					tokens,
+					arguments: parsed,
+				},
+				arguments_span.into(),
 			))
 		} else {
-			Err(lookahead.error())
+			let path = parse_path(input, id)?;
+			if input.peek(syn::token::Paren) {
+				let arguments;
+				parenthesized!(arguments in input);
+				let (arguments, arguments_span) = (arguments.parse::<ast::Arguments>()?, arguments.span());
+				Ok(ast::AtomicExpression::PathCall(path, arguments, arguments_span.into()))
+			} else if input.peek(syn::token::Brace) {
+				let fields;
+				braced!(fields in input);
+				let fields_span = fields.span();
+				Ok(ast::AtomicExpression::StructLiteral(
+					path,
+					parse_field_values(&fields)?,
+					fields_span.into(),
+				))
+			} else {
+				Ok(ast::AtomicExpression::Path(path))
+			}
 		}
 	} else {
 		Err(lookahead.error())
 	}
 }
 
+/// Parses the remainder of a path that starts with `first`, e.g., the `::option::Option::<Integer>::Some` in
+/// `std::option::Option::<Integer>::Some`.
+fn parse_path(input: parse::ParseStream, first: Ident) -> parse::Result<ast::Path> {
+	let mut segments = vec![ast::PathSegment {
+		name: first.unraw().to_string(),
+		generic_arguments: Vec::new(),
+		span: first.span().into(),
+	}];
+	while input.peek(Token![::]) {
+		input.parse::<Token![::]>()?;
+		let lookahead = input.lookahead1();
+		if lookahead.peek(Token![<]) && segments.last().unwrap().generic_arguments.is_empty() {
+			segments.last_mut().unwrap().generic_arguments = parse_generic_arguments(input)?;
+		} else if lookahead.peek(Ident) {
+			let id = input.parse::<Ident>()?;
+			segments.push(ast::PathSegment {
+				name: id.unraw().to_string(),
+				generic_arguments: Vec::new(),
+				span: id.span().into(),
+			});
+		} else {
+			return Err(lookahead.error());
+		}
+	}
+	Ok(ast::Path { segments })
+}
+
+/// Parses the fields of a struct literal, e.g., the `x: 1, y: 2` in `Point { x: 1, y: 2 }`.
+fn parse_field_values(input: parse::ParseStream) -> parse::Result<Vec<ast::FieldValue>> {
+	let mut fields = Vec::new();
+	while !input.is_empty() {
+		let id = input.parse::<Ident>()?;
+		input.parse::<Token![:]>()?;
+		let (expression, lookahead) = parse_expression(input)?;
+		fields.push(ast::FieldValue {
+			name: id.unraw().to_string(),
+			expression,
+			span: id.span().into(),
+		});
+
+		if input.is_empty() {
+			break;
+		}
+
+		if lookahead.peek(Token![,]) {
+			input.parse::<Token![,]>()?;
+		} else {
+			return Err(lookahead.error());
+		}
+	}
+	Ok(fields)
+}
+
+/// Parses the `<A, B>` part of a turbofish.
+fn parse_generic_arguments(input: parse::ParseStream) -> parse::Result<Vec<ast::Type>> {
+	input.parse::<Token![<]>()?;
+	let mut types = Vec::new();
+	loop {
+		types.push(input.parse::<ast::Type>()?);
+		let lookahead = input.lookahead1();
+		if lookahead.peek(Token![,]) {
+			input.parse::<Token![,]>()?;
+			if input.peek(Token![>]) {
+				break;
+			}
+		} else if lookahead.peek(Token![>]) {
+			break;
+		} else {
+			return Err(lookahead.error());
+		}
+	}
+	input.parse::<Token![>]>()?;
+	Ok(types)
+}
+
+impl parse::Parse for ast::Type {
+	fn parse(input: parse::ParseStream) -> parse::Result<Self> {
+		let lookahead = input.lookahead1();
+		if lookahead.peek(Token![&]) {
+			// references are treated as the values that they refer to
+			input.parse::<Token![&]>()?;
+			input.parse::<Option<Token![mut]>>()?;
+			input.parse::<ast::Type>()
+		} else if lookahead.peek(syn::token::Paren) {
+			let parenthesized;
+			parenthesized!(parenthesized in input);
+			let mut elements = Vec::new();
+			let mut trailing_comma = false;
+			while !parenthesized.is_empty() {
+				elements.push(parenthesized.parse::<ast::Type>()?);
+				if parenthesized.is_empty() {
+					trailing_comma = false;
+					break;
+				}
+				parenthesized.parse::<Token![,]>()?;
+				trailing_comma = true;
+			}
+			if elements.len() == 1 && !trailing_comma {
+				Ok(elements.pop().unwrap())
+			} else {
+				Ok(new_type(ast::TypeShape::Tuple(elements), parenthesized.span()))
+			}
+		} else if lookahead.peek(syn::token::Bracket) {
+			let bracketed;
+			bracketed!(bracketed in input);
+			let element_type = bracketed.parse::<ast::Type>()?;
+			if bracketed.peek(Token![;]) {
+				return Err(bracketed.error("Arrays with a fixed length are not supported"));
+			} else if !bracketed.is_empty() {
+				return Err(bracketed.error("expected `]`"));
+			}
+			Ok(new_type(
+				ast::TypeShape::Array(Box::new(element_type)),
+				bracketed.span(),
+			))
+		} else if lookahead.peek(Ident) || lookahead.peek(Token![::]) {
+			parse_type_path(input)
+		} else {
+			Err(lookahead.error())
+		}
+	}
+}
+
+/// Parses a named type, e.g., `std::option::Option<Integer>`. The generic arguments may also be written as a turbofish,
+/// and `Vec<T>` is the Rust spelling of the array type `[T]`.
+fn parse_type_path(input: parse::ParseStream) -> parse::Result<ast::Type> {
+	input.parse::<Option<Token![::]>>()?;
+	let first = input.parse::<Ident>()?;
+	let span = first.span();
+	let mut name = first.unraw().to_string();
+	// `::` consists of two tokens
+	while input.peek(Token![::]) && input.peek3(Ident) {
+		input.parse::<Token![::]>()?;
+		name.push_str("::");
+		name.push_str(&input.parse::<Ident>()?.unraw().to_string());
+	}
+
+	if input.peek(Token![::]) && input.peek3(Token![<]) {
+		input.parse::<Token![::]>()?;
+	}
+	if !input.peek(Token![<]) || input.peek(Token![<=]) || input.peek(Token![<<]) {
+		return Ok(new_type(ast::TypeShape::Path(name, Vec::new()), span));
+	}
+	let mut generic_arguments = parse_generic_arguments(input)?;
+	if input.peek(Token![::]) && input.peek3(Ident) {
+		return Err(input.error("Generic arguments are only supported on the last segment of a type"));
+	}
+
+	if name == "Vec" && generic_arguments.len() == 1 {
+		let element_type = generic_arguments.pop().unwrap();
+		Ok(new_type(ast::TypeShape::Array(Box::new(element_type)), span))
+	} else {
+		Ok(new_type(ast::TypeShape::Path(name, generic_arguments), span))
+	}
+}
+
+/// Creates a type whose name is normalized to the way types are named in the `Context`.
+fn new_type(shape: ast::TypeShape, span: proc_macro2::Span) -> ast::Type {
+	let name = match &shape {
+		ast::TypeShape::Path(name, generic_arguments) if generic_arguments.is_empty() => name.clone(),
+		ast::TypeShape::Path(name, generic_arguments) => format!(
+			"{}<{}>",
+			name,
+			generic_arguments
+				.iter()
+				.map(|generic_argument| generic_argument.name.as_str())
+				.collect::<Vec<&str>>()
+				.join(", ")
+		),
+		ast::TypeShape::Array(element_type) => crate::types::make_array_name(&element_type.name),
+		ast::TypeShape::Tuple(element_types) => {
+			crate::types::make_tuple_name(element_types.iter().map(|element_type| &element_type.name))
+		}
+	};
+	ast::Type {
+		name,
+		shape,
+		span: span.into(),
+	}
+}
+
 impl parse::Parse for ast::Prefix {
 	fn parse(input: parse::ParseStream) -> parse::Result<Self> {
 		let lookahead = input.lookahead1();
 		if lookahead.peek(Token![!]) {
 			let token = input.parse::<Token![!]>()?;
-			Ok(ast::Prefix::Not(token.span))
+			Ok(ast::Prefix::Not(token.span.into()))
 		} else if lookahead.peek(Token![-]) {
 			let token = input.parse::<Token![-]>()?;
-			Ok(ast::Prefix::Minus(token.span))
+			Ok(ast::Prefix::Minus(token.span.into()))
 		} else {
 			Err(lookahead.error())
 		}
 	}
 }
 
+/// The tokens of each argument in a comma-separated list of expressions.
+pub(crate) struct ArgumentTokens(pub Vec<proc_macro2::TokenStream>);
+
+impl parse::Parse for ArgumentTokens {
+	fn parse(input: parse::ParseStream) -> parse::Result<Self> {
+		Ok(ArgumentTokens(parse_argument_tokens(input, usize::MAX)?))
+	}
+}
+
+/// Splits off the tokens of the first `count` arguments of a comma-separated list of expressions and ignores the rest,
+/// e.g., the format string that follows the condition of `assert!`.
+pub(crate) fn parse_argument_tokens(
+	input: parse::ParseStream,
+	count: usize,
+) -> parse::Result<Vec<proc_macro2::TokenStream>> {
+	let mut arguments = Vec::new();
+	while !input.is_empty() && arguments.len() < count {
+		let begin = input.cursor();
+		let (_expression, lookahead) = parse_expression(input)?;
+		let end = input.cursor();
+
+		let mut tokens = proc_macro2::TokenStream::new();
+		let mut cursor = begin;
+		while cursor != end {
+			let (token_tree, next) = cursor.token_tree().unwrap();
+			tokens.extend(std::iter::once(token_tree));
+			cursor = next;
+		}
+		arguments.push(tokens);
+
+		if input.is_empty() {
+			break;
+		}
+
+		if lookahead.peek(Token![,]) {
+			input.parse::<Token![,]>()?;
+		} else {
+			return Err(lookahead.error());
+		}
+	}
+	if arguments.len() == count {
+		input.parse::<proc_macro2::TokenStream>()?;
+	}
+
+	Ok(arguments)
+}
+
 impl parse::Parse for ast::Arguments {
 	fn parse(input: parse::ParseStream) -> parse::Result<Self> {
 		let mut arguments = Vec::new();
@@ -0,0 +1,199 @@
+use num::BigInt;
+use syn::ext::IdentExt;
+use syn::{braced, bracketed, parenthesized, parse, Ident, LitBool, LitByte, LitChar, LitInt, LitStr, Token};
+
+use super::super::ast;
+use super::parse_expression;
+
+impl parse::Parse for ast::Pattern {
+	fn parse(input: parse::ParseStream) -> parse::Result<Self> {
+		input.parse::<Option<Token![|]>>()?;
+		let first = parse_single_pattern(input)?;
+		if !input.peek(Token![|]) || input.peek(Token![||]) {
+			return Ok(first);
+		}
+
+		let span = first.span();
+		let mut patterns = vec![first];
+		while input.peek(Token![|]) && !input.peek(Token![||]) {
+			input.parse::<Token![|]>()?;
+			patterns.push(parse_single_pattern(input)?);
+		}
+		Ok(ast::Pattern::Or(patterns, span))
+	}
+}
+
+impl parse::Parse for ast::MatchesArguments {
+	fn parse(input: parse::ParseStream) -> parse::Result<Self> {
+		let (expression, lookahead) = parse_expression(input)?;
+		if !lookahead.peek(Token![,]) {
+			return Err(lookahead.error());
+		}
+		input.parse::<Token![,]>()?;
+		let pattern = input.parse::<ast::Pattern>()?;
+		if input.peek(Token![if]) {
+			return Err(input.error("match guards are not supported"));
+		}
+		input.parse::<Option<Token![,]>>()?;
+		if !input.is_empty() {
+			return Err(input.error("unexpected token after pattern"));
+		}
+		Ok(ast::MatchesArguments { expression, pattern })
+	}
+}
+
+fn parse_single_pattern(input: parse::ParseStream) -> parse::Result<ast::Pattern> {
+	let lookahead = input.lookahead1();
+	if lookahead.peek(Token![_]) {
+		let token = input.parse::<Token![_]>()?;
+		Ok(ast::Pattern::Wildcard(token.span.into()))
+	} else if lookahead.peek(Token![..=]) {
+		let token = input.parse::<Token![..=]>()?;
+		let end = parse_literal(input)?;
+		Ok(ast::Pattern::Range(
+			None,
+			ast::RangeLimits::Closed(token.spans[0].into()),
+			Some(Box::new(end)),
+		))
+	} else if lookahead.peek(Token![..]) {
+		let token = input.parse::<Token![..]>()?;
+		Ok(ast::Pattern::Rest(token.spans[0].into()))
+	} else if lookahead.peek(syn::token::Paren) {
+		let content;
+		parenthesized!(content in input);
+		let (mut patterns, trailing_comma) = parse_pattern_list(&content)?;
+		if patterns.len() == 1 && !trailing_comma {
+			Ok(patterns.pop().unwrap())
+		} else {
+			Ok(ast::Pattern::Tuple(patterns, content.span().into()))
+		}
+	} else if lookahead.peek(syn::token::Bracket) {
+		let content;
+		bracketed!(content in input);
+		let (patterns, _trailing_comma) = parse_pattern_list(&content)?;
+		Ok(ast::Pattern::Array(patterns, content.span().into()))
+	} else if lookahead.peek(LitBool)
+		|| lookahead.peek(LitInt)
+		|| lookahead.peek(LitByte)
+		|| lookahead.peek(LitChar)
+		|| lookahead.peek(LitStr)
+		|| lookahead.peek(Token![-])
+	{
+		let start = parse_literal(input)?;
+		if input.peek(Token![..=]) {
+			let token = input.parse::<Token![..=]>()?;
+			let end = parse_literal(input)?;
+			Ok(ast::Pattern::Range(
+				Some(Box::new(start)),
+				ast::RangeLimits::Closed(token.spans[0].into()),
+				Some(Box::new(end)),
+			))
+		} else if input.peek(Token![..]) {
+			let token = input.parse::<Token![..]>()?;
+			let end = if input.peek(LitInt) || input.peek(LitByte) || input.peek(LitChar) || input.peek(Token![-]) {
+				Some(Box::new(parse_literal(input)?))
+			} else {
+				None
+			};
+			Ok(ast::Pattern::Range(
+				Some(Box::new(start)),
+				ast::RangeLimits::HalfOpen(token.spans[0].into()),
+				end,
+			))
+		} else {
+			Ok(ast::Pattern::Literal(start))
+		}
+	} else if lookahead.peek(Ident) || lookahead.peek(Token![::]) {
+		let span = input.span();
+		input.parse::<Option<Token![::]>>()?;
+		let mut path = vec![input.parse::<Ident>()?.unraw().to_string()];
+		while input.peek(Token![::]) {
+			input.parse::<Token![::]>()?;
+			path.push(input.parse::<Ident>()?.unraw().to_string());
+		}
+
+		if input.peek(syn::token::Paren) {
+			let content;
+			parenthesized!(content in input);
+			let (patterns, _trailing_comma) = parse_pattern_list(&content)?;
+			Ok(ast::Pattern::TupleStruct(path, patterns, span.into()))
+		} else if input.peek(syn::token::Brace) {
+			let content;
+			braced!(content in input);
+			let mut fields = Vec::new();
+			let mut rest = false;
+			while !content.is_empty() {
+				if content.peek(Token![..]) {
+					content.parse::<Token![..]>()?;
+					rest = true;
+					break;
+				}
+				let name = content.parse::<Ident>()?;
+				let pattern = if content.peek(Token![:]) {
+					content.parse::<Token![:]>()?;
+					content.parse::<ast::Pattern>()?
+				} else {
+					ast::Pattern::Identifier(name.unraw().to_string(), name.span().into())
+				};
+				fields.push((name.unraw().to_string(), pattern));
+				if content.is_empty() {
+					break;
+				}
+				content.parse::<Token![,]>()?;
+			}
+			Ok(ast::Pattern::Struct(path, fields, rest, span.into()))
+		} else if path.len() == 1 {
+			Ok(ast::Pattern::Identifier(path.pop().unwrap(), span.into()))
+		} else {
+			Ok(ast::Pattern::Path(path, span.into()))
+		}
+	} else {
+		Err(lookahead.error())
+	}
+}
+
+/// Parses a comma-separated list of patterns, returning whether the list ended with a comma.
+fn parse_pattern_list(input: parse::ParseStream) -> parse::Result<(Vec<ast::Pattern>, bool)> {
+	let mut patterns = Vec::new();
+	while !input.is_empty() {
+		patterns.push(input.parse::<ast::Pattern>()?);
+		if input.is_empty() {
+			return Ok((patterns, false));
+		}
+		input.parse::<Token![,]>()?;
+	}
+	Ok((patterns, true))
+}
+
+fn parse_literal(input: parse::ParseStream) -> parse::Result<ast::AtomicExpression> {
+	if let Some(minus) = input.parse::<Option<Token![-]>>()? {
+		let lit_int = input.parse::<LitInt>()?;
+		return Ok(ast::AtomicExpression::LitInt(
+			-lit_int.base10_parse::<BigInt>()?,
+			minus.span.join(lit_int.span()).unwrap_or(minus.span).into(),
+		));
+	}
+
+	let lookahead = input.lookahead1();
+	if lookahead.peek(LitBool) {
+		let lit_bool = input.parse::<LitBool>()?;
+		Ok(ast::AtomicExpression::LitBool(lit_bool.value, lit_bool.span.into()))
+	} else if lookahead.peek(LitInt) {
+		let lit_int = input.parse::<LitInt>()?;
+		Ok(ast::AtomicExpression::LitInt(
+			lit_int.base10_parse::<BigInt>()?,
+			lit_int.span().into(),
+		))
+	} else if lookahead.peek(LitByte) {
+		let lit_byte = input.parse::<LitByte>()?;
+		Ok(ast::AtomicExpression::LitByte(lit_byte.value(), lit_byte.span().into()))
+	} else if lookahead.peek(LitChar) {
+		let lit_char = input.parse::<LitChar>()?;
+		Ok(ast::AtomicExpression::LitChar(lit_char.value(), lit_char.span().into()))
+	} else if lookahead.peek(LitStr) {
+		let lit_str = input.parse::<LitStr>()?;
+		Ok(ast::AtomicExpression::LitStr(lit_str.value(), lit_str.span().into()))
+	} else {
+		Err(lookahead.error())
+	}
+}
@@ -17,3 +17,327 @@ fn string_lit() {
 		),
 	}
 }
+
+#[test]
+fn range() {
+	let result = syn::parse_str::<ast::Expression>("1..").unwrap();
+	match result {
+		ast::Expression::Range(Some(_), ast::RangeLimits::HalfOpen(_), None) => (),
+		_ => panic!(
+			"Expression was expected to be a range from 1, but is {:#?} instead.",
+			result
+		),
+	}
+
+	let result = syn::parse_str::<ast::Expression>("..=1 + 2").unwrap();
+	match result {
+		ast::Expression::Range(None, ast::RangeLimits::Closed(_), Some(end)) => match *end {
+			ast::Expression::Binary(_, _, ast::BinaryOperator::Add(_)) => (),
+			_ => panic!("Range end was expected to be an addition, but is {:#?} instead.", end),
+		},
+		_ => panic!(
+			"Expression was expected to be a range to 3, but is {:#?} instead.",
+			result
+		),
+	}
+
+	for source in &["(1..)", "[1.., 2..]", "{ 1.. }", "{ let r = 1..; r }"] {
+		syn::parse_str::<ast::Expression>(source).unwrap();
+	}
+
+	syn::parse_str::<ast::Expression>("1..=").unwrap_err();
+}
+
+#[test]
+fn turbofish() {
+	let result = syn::parse_str::<ast::Expression>("$.parse::<Option<(Integer, [bool])>>()").unwrap();
+	match result {
+		ast::Expression::Suffix(_, ast::Suffix::FunctionCall(name, _, generic_arguments, _, _)) => {
+			assert_eq!(name, "parse");
+			assert_eq!(generic_arguments.len(), 1);
+			assert_eq!(generic_arguments[0].name, "Option<(Integer, [bool])>");
+		}
+		_ => panic!(
+			"Expression was expected to be a member function call, but is {:#?} instead.",
+			result
+		),
+	}
+}
+
+#[test]
+fn path() {
+	let result = syn::parse_str::<ast::Expression>("std::option::Option::<[bool]>::Some(vec![true])").unwrap();
+	match result {
+		ast::Expression::Atomic(ast::AtomicExpression::PathCall(path, arguments, _)) => {
+			let names = path
+				.segments
+				.iter()
+				.map(|segment| segment.name.as_str())
+				.collect::<Vec<_>>();
+			assert_eq!(names, ["std", "option", "Option", "Some"]);
+			assert_eq!(path.segments[2].generic_arguments.len(), 1);
+			assert_eq!(path.segments[2].generic_arguments[0].name, "[bool]");
+			assert_eq!(arguments.arguments.len(), 1);
+		}
+		_ => panic!(
+			"Expression was expected to be a path call, but is {:#?} instead.",
+			result
+		),
+	}
+
+	let result = syn::parse_str::<ast::Expression>("Shape::Rect { w: 1, r#type: 2 }").unwrap();
+	match result {
+		ast::Expression::Atomic(ast::AtomicExpression::StructLiteral(path, fields, _)) => {
+			assert_eq!(path.segments.len(), 2);
+			let names = fields.iter().map(|field| field.name.as_str()).collect::<Vec<_>>();
+			assert_eq!(names, ["w", "type"]);
+		}
+		_ => panic!(
+			"Expression was expected to be a struct literal, but is {:#?} instead.",
+			result
+		),
+	}
+
+	assert!(syn::parse_str::<ast::Expression>("Option::<Integer>::<bool>::None").is_err());
+	assert!(syn::parse_str::<ast::Expression>("Point { x }").is_err());
+}
+
+fn parse_format(source: &str, name: &str, format_string_index: usize) -> syn::Result<ast::FormatArguments> {
+	parse_format_arguments(source.parse().unwrap(), name, format_string_index)
+}
+
+#[test]
+fn format_string() {
+	let arguments = parse_format(r#"$, "{{{x:>0$?}}} {}", x = 1"#, "write", 1).unwrap();
+	assert_eq!(arguments.leading_arguments.len(), 1);
+	assert_eq!(arguments.arguments.len(), 1);
+	let pieces = arguments.format_string.unwrap().pieces;
+	assert_eq!(pieces.len(), 4);
+	match &pieces[1] {
+		ast::FormatPiece::Placeholder { argument, spec } => {
+			assert_eq!(*argument, 0);
+			assert!(matches!(spec.align, Some(ast::FormatAlign::Right)));
+			assert!(matches!(spec.width, Some(ast::FormatCount::Argument(0))));
+			assert_eq!(spec.r#trait, ast::FormatTrait::Debug);
+		}
+		piece => panic!("Expected a placeholder, but got {:?}", piece),
+	}
+
+	assert!(parse_format(r#""{}", 1, 2"#, "format", 0).is_err());
+	assert!(parse_format(r#""{y}""#, "format", 0).is_err());
+	assert!(parse_format(r#""}""#, "format", 0).is_err());
+	assert!(parse_format(r#"1"#, "format", 0).is_err());
+}
+
+#[test]
+fn macro_arguments() {
+	let arguments = |source| match syn::parse_str::<ast::Expression>(source).unwrap() {
+		ast::Expression::Atomic(ast::AtomicExpression::MacroCall(_, _, arguments, _)) => arguments,
+		result => panic!(
+			"Expression was expected to be a macro call, but is {:#?} instead.",
+			result
+		),
+	};
+
+	assert_eq!(arguments("vec![1, 2]").arguments.unwrap().arguments.len(), 2);
+	// the parsed expression does not depend on which macros exist, format strings are only parsed once the call is
+	// evaluated
+	assert_eq!(arguments(r#"format!("{}}", 1)"#).tokens.text(), r#""{}}", 1"#);
+	// tokens that are not expressions are kept for lazy macros
+	assert!(arguments("lazy!(1 +)").arguments.is_none());
+	assert!(arguments("vec!(1 +)").arguments.is_none());
+	assert!(syn::parse_str::<ast::Expression>("vec!(1 +").is_err());
+}
+
+#[test]
+fn pattern() {
+	let result = syn::parse_str::<ast::Pattern>("Some((1 | -2, 'a'..='z', [first, .., _])) | None").unwrap();
+	match result {
+		ast::Pattern::Or(patterns, _) => {
+			assert_eq!(patterns.len(), 2);
+			match &patterns[0] {
+				ast::Pattern::TupleStruct(path, elements, _) => {
+					assert_eq!(path, &["Some"]);
+					assert!(matches!(&elements[..], [ast::Pattern::Tuple(elements, _)] if elements.len() == 3));
+				}
+				pattern => panic!("Expected a tuple struct pattern, but got {:?}", pattern),
+			}
+			assert!(matches!(&patterns[1], ast::Pattern::Identifier(name, _) if name == "None"));
+		}
+		_ => panic!(
+			"Pattern was expected to be an or-pattern, but is {:#?} instead.",
+			result
+		),
+	}
+
+	assert!(syn::parse_str::<ast::MatchesArguments>("$, Some(x) if x > 1").is_err());
+}
+
+#[test]
+fn block() {
+	let result = syn::parse_str::<ast::Expression>("{ let mut x: (&str, Vec<Integer>,) = y; -x as char * 2 }").unwrap();
+	match result {
+		ast::Expression::Block(block) => {
+			match &block.statements[..] {
+				[ast::Statement::Let(_, true, pattern, Some(r#type), Some(_))] => {
+					assert!(matches!(&**pattern, ast::Pattern::Identifier(name, _) if name == "x"));
+					assert_eq!(r#type.name, "(str, [Integer])");
+					assert!(matches!(&r#type.shape, ast::TypeShape::Tuple(elements) if elements.len() == 2));
+				}
+				statements => panic!("Expected a single `let` statement, but got {:#?}", statements),
+			}
+			match block.expression.as_deref() {
+				Some(ast::Expression::Binary(lhs, _, ast::BinaryOperator::Mul(_))) => match &**lhs {
+					ast::Expression::Cast(expression, _, r#type) => {
+						assert!(matches!(
+							**expression,
+							ast::Expression::Prefix(_, ast::Prefix::Minus(_))
+						));
+						assert_eq!(r#type.name, "char");
+					}
+					lhs => panic!("Expected a cast, but got {:#?}", lhs),
+				},
+				expression => panic!("Expected a multiplication, but got {:#?}", expression),
+			}
+		}
+		_ => panic!("Expression was expected to be a block, but is {:#?} instead.", result),
+	}
+
+	assert!(syn::parse_str::<ast::Expression>("{ let x: [Integer; 3] = y; x }").is_err());
+	assert!(syn::parse_str::<ast::Expression>("{ let (x, y) = z; x }").is_err());
+	assert!(syn::parse_str::<ast::Expression>("{ let x; x }").is_err());
+}
+
+#[test]
+fn items() {
+	let result = syn::parse_str::<ast::Expression>(
+		"{ #[derive(Clone, Debug)] enum E { A, B(Integer), C { x: bool } } struct S(E); struct U; 1 }",
+	)
+	.unwrap();
+	match &result {
+		ast::Expression::Block(block) => match &block.statements[..] {
+			[ast::Statement::Item(ast::Item::Enum(e)), ast::Statement::Item(ast::Item::Struct(s)), ast::Statement::Item(ast::Item::Struct(u))] =>
+			{
+				assert_eq!(e.name, "E");
+				assert_eq!(
+					e.derives.iter().map(|(name, _span)| name.as_str()).collect::<Vec<_>>(),
+					["Clone", "Debug"]
+				);
+				assert!(matches!(
+					&e.body[..],
+					[
+						ast::Variant {
+							fields: ast::Fields::Unit,
+							..
+						},
+						ast::Variant {
+							fields: ast::Fields::Tuple(_),
+							..
+						},
+						ast::Variant {
+							fields: ast::Fields::Struct(_),
+							..
+						},
+					]
+				));
+				assert!(matches!(&s.body, ast::Fields::Tuple(types) if types.len() == 1 && types[0].name == "E"));
+				assert!(matches!(u.body, ast::Fields::Unit));
+			}
+			statements => panic!("Expected three items, but got {:#?}", statements),
+		},
+		_ => panic!("Expression was expected to be a block, but is {:#?} instead.", result),
+	}
+
+	assert!(syn::parse_str::<ast::Expression>("{ struct S }").is_err());
+	assert!(syn::parse_str::<ast::Expression>("{ struct S<T>(T); }").is_err());
+	assert!(syn::parse_str::<ast::Expression>("{ #[inline] struct S; }").is_err());
+}
+
+#[test]
+fn impl_items() {
+	let result = syn::parse_str::<ast::Expression>(
+		"{ impl Add for P { fn add(self, other: P) -> P { self } } impl P { pub fn new(x: Integer) -> P { P(x) } } }",
+	)
+	.unwrap();
+	match &result {
+		ast::Expression::Block(block) => match &block.statements[..] {
+			[ast::Statement::Item(ast::Item::Impl(add)), ast::Statement::Item(ast::Item::Impl(inherent))] => {
+				assert!(matches!(&add.r#trait, Some((name, _span)) if name == "Add"));
+				assert_eq!(add.self_type.name, "P");
+				match &add.functions[..] {
+					[function] => {
+						assert_eq!(function.name, "add");
+						assert!(function.receiver.is_some());
+						assert!(
+							matches!(&function.parameters[..], [(name, _span, r#type)] if name == "other" && r#type.name == "P")
+						);
+						assert!(matches!(&function.return_type, Some(r#type) if r#type.name == "P"));
+					}
+					functions => panic!("Expected a single function, but got {:#?}", functions),
+				}
+				assert!(inherent.r#trait.is_none());
+				assert!(matches!(&inherent.functions[..], [function] if function.receiver.is_none()));
+			}
+			statements => panic!("Expected two `impl` blocks, but got {:#?}", statements),
+		},
+		_ => panic!("Expression was expected to be a block, but is {:#?} instead.", result),
+	}
+
+	assert!(syn::parse_str::<ast::Expression>("{ impl<T> P { } }").is_err());
+	assert!(syn::parse_str::<ast::Expression>("{ impl P { fn f<T>(self) { } } }").is_err());
+	assert!(syn::parse_str::<ast::Expression>("{ impl P { const X: Integer = 1; } }").is_err());
+	assert!(syn::parse_str::<ast::Expression>("{ impl P { fn f(x: Integer, self) { } } }").is_err());
+}
+
+#[test]
+fn global_items() {
+	let result = syn::parse_str::<ast::Expression>("{ const A: Integer = 1 + 2; static B: &str = \"b\"; A }").unwrap();
+	match &result {
+		ast::Expression::Block(block) => match &block.statements[..] {
+			[ast::Statement::Item(ast::Item::Const(a)), ast::Statement::Item(ast::Item::Static(b))] => {
+				assert_eq!(a.name, "A");
+				assert_eq!(a.r#type.name, "Integer");
+				assert!(matches!(
+					*a.expression,
+					ast::Expression::Binary(_, _, ast::BinaryOperator::Add(_))
+				));
+				assert_eq!(b.name, "B");
+				assert_eq!(b.r#type.name, "str");
+			}
+			statements => panic!("Expected two items, but got {:#?}", statements),
+		},
+		_ => panic!("Expression was expected to be a block, but is {:#?} instead.", result),
+	}
+
+	assert!(syn::parse_str::<ast::Expression>("{ const A = 1; }").is_err());
+	assert!(syn::parse_str::<ast::Expression>("{ const A: Integer; }").is_err());
+	assert!(syn::parse_str::<ast::Expression>("{ static mut A: Integer = 1; }").is_err());
+}
+
+#[test]
+fn statements() {
+	let result = parse_statements
+		.parse_str("const A: Integer = 1; let x = A; x")
+		.unwrap();
+	match &result {
+		ast::Expression::Block(block) => {
+			assert!(matches!(
+				&block.statements[..],
+				[
+					ast::Statement::Item(ast::Item::Const(_)),
+					ast::Statement::Let(_, false, _, None, Some(_))
+				]
+			));
+			assert!(matches!(block.expression.as_deref(), Some(ast::Expression::Atomic(_))));
+		}
+		_ => panic!("Expression was expected to be a block, but is {:#?} instead.", result),
+	}
+
+	let result = parse_statements.parse_str("1 + 2").unwrap();
+	assert!(matches!(
+		result,
+		ast::Expression::Binary(_, _, ast::BinaryOperator::Add(_))
+	));
+	assert!(parse_statements.parse_str("").is_err());
+	assert!(parse_statements.parse_str("1 2").is_err());
+}
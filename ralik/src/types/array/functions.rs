@@ -1,11 +1,14 @@
+use std::cmp::Ordering;
+
 use crate::error::RuntimeError;
 use crate::{Context, TypeHandle, Value};
 
 use super::super::arguments::Arguments;
+use super::super::option::{new_none, new_some};
 
 pub(crate) fn clone(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
 	assert!(this_type.kind() == crate::types::TypeKind::Array);
-	let element_type = &this_type.type_parameters()[0];
+	let element_type = &this_type.type_parameter_ids()[0];
 	let element_type = TypeHandle::from_type_id(context.clone(), *element_type);
 
 	arguments.check_len(1)?;
@@ -16,7 +19,7 @@ pub(crate) fn clone(context: &Context, this_type: &TypeHandle, arguments: &[Valu
 	let values = this
 		.iter()
 		// FIXME: the member function signature seems like it does a lot of copying
-		.map(|element| function(context, &element_type, &[element.clone()]))
+		.map(|element| function.call(context, &element_type, &[element.clone()]))
 		.collect::<Result<Vec<Value>, RuntimeError>>()?;
 	Ok(Value::new_array(context, &element_type, values)?)
 }
@@ -32,3 +35,157 @@ pub(crate) fn len(context: &Context, this_type: &TypeHandle, arguments: &[Value]
 	let this = arguments.check_type(0, this_type)?.as_array().unwrap();
 	Ok(Value::new_integer(context, this.len())?)
 }
+
+/**
+Calls the function for each element and collects the results into a new array. The element type of the result is
+the type of the returned values, or the element type of the array if it is empty.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("['a', 'B'].map(char::is_uppercase)", &context).unwrap();
+assert_eq!(result, eval_str("[false, true]", &context).unwrap());
+```
+*/
+pub(crate) fn map(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	assert!(this_type.kind() == crate::types::TypeKind::Array);
+	let element_type = TypeHandle::from_type_id(context.clone(), this_type.type_parameter_ids()[0]);
+
+	arguments.check_len(2)?;
+	let this = arguments.check_type(0, this_type)?.as_array().unwrap();
+	let function = arguments.check_type(
+		1,
+		&context
+			.get_function_type()
+			.map_err(|err| RuntimeError::InvalidCoreType(err.into()))?,
+	)?;
+
+	let values = this
+		.iter()
+		.map(|element| function.call(context, std::slice::from_ref(element)))
+		.collect::<Result<Vec<Value>, RuntimeError>>()?;
+	let element_type = values
+		.first()
+		.map(|value| value.get_type().clone())
+		.unwrap_or(element_type);
+	Ok(Value::new_array(context, &element_type, values)?)
+}
+
+/**
+Returns the largest element, or `None` if the array is empty. If several elements are equally large, the last of them
+is returned, as in Rust. The element type must implement `Ord`.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("[3, 7, 5].max().unwrap()", &context).unwrap();
+assert_eq!(result.as_i32(), Some(7));
+assert_eq!(eval_str("[[1], [2]].max().unwrap()", &context).unwrap().to_string(), "[2]");
+assert!(eval_str("{ struct S; [S].max() }", &context).is_err());
+```
+*/
+pub(crate) fn max(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	assert!(this_type.kind() == crate::types::TypeKind::Array);
+	let element_type = TypeHandle::from_type_id(context.clone(), this_type.type_parameter_ids()[0]);
+
+	arguments.check_len(1)?;
+	let this = arguments.check_type(0, this_type)?.as_array().unwrap();
+	element_type.check_implements("Ord")?;
+
+	let mut max = match this.first() {
+		Some(first) => first,
+		None => return new_none(context, &element_type),
+	};
+	for element in &this[1..] {
+		if !element_type.call_predicate(context, crate::ops::LESS, &[element.clone(), max.clone()])? {
+			max = element;
+		}
+	}
+	new_some(context, max.clone())
+}
+
+/**
+Returns a sorted copy of the array. The sort is stable, and the element type must implement `Ord`.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("[\"b\", \"c\", \"a\"].sort()", &context).unwrap();
+assert_eq!(result, eval_str("[\"a\", \"b\", \"c\"]", &context).unwrap());
+```
+*/
+pub(crate) fn sort(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	assert!(this_type.kind() == crate::types::TypeKind::Array);
+	let element_type = TypeHandle::from_type_id(context.clone(), this_type.type_parameter_ids()[0]);
+
+	arguments.check_len(1)?;
+	let this = arguments.check_type(0, this_type)?.as_array().unwrap();
+	element_type.check_implements("Ord")?;
+
+	// `sort_by` cannot propagate errors, so the first one is kept and all later comparisons are skipped
+	let mut error = None;
+	let mut values = this.to_vec();
+	values.sort_by(|lhs, rhs| {
+		if error.is_some() {
+			return Ordering::Equal;
+		}
+		let less =
+			|lhs: &Value, rhs: &Value| element_type.call_predicate(context, crate::ops::LESS, &[lhs.clone(), rhs.clone()]);
+		match less(lhs, rhs).and_then(|less_than| Ok((less_than, less(rhs, lhs)?))) {
+			Ok((true, _)) => Ordering::Less,
+			Ok((false, true)) => Ordering::Greater,
+			Ok((false, false)) => Ordering::Equal,
+			Err(err) => {
+				error = Some(err);
+				Ordering::Equal
+			}
+		}
+	});
+	match error {
+		Some(err) => Err(err),
+		None => Ok(Value::new_array(context, &element_type, values)?),
+	}
+}
+
+pub(crate) fn debug(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	assert!(this_type.kind() == crate::types::TypeKind::Array);
+	let element_type = TypeHandle::from_type_id(context.clone(), this_type.type_parameter_ids()[0]);
+
+	arguments.check_len(2)?;
+	let this = arguments.check_type(0, this_type)?.as_array().unwrap();
+	let alternate = arguments.as_bool(1, context)?;
+
+	let entries = this
+		.iter()
+		.map(|element| element_type.call_debug(context, element, alternate))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	Ok(Value::new_string(
+		context,
+		crate::types::debug_list(&entries, alternate),
+	)?)
+}
+
+pub(crate) fn default(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	assert!(this_type.kind() == crate::types::TypeKind::Array);
+	let element_type = TypeHandle::from_type_id(context.clone(), this_type.type_parameter_ids()[0]);
+
+	arguments.check_len(0)?;
+	Ok(Value::new_array(context, &element_type, Vec::new())?)
+}
+
+/**
+Associated function that creates a new, empty array. As the element type cannot be inferred, it must be given
+explicitly.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("Vec::<char>::new()", &context).unwrap();
+assert_eq!(result.get_type().name().as_ref(), "[char]");
+assert_eq!(result.as_array().unwrap().len(), 0);
+```
+*/
+pub(crate) fn new(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	default(context, this_type, arguments)
+}
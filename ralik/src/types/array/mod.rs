@@ -1,6 +1,6 @@
 use anyhow::ensure;
 
-use super::{GenericTypeBuilder, MemberFunction, TypeKind};
+use super::{compare, member_functions, GenericTypeBuilder, TypeKind};
 use crate::context::Context;
 
 mod functions;
@@ -10,13 +10,35 @@ pub fn make_array_name(element_type: &str) -> String {
 	format!("[{}]", element_type)
 }
 
-pub fn array_generic(_context: &Context, element_type: &[&str]) -> Result<GenericTypeBuilder, anyhow::Error> {
+/**
+Creates array types, which compare lexicographically as long as their element type supports the respective
+operations, in which case the array type also implements `PartialEq`, `PartialOrd` and `Ord` like its element type.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("([1, 2] == [1, 2], [1, 2] != [1], [1, 2] < [1, 3], [1] < [1, 2], [2] >= [1, 2])", &context);
+assert_eq!(result.unwrap().to_string(), "(true, true, true, true, true)");
+
+let result = eval_str("{ #[derive(PartialEq)] struct P { a: Vec<Integer> } P { a: [1] } == P { a: [1] } }", &context);
+assert_eq!(result.unwrap().to_string(), "true");
+assert_eq!(eval_str("[[2], [1, 2]].sort()", &context).unwrap().to_string(), "[[1, 2], [2]]");
+```
+*/
+pub fn array_generic(context: &Context, element_type: &[&str]) -> Result<GenericTypeBuilder, anyhow::Error> {
 	ensure!(
 		element_type.len() == 1,
 		"Can only create arrays with exactly one element type ({} provided)",
 		element_type.len()
 	);
 	let element_type = element_type[0];
+	// an element type that is registered together with the array cannot be inspected yet and counts as implementing
+	// nothing
+	let element_type_implements = |interface: &str| {
+		context
+			.get_type(element_type)
+			.is_some_and(|element_type| element_type.implements(interface))
+	};
 
 	Ok(GenericTypeBuilder {
 		kind: TypeKind::Array,
@@ -25,14 +47,34 @@ pub fn array_generic(_context: &Context, element_type: &[&str]) -> Result<Generi
 		field_types: Default::default(),
 		variant_names: Default::default(),
 		variants: Default::default(),
-		functions: [
-			(crate::ops::INDEX, ops::index as MemberFunction),
+		functions: member_functions(&[
+			(crate::ops::INDEX, ops::index),
+			(crate::ops::EQUAL, compare::equal::<ops::ArrayElements>),
+			(crate::ops::NOT_EQUAL, compare::not_equal::<ops::ArrayElements>),
+			(crate::ops::LESS, compare::less::<ops::ArrayElements>),
+			(crate::ops::LESS_OR_EQUAL, compare::less_or_equal::<ops::ArrayElements>),
+			(crate::ops::GREATER, compare::greater::<ops::ArrayElements>),
+			(
+				crate::ops::GREATER_OR_EQUAL,
+				compare::greater_or_equal::<ops::ArrayElements>,
+			),
+			(crate::ops::DEBUG, functions::debug),
 			("clone", functions::clone),
 			("is_empty", functions::is_empty),
 			("len", functions::len),
-		]
-		.iter()
-		.map(|(name, function)| ((*name).to_owned(), *function))
-		.collect(),
+			("map", functions::map),
+			("max", functions::max),
+			("sort", functions::sort),
+		]),
+		associated_functions: member_functions(&[("default", functions::default), ("new", functions::new)]),
+		interfaces: ["Clone", "Debug", "Default"]
+			.iter()
+			.chain(
+				["Ord", "PartialEq", "PartialOrd"]
+					.iter()
+					.filter(|name| element_type_implements(name)),
+			)
+			.map(|name| (*name).to_owned())
+			.collect(),
 	})
 }
@@ -1,9 +1,12 @@
+use std::cmp::Ordering;
+
 use num::ToPrimitive;
 
 use crate::error::{Overflow, RuntimeError};
 use crate::{Context, TypeHandle, Value};
 
 use super::super::arguments::Arguments;
+use super::super::compare::{ElementPairs, Elements};
 
 pub(crate) fn index(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
 	arguments.check_len(2)?;
@@ -17,3 +20,26 @@ pub(crate) fn index(context: &Context, this_type: &TypeHandle, arguments: &[Valu
 		len: this.len(),
 	})?)
 }
+
+/// Arrays compare their elements in order, and a prefix of an array is less than the array.
+pub(super) struct ArrayElements;
+
+impl Elements for ArrayElements {
+	fn elements(
+		context: &Context,
+		this_type: &TypeHandle,
+		arguments: &[Value],
+	) -> Result<(ElementPairs, Ordering), RuntimeError> {
+		arguments.check_len(2)?;
+		let lhs = arguments.check_type(0, this_type)?.as_array().unwrap();
+		let rhs = arguments.check_type(1, this_type)?.as_array().unwrap();
+
+		let element_type = TypeHandle::from_type_id(context.clone(), this_type.type_parameter_ids()[0]);
+		let elements = lhs
+			.iter()
+			.zip(rhs.iter())
+			.map(|(lhs, rhs)| (element_type.clone(), [lhs.clone(), rhs.clone()]))
+			.collect();
+		Ok((elements, lhs.len().cmp(&rhs.len())))
+	}
+}
@@ -8,6 +8,18 @@ pub(crate) fn clone(_context: &Context, this_type: &TypeHandle, arguments: &[Val
 	arguments.check_type(0, this_type).map(|value| value.clone())
 }
 
+pub(crate) fn debug(context: &Context, _this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let this = arguments.as_bool(0, context)?;
+	let _alternate = arguments.as_bool(1, context)?;
+	Ok(Value::new_string(context, format!("{:?}", this))?)
+}
+
+pub(crate) fn default(context: &Context, _this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(0)?;
+	Ok(Value::new_bool(context, false)?)
+}
+
 pub(crate) fn to_string(
 	context: &Context,
 	_this_type: &TypeHandle,
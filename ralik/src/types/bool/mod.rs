@@ -1,4 +1,4 @@
-use super::{MemberFunction, TypeBuilder, TypeKind};
+use super::{member_functions, TypeBuilder, TypeKind};
 
 mod functions;
 mod ops;
@@ -16,8 +16,8 @@ pub fn new_bool_type() -> TypeBuilder {
 		field_types: Default::default(),
 		variant_names: Default::default(),
 		variants: Default::default(),
-		functions: [
-			(crate::ops::NOT, ops::not as MemberFunction),
+		functions: member_functions(&[
+			(crate::ops::NOT, ops::not),
 			(crate::ops::BIT_AND, ops::bit_and),
 			(crate::ops::BIT_OR, ops::bit_or),
 			(crate::ops::BIT_XOR, ops::bit_xor),
@@ -27,11 +27,11 @@ pub fn new_bool_type() -> TypeBuilder {
 			(crate::ops::LESS_OR_EQUAL, ops::less_or_equal),
 			(crate::ops::GREATER, ops::greater),
 			(crate::ops::GREATER_OR_EQUAL, ops::greater_or_equal),
+			(crate::ops::DEBUG, functions::debug),
 			("clone", functions::clone),
 			("to_string", functions::to_string),
-		]
-		.iter()
-		.map(|(name, function)| ((*name).to_owned(), *function))
-		.collect(),
+		]),
+		associated_functions: member_functions(&[("default", functions::default)]),
+		interfaces: super::scalar_interfaces(),
 	}
 }
@@ -1,15 +1,29 @@
+use anyhow::anyhow;
 use num::ToPrimitive;
 
 use crate::error::{Overflow, RuntimeError};
 use crate::{Context, TypeHandle, Value};
 
 use super::super::arguments::Arguments;
+use super::super::option::{new_none, new_some};
 
 pub(crate) fn clone(_context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
 	arguments.check_len(1)?;
 	arguments.check_type(0, this_type).map(|value| value.clone())
 }
 
+pub(crate) fn debug(context: &Context, _this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let this = arguments.as_char(0, context)?;
+	let _alternate = arguments.as_bool(1, context)?;
+	Ok(Value::new_string(context, format!("{:?}", this))?)
+}
+
+pub(crate) fn default(context: &Context, _this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(0)?;
+	Ok(Value::new_char(context, char::default())?)
+}
+
 pub(crate) fn eq_ignore_ascii_case(
 	context: &Context,
 	_this_type: &TypeHandle,
@@ -21,6 +35,62 @@ pub(crate) fn eq_ignore_ascii_case(
 	Ok(Value::new_bool(context, this.eq_ignore_ascii_case(&arg))?)
 }
 
+/**
+Associated function that converts a digit in the given radix to a `char`.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("char::from_digit(11, 16).unwrap()", &context).unwrap();
+assert_eq!(result.as_char(), Some('b'));
+let result = eval_str("char::from_digit(11, 10).is_none()", &context).unwrap();
+assert_eq!(result.as_bool(), Some(true));
+```
+*/
+pub(crate) fn from_digit(
+	context: &Context,
+	_this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let num = arguments.as_integer(0, context)?.to_u32();
+	let radix = arguments.as_integer(1, context)?.to_u32().ok_or(Overflow::U32)?;
+	if radix > 36 {
+		return Err(anyhow!("The radix {} is larger than 36", radix).into());
+	}
+	new_char_option(context, num.and_then(|num| std::char::from_digit(num, radix)))
+}
+
+/**
+Associated function that converts a code point to a `char`, if it is a valid unicode scalar value.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("char::from_u32(65).unwrap()", &context).unwrap();
+assert_eq!(result.as_char(), Some('A'));
+let result = eval_str("char::from_u32(0xd800).is_none()", &context).unwrap();
+assert_eq!(result.as_bool(), Some(true));
+```
+*/
+pub(crate) fn from_u32(context: &Context, _this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(1)?;
+	let value = arguments.as_integer(0, context)?.to_u32().and_then(std::char::from_u32);
+	new_char_option(context, value)
+}
+
+fn new_char_option(context: &Context, value: Option<char>) -> Result<Value, RuntimeError> {
+	match value {
+		Some(value) => new_some(context, Value::new_char(context, value)?),
+		None => new_none(
+			context,
+			&context
+				.get_char_type()
+				.map_err(|err| RuntimeError::InvalidCoreType(err.into()))?,
+		),
+	}
+}
+
 pub(crate) fn is_alphabetic(
 	context: &Context,
 	_this_type: &TypeHandle,
@@ -1,4 +1,4 @@
-use super::{MemberFunction, TypeBuilder, TypeKind};
+use super::{member_functions, TypeBuilder, TypeKind};
 
 mod functions;
 mod ops;
@@ -16,13 +16,14 @@ pub fn new_char_type() -> TypeBuilder {
 		field_types: Default::default(),
 		variant_names: Default::default(),
 		variants: Default::default(),
-		functions: [
-			(crate::ops::EQUAL, ops::equal as MemberFunction),
+		functions: member_functions(&[
+			(crate::ops::EQUAL, ops::equal),
 			(crate::ops::NOT_EQUAL, ops::not_equal),
 			(crate::ops::LESS, ops::less),
 			(crate::ops::LESS_OR_EQUAL, ops::less_or_equal),
 			(crate::ops::GREATER, ops::greater),
 			(crate::ops::GREATER_OR_EQUAL, ops::greater_or_equal),
+			(crate::ops::DEBUG, functions::debug),
 			("clone", functions::clone),
 			("eq_ignore_ascii_case", functions::eq_ignore_ascii_case),
 			("is_alphabetic", functions::is_alphabetic),
@@ -49,9 +50,12 @@ pub fn new_char_type() -> TypeBuilder {
 			("to_ascii_lowercase", functions::to_ascii_lowercase),
 			("to_ascii_uppercase", functions::to_ascii_uppercase),
 			("to_string", functions::to_string),
-		]
-		.iter()
-		.map(|(name, function)| ((*name).to_owned(), *function))
-		.collect(),
+		]),
+		associated_functions: member_functions(&[
+			("default", functions::default),
+			("from_digit", functions::from_digit),
+			("from_u32", functions::from_u32),
+		]),
+		interfaces: super::scalar_interfaces(),
 	}
 }
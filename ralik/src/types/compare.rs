@@ -0,0 +1,104 @@
+use std::cmp::Ordering;
+
+use crate::error::RuntimeError;
+use crate::{Context, TypeHandle, Value};
+
+/// Pairs of corresponding elements of two values, each with the type of the elements.
+pub(crate) type ElementPairs = Vec<(TypeHandle, [Value; 2])>;
+
+/**
+Helpers for implementing the comparison operators of types whose values are compared element by element, such as
+tuples, arrays and derived structs and enums. The elements are compared lexicographically with `[op]::Equal` and
+`[op]::Less` of their types, which means that these operators are only called on elements that need to be compared.
+*/
+pub(crate) trait Elements {
+	/// Checks the two arguments and returns their pairs of elements, each with its type, together with the ordering of
+	/// the arguments if all of these pairs are equal.
+	fn elements(
+		context: &Context,
+		this_type: &TypeHandle,
+		arguments: &[Value],
+	) -> Result<(ElementPairs, Ordering), RuntimeError>;
+}
+
+fn is_equal<T: Elements>(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<bool, RuntimeError> {
+	let (elements, ordering) = T::elements(context, this_type, arguments)?;
+	for (element_type, arguments) in elements {
+		if !element_type.call_predicate(context, crate::ops::EQUAL, &arguments)? {
+			return Ok(false);
+		}
+	}
+	Ok(ordering == Ordering::Equal)
+}
+
+fn compare<T: Elements>(
+	context: &Context,
+	this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Ordering, RuntimeError> {
+	let (elements, ordering) = T::elements(context, this_type, arguments)?;
+	for (element_type, arguments) in elements {
+		if !element_type.call_predicate(context, crate::ops::EQUAL, &arguments)? {
+			return if element_type.call_predicate(context, crate::ops::LESS, &arguments)? {
+				Ok(Ordering::Less)
+			} else {
+				Ok(Ordering::Greater)
+			};
+		}
+	}
+	Ok(ordering)
+}
+
+pub(crate) fn equal<T: Elements>(
+	context: &Context,
+	this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	let result = is_equal::<T>(context, this_type, arguments)?;
+	Ok(Value::new_bool(context, result)?)
+}
+
+pub(crate) fn not_equal<T: Elements>(
+	context: &Context,
+	this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	let result = is_equal::<T>(context, this_type, arguments)?;
+	Ok(Value::new_bool(context, !result)?)
+}
+
+pub(crate) fn less<T: Elements>(
+	context: &Context,
+	this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	let ordering = compare::<T>(context, this_type, arguments)?;
+	Ok(Value::new_bool(context, ordering == Ordering::Less)?)
+}
+
+pub(crate) fn less_or_equal<T: Elements>(
+	context: &Context,
+	this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	let ordering = compare::<T>(context, this_type, arguments)?;
+	Ok(Value::new_bool(context, ordering != Ordering::Greater)?)
+}
+
+pub(crate) fn greater<T: Elements>(
+	context: &Context,
+	this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	let ordering = compare::<T>(context, this_type, arguments)?;
+	Ok(Value::new_bool(context, ordering == Ordering::Greater)?)
+}
+
+pub(crate) fn greater_or_equal<T: Elements>(
+	context: &Context,
+	this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	let ordering = compare::<T>(context, this_type, arguments)?;
+	Ok(Value::new_bool(context, ordering != Ordering::Less)?)
+}
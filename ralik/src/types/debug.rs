@@ -0,0 +1,63 @@
+use std::fmt;
+
+/**
+Helpers for implementing `[op]::Debug`, which takes `self` and a `bool` that selects the alternate (`{:#?}`) form.
+
+The representation of nested values is passed in as already rendered strings, which are then laid out with the same
+rules that `std::fmt::Formatter::debug_tuple` and friends use, including the indentation of nested values in the
+alternate form.
+*/
+pub(crate) fn debug_tuple(name: &str, fields: &[String], alternate: bool) -> String {
+	if name.is_empty() && fields.is_empty() {
+		return "()".to_owned();
+	}
+	render(alternate, |f| {
+		let mut builder = f.debug_tuple(name);
+		for field in fields {
+			builder.field(&Rendered(field));
+		}
+		builder.finish()
+	})
+}
+
+pub(crate) fn debug_struct(name: &str, fields: &[(&str, String)], alternate: bool) -> String {
+	render(alternate, |f| {
+		let mut builder = f.debug_struct(name);
+		for (field_name, field) in fields {
+			builder.field(field_name, &Rendered(field));
+		}
+		builder.finish()
+	})
+}
+
+pub(crate) fn debug_list(entries: &[String], alternate: bool) -> String {
+	render(alternate, |f| {
+		f.debug_list()
+			.entries(entries.iter().map(|entry| Rendered(entry)))
+			.finish()
+	})
+}
+
+struct Rendered<'a>(&'a str);
+
+impl<'a> fmt::Debug for Rendered<'a> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.0)
+	}
+}
+
+fn render(alternate: bool, fmt: impl Fn(&mut fmt::Formatter) -> fmt::Result) -> String {
+	struct Render<F>(F);
+
+	impl<F: Fn(&mut fmt::Formatter) -> fmt::Result> fmt::Debug for Render<F> {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			(self.0)(f)
+		}
+	}
+
+	if alternate {
+		format!("{:#?}", Render(fmt))
+	} else {
+		format!("{:?}", Render(fmt))
+	}
+}
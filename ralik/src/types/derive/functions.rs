@@ -0,0 +1,141 @@
+use crate::error::RuntimeError;
+use crate::types::{TypeKind, Variant};
+use crate::{Context, TypeHandle, Value};
+
+use super::super::arguments::Arguments;
+use super::{check_kind, construct, shape_of_type, shape_of_value, shape_of_variant, Fields};
+
+pub(crate) fn clone(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	check_kind(this_type)?;
+	arguments.check_len(1)?;
+	let this = arguments.check_type(0, this_type)?;
+	let shape = shape_of_value(this_type, this);
+
+	let values = this
+		.as_array()
+		.unwrap()
+		.iter()
+		.zip(shape.fields.types())
+		.map(|(field, field_type)| {
+			TypeHandle::from_type_id(context.clone(), field_type).call_function(context, "clone", std::slice::from_ref(field))
+		})
+		.collect::<Result<Vec<Value>, RuntimeError>>()?;
+	construct(context, this_type, &shape, values)
+}
+
+pub(crate) fn debug(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	check_kind(this_type)?;
+	arguments.check_len(2)?;
+	let this = arguments.check_type(0, this_type)?;
+	let alternate = arguments.as_bool(1, context)?;
+	let shape = shape_of_value(this_type, this);
+
+	let fields = this
+		.as_array()
+		.unwrap()
+		.iter()
+		.zip(shape.fields.types())
+		.map(|(field, field_type)| {
+			TypeHandle::from_type_id(context.clone(), field_type).call_debug(context, field, alternate)
+		})
+		.collect::<Result<Vec<String>, RuntimeError>>()?;
+
+	let name = match &shape.variant {
+		Some((_id, variant_name)) => variant_name.to_string(),
+		None => this_type.name().to_string(),
+	};
+	let result = match &shape.fields {
+		Fields::Unit => name,
+		Fields::Tuple(_) => crate::types::debug_tuple(&name, &fields, alternate),
+		Fields::Struct(names) => {
+			let fields = names
+				.iter()
+				.zip(fields)
+				.map(|((field_name, _type_id), field)| (&**field_name, field))
+				.collect::<Vec<_>>();
+			crate::types::debug_struct(&name, &fields, alternate)
+		}
+	};
+
+	Ok(Value::new_string(context, result)?)
+}
+
+pub(crate) fn to_string(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	check_kind(this_type)?;
+	arguments.check_len(1)?;
+	let this = arguments.check_type(0, this_type)?;
+	let shape = shape_of_value(this_type, this);
+
+	let fields = this
+		.as_array()
+		.unwrap()
+		.iter()
+		.zip(shape.fields.types())
+		.map(|(field, field_type)| {
+			TypeHandle::from_type_id(context.clone(), field_type).call_string_function(
+				context,
+				"to_string",
+				std::slice::from_ref(field),
+			)
+		})
+		.collect::<Result<Vec<String>, RuntimeError>>()?;
+
+	let mut result = match &shape.variant {
+		Some((_id, variant_name)) => variant_name.to_string(),
+		None => this_type.name().to_string(),
+	};
+	match &shape.fields {
+		Fields::Unit => (),
+		Fields::Tuple(_) => {
+			result.push('(');
+			result.push_str(&fields.join(", "));
+			result.push(')');
+		}
+		Fields::Struct(names) => {
+			if !names.is_empty() {
+				result.push_str(" { ");
+				for (i, ((name, _type_id), field)) in names.iter().zip(fields.iter()).enumerate() {
+					if i > 0 {
+						result.push_str(", ");
+					}
+					result.push_str(name);
+					result.push_str(": ");
+					result.push_str(field);
+				}
+				result.push_str(" }");
+			}
+		}
+	}
+
+	Ok(Value::new_string(context, result)?)
+}
+
+pub(crate) fn default(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	check_kind(this_type)?;
+	arguments.check_len(0)?;
+
+	let shape = match this_type.kind() {
+		TypeKind::Enum => match this_type.variant_table().1.first() {
+			Some(variant @ Variant::Unit(_)) => shape_of_variant(0, variant),
+			_ => {
+				return Err(
+					anyhow::anyhow!(
+						"Cannot derive a default value for enum `{}`, as its first variant is not a unit variant",
+						this_type.name()
+					)
+					.into(),
+				)
+			}
+		},
+		_ => shape_of_type(this_type).unwrap(),
+	};
+
+	let values = shape
+		.fields
+		.types()
+		.map(|field_type| {
+			TypeHandle::from_type_id(context.clone(), field_type).call_associated_function(context, "default", &[])
+		})
+		.collect::<Result<Vec<Value>, RuntimeError>>()?;
+	construct(context, this_type, &shape, values)
+}
@@ -0,0 +1,225 @@
+use std::sync::Arc;
+
+use crate::context::TypeId;
+use crate::error::RuntimeError;
+use crate::{Context, TypeHandle, Value};
+
+use super::{compare, MemberFunction, TypeKind, Variant};
+
+mod functions;
+mod ops;
+
+/**
+Behaviour that can be generated for host-registered structs, tuple structs, unit structs and enums, analogous to
+`#[derive(...)]` in Rust.
+
+The generated member functions work structurally, i.e., they delegate to the respective member functions of each field
+type. Deriving `PartialEq` for a struct with a field of type `Integer` therefore compares that field with
+`Integer`'s own `==` operator.
+
+```rust
+# use ralik::{eval_str, Context, Value};
+# use ralik::types::{new_tuple_struct_type, Derive};
+let context = Context::new();
+let meters = new_tuple_struct_type("Meters", vec!["Integer".to_owned()]);
+context.register_types(vec![meters.derive(vec![
+	Derive::PartialEq,
+	Derive::PartialOrd,
+	Derive::Clone,
+	Derive::Debug,
+	Derive::Display,
+	Derive::Default,
+])]);
+let value = Value::new_tuple_struct(&context, "Meters", vec![Value::new_integer(&context, 42).unwrap()]).unwrap();
+context.insert_variable("$", value);
+
+assert_eq!(eval_str("$ == $.clone()", &context).unwrap(), eval_str("true", &context).unwrap());
+assert_eq!(eval_str("$ != $", &context).unwrap(), eval_str("false", &context).unwrap());
+assert_eq!(eval_str("Meters(41) < $", &context).unwrap(), eval_str("true", &context).unwrap());
+assert_eq!(eval_str("$ >= Meters(43)", &context).unwrap(), eval_str("false", &context).unwrap());
+assert_eq!(eval_str("Meters::default()", &context).unwrap(), eval_str("Meters(0)", &context).unwrap());
+assert_eq!(eval_str("format!(\"{:?}\", $)", &context).unwrap().as_string(), Some("Meters(42)"));
+assert_eq!(eval_str("$.to_string()", &context).unwrap().as_string(), Some("Meters(42)"));
+```
+*/
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Derive {
+	/// Generates `==` and `!=`, comparing field by field.
+	PartialEq,
+	/// Generates `<`, `<=`, `>` and `>=`, comparing lexicographically in field order. Enum values are ordered by their
+	/// variant first, in the order in which the variants were declared. Requires `PartialEq` on all fields.
+	PartialOrd,
+	/// Generates `clone`, cloning field by field.
+	Clone,
+	/// Generates the debug representation used by `{:?}`, using the debug representation of all fields.
+	Debug,
+	/// Generates `to_string`, using the `to_string` member function of all fields.
+	Display,
+	/// Generates `default`, which takes no arguments and creates a value by calling `default` for all fields. Enums
+	/// default to their first variant, which must be a unit variant.
+	Default,
+}
+
+impl Derive {
+	/// The name of the interface that the derived member functions implement.
+	pub fn interface(self) -> &'static str {
+		match self {
+			Derive::PartialEq => "PartialEq",
+			Derive::PartialOrd => "PartialOrd",
+			Derive::Clone => "Clone",
+			Derive::Debug => "Debug",
+			Derive::Display => "Display",
+			Derive::Default => "Default",
+		}
+	}
+
+	pub(super) fn functions(self) -> &'static [(&'static str, MemberFunction)] {
+		match self {
+			Derive::PartialEq => &[
+				(crate::ops::EQUAL, compare::equal::<ops::FieldElements>),
+				(crate::ops::NOT_EQUAL, compare::not_equal::<ops::FieldElements>),
+			],
+			Derive::PartialOrd => &[
+				(crate::ops::LESS, compare::less::<ops::FieldElements>),
+				(crate::ops::LESS_OR_EQUAL, compare::less_or_equal::<ops::FieldElements>),
+				(crate::ops::GREATER, compare::greater::<ops::FieldElements>),
+				(
+					crate::ops::GREATER_OR_EQUAL,
+					compare::greater_or_equal::<ops::FieldElements>,
+				),
+			],
+			Derive::Clone => &[("clone", functions::clone)],
+			Derive::Debug => &[(crate::ops::DEBUG, functions::debug)],
+			Derive::Display => &[("to_string", functions::to_string)],
+			Derive::Default => &[],
+		}
+	}
+
+	pub(super) fn associated_functions(self) -> &'static [(&'static str, MemberFunction)] {
+		match self {
+			Derive::Default => &[("default", functions::default)],
+			_ => &[],
+		}
+	}
+}
+
+/// The structure of one concrete value, as seen by derived member functions.
+struct Shape {
+	variant: Option<(usize, Box<str>)>,
+	fields: Fields,
+}
+
+enum Fields {
+	Unit,
+	Tuple(Arc<[TypeId]>),
+	Struct(Vec<(Box<str>, TypeId)>),
+}
+
+impl Fields {
+	fn types(&self) -> Box<dyn Iterator<Item = TypeId> + '_> {
+		match self {
+			Fields::Unit => Box::new(std::iter::empty()),
+			Fields::Tuple(types) => Box::new(types.iter().copied()),
+			Fields::Struct(fields) => Box::new(fields.iter().map(|(_name, type_id)| *type_id)),
+		}
+	}
+}
+
+fn shape_of_variant(variant_id: usize, variant: &Variant) -> Shape {
+	match variant {
+		Variant::Unit(name) => Shape {
+			variant: Some((variant_id, name.clone())),
+			fields: Fields::Unit,
+		},
+		Variant::Tuple(name, field_types) => Shape {
+			variant: Some((variant_id, name.clone())),
+			fields: Fields::Tuple(field_types.iter().copied().collect()),
+		},
+		Variant::Struct(name, field_names, field_types) => Shape {
+			variant: Some((variant_id, name.clone())),
+			fields: Fields::Struct(sorted_fields(field_names.iter(), field_types)),
+		},
+	}
+}
+
+fn sorted_fields<'a>(
+	field_names: impl Iterator<Item = (&'a Box<str>, &'a usize)>,
+	field_types: &[TypeId],
+) -> Vec<(Box<str>, TypeId)> {
+	let mut fields = field_names
+		.map(|(name, index)| (*index, name.clone()))
+		.collect::<Vec<_>>();
+	fields.sort_unstable_by_key(|(index, _name)| *index);
+	fields
+		.into_iter()
+		.map(|(index, name)| (name, field_types[index]))
+		.collect()
+}
+
+/// Determines the shape of a type, which is only possible for types that are not enums.
+fn shape_of_type(this_type: &TypeHandle) -> Option<Shape> {
+	match this_type.kind() {
+		TypeKind::UnitStruct => Some(Shape {
+			variant: None,
+			fields: Fields::Unit,
+		}),
+		TypeKind::TupleStruct => Some(Shape {
+			variant: None,
+			fields: Fields::Tuple(this_type.fields().1),
+		}),
+		TypeKind::Struct => {
+			let (field_names, field_types) = this_type.fields();
+			Some(Shape {
+				variant: None,
+				fields: Fields::Struct(sorted_fields(field_names.iter(), &field_types)),
+			})
+		}
+		_ => None,
+	}
+}
+
+fn shape_of_value(this_type: &TypeHandle, value: &Value) -> Shape {
+	match this_type.kind() {
+		TypeKind::Enum => {
+			let variant_id = value.as_variant_id().unwrap();
+			shape_of_variant(variant_id, &this_type.variant_table().1[variant_id])
+		}
+		_ => shape_of_type(this_type).unwrap(),
+	}
+}
+
+/// Creates a new value of `this_type` with the given shape and field values.
+fn construct(
+	context: &Context,
+	this_type: &TypeHandle,
+	shape: &Shape,
+	values: Vec<Value>,
+) -> Result<Value, RuntimeError> {
+	let name = this_type.name();
+	match (&shape.variant, &shape.fields) {
+		(None, Fields::Unit) => Ok(Value::new_unit_struct(context, name)?),
+		(None, Fields::Tuple(_)) => Ok(Value::new_tuple_struct(context, name, values)?),
+		(None, Fields::Struct(fields)) => Ok(Value::new_struct(
+			context,
+			name,
+			fields.iter().map(|(name, _type_id)| name).zip(values),
+		)?),
+		(Some((_id, variant_name)), Fields::Unit) => Ok(Value::new_enum_unit_variant(context, name, variant_name)?),
+		(Some((_id, variant_name)), Fields::Tuple(_)) => {
+			Ok(Value::new_enum_tuple_variant(context, name, variant_name, values)?)
+		}
+		(Some((_id, variant_name)), Fields::Struct(fields)) => Ok(Value::new_enum_struct_variant(
+			context,
+			name,
+			variant_name,
+			fields.iter().map(|(name, _type_id)| name).zip(values),
+		)?),
+	}
+}
+
+fn check_kind(this_type: &TypeHandle) -> Result<(), RuntimeError> {
+	match this_type.kind() {
+		TypeKind::UnitStruct | TypeKind::TupleStruct | TypeKind::Struct | TypeKind::Enum => Ok(()),
+		kind => Err(anyhow::anyhow!("Cannot use derived member functions with types of kind {:?}", kind).into()),
+	}
+}
@@ -0,0 +1,47 @@
+use std::cmp::Ordering;
+
+use crate::error::RuntimeError;
+use crate::{Context, TypeHandle, Value};
+
+use super::super::arguments::Arguments;
+use super::super::compare::{ElementPairs, Elements};
+use super::{check_kind, shape_of_value};
+
+/// Derived structs and enums compare their fields in order, while values of different variants are ordered by the
+/// order in which their variants are declared.
+pub(super) struct FieldElements;
+
+impl Elements for FieldElements {
+	fn elements(
+		context: &Context,
+		this_type: &TypeHandle,
+		arguments: &[Value],
+	) -> Result<(ElementPairs, Ordering), RuntimeError> {
+		check_kind(this_type)?;
+		arguments.check_len(2)?;
+		let lhs = arguments.check_type(0, this_type)?;
+		let rhs = arguments.check_type(1, this_type)?;
+
+		let lhs_variant = lhs.as_variant_id();
+		let rhs_variant = rhs.as_variant_id();
+		if lhs_variant != rhs_variant {
+			return Ok((Vec::new(), lhs_variant.cmp(&rhs_variant)));
+		}
+
+		let shape = shape_of_value(this_type, lhs);
+		let elements = lhs
+			.as_array()
+			.unwrap()
+			.iter()
+			.zip(rhs.as_array().unwrap().iter())
+			.zip(shape.fields.types())
+			.map(|((lhs, rhs), field_type)| {
+				(
+					TypeHandle::from_type_id(context.clone(), field_type),
+					[lhs.clone(), rhs.clone()],
+				)
+			})
+			.collect();
+		Ok((elements, Ordering::Equal))
+	}
+}
@@ -4,14 +4,26 @@ use super::{TypeBuilder, TypeKind, VariantBuilder};
 //mod ops;
 
 pub fn new_enum_type(name: impl Into<String>, variants: impl Into<Vec<VariantBuilder>>) -> TypeBuilder {
+	let variants = variants.into();
+	let variant_names = variants
+		.iter()
+		.enumerate()
+		.map(|(i, variant)| match variant {
+			VariantBuilder::Unit(name) | VariantBuilder::Tuple(name, _) | VariantBuilder::Struct(name, _, _) => {
+				(name.clone(), i)
+			}
+		})
+		.collect();
 	TypeBuilder {
 		name: name.into(),
 		kind: TypeKind::Enum,
 		type_parameters: Default::default(),
 		field_names: Default::default(),
 		field_types: Default::default(),
-		variant_names: Default::default(),
-		variants: variants.into(),
+		variant_names,
+		variants,
 		functions: Default::default(),
+		associated_functions: Default::default(),
+		interfaces: Default::default(),
 	}
 }
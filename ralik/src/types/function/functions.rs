@@ -0,0 +1,27 @@
+use crate::error::RuntimeError;
+use crate::{Context, TypeHandle, Value};
+
+use super::super::arguments::Arguments;
+use super::Callable;
+
+pub(crate) fn clone(_context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(1)?;
+	arguments.check_type(0, this_type).cloned()
+}
+
+/**
+Functions are formatted as the path by which they were named.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("format!(\"{:?}\", [String::len])", &context).unwrap();
+assert_eq!(result.as_string(), Some("[std::string::String::len]"));
+```
+*/
+pub(crate) fn debug(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let this = arguments.check_type(0, this_type)?.downcast_ref::<Callable>().unwrap();
+	let _alternate = arguments.as_bool(1, context)?;
+	Ok(Value::new_string(context, this.name())?)
+}
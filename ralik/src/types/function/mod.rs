@@ -0,0 +1,55 @@
+use crate::error::RuntimeError;
+use crate::{Context, NativeFunction, TypeHandle, Value};
+
+use super::{member_functions, NativeMemberFunction, TypeBuilder, TypeKind};
+
+mod functions;
+
+pub const fn make_function_name() -> &'static str {
+	"fn"
+}
+
+/**
+Creates the type of functions that are used as values, e.g., `String::len` in `["a", "bc"].map(String::len)`. Its
+values are opaque, so that scripts can only pass them around and call them.
+*/
+pub fn new_function_type() -> TypeBuilder {
+	TypeBuilder {
+		name: make_function_name().into(),
+		kind: TypeKind::Opaque(std::any::TypeId::of::<Callable>()),
+		type_parameters: Default::default(),
+		field_names: Default::default(),
+		field_types: Default::default(),
+		variant_names: Default::default(),
+		variants: Default::default(),
+		functions: member_functions(&[(crate::ops::DEBUG, functions::debug), ("clone", functions::clone)]),
+		associated_functions: Default::default(),
+		interfaces: vec!["Clone".into(), "Debug".into()],
+	}
+}
+
+/// The data of a value of the function type.
+pub(crate) enum Callable {
+	/// A free function, e.g., `to_upper`.
+	Function(String, NativeFunction),
+	/// An associated function or a member function of a type, e.g., `String::new` or `String::len`. Member functions
+	/// take their receiver as the first argument.
+	TypeFunction(String, TypeHandle, NativeMemberFunction),
+}
+
+impl Callable {
+	/// The path by which the function was named, e.g., `std::string::String::len`.
+	pub(crate) fn name(&self) -> String {
+		match self {
+			Callable::Function(name, _function) => name.clone(),
+			Callable::TypeFunction(name, r#type, _function) => format!("{}::{}", r#type.name(), name),
+		}
+	}
+
+	pub(crate) fn call(&self, context: &Context, arguments: &[Value]) -> Result<Value, RuntimeError> {
+		match self {
+			Callable::Function(_name, function) => function.call(context, arguments),
+			Callable::TypeFunction(_name, r#type, function) => function.call(context, r#type, arguments),
+		}
+	}
+}
@@ -1,4 +1,4 @@
-use super::{MemberFunction, TypeBuilder, TypeKind};
+use super::{member_functions, TypeBuilder, TypeKind};
 
 mod functions;
 mod ops;
@@ -16,8 +16,8 @@ pub fn new_integer_type() -> TypeBuilder {
 		field_types: Default::default(),
 		variant_names: Default::default(),
 		variants: Default::default(),
-		functions: [
-			(crate::ops::NOT, ops::not as MemberFunction),
+		functions: member_functions(&[
+			(crate::ops::NOT, ops::not),
 			(crate::ops::NEGATE, ops::negate),
 			(crate::ops::NOT, ops::not),
 			(crate::ops::MUL, ops::multiply),
@@ -36,6 +36,7 @@ pub fn new_integer_type() -> TypeBuilder {
 			(crate::ops::LESS_OR_EQUAL, ops::less_or_equal),
 			(crate::ops::GREATER, ops::greater),
 			(crate::ops::GREATER_OR_EQUAL, ops::greater_or_equal),
+			(crate::ops::DEBUG, functions::debug),
 			("abs", functions::abs),
 			//("checked_div", functions::checked_div),
 			("clone", functions::clone),
@@ -44,9 +45,8 @@ pub fn new_integer_type() -> TypeBuilder {
 			("pow", functions::pow),
 			("signum", functions::signum),
 			("to_string", functions::to_string),
-		]
-		.iter()
-		.map(|(name, function)| ((*name).to_owned(), *function))
-		.collect(),
+		]),
+		associated_functions: member_functions(&[("default", functions::default)]),
+		interfaces: super::scalar_interfaces(),
 	}
 }
@@ -0,0 +1,116 @@
+use std::collections::hash_map::HashMap;
+
+use super::{MemberFunction, NativeMemberFunction};
+
+mod ops;
+
+/**
+A named set of member functions, analogous to a trait in Rust. Types declare which interfaces they implement in
+`TypeBuilder::interfaces`, which is checked when they are registered: each required function must be provided by the
+type, while default functions are added to it unless it provides its own.
+
+```rust
+# use ralik::types::{new_unit_struct_type, Interface, MemberFunction};
+# use ralik::{eval_str, Context, Value};
+let context = Context::new();
+let greet: MemberFunction = |context, _this_type, _arguments| Ok(Value::new_string(context, "Hello")?);
+let loud: MemberFunction = |context, this_type, arguments| {
+	let greeting = this_type.get_function("greet").unwrap().call(context, this_type, arguments)?;
+	Ok(Value::new_string(context, format!("{}!", greeting.as_string().unwrap()))?)
+};
+let mut greeter = Interface::new("Greeter", vec!["greet"]);
+greeter.default_functions.insert("greet_loudly".into(), loud.into());
+context.register_interface(greeter.clone()).unwrap();
+assert!(context.register_interface(greeter).is_err());
+
+let mut config_type = new_unit_struct_type("Config");
+config_type.functions.insert("greet".into(), greet.into());
+config_type.interfaces.push("Greeter".into());
+context.register_types(vec![config_type]);
+
+let value = eval_str("Config.greet_loudly()", &context).unwrap();
+assert_eq!(value.as_string(), Some("Hello!"));
+assert!(context.get_type("Config").unwrap().implements("Greeter"));
+```
+*/
+#[derive(Clone, Debug)]
+pub struct Interface {
+	pub name: String,
+	/// The member functions that each implementing type must provide.
+	pub required_functions: Vec<String>,
+	/// The associated functions that each implementing type must provide, e.g., `default`.
+	pub required_associated_functions: Vec<String>,
+	/// The member functions that are added to each implementing type that does not provide them itself.
+	pub default_functions: HashMap<String, NativeMemberFunction>,
+}
+
+impl Interface {
+	pub fn new(name: impl Into<String>, required_functions: impl IntoIterator<Item = impl Into<String>>) -> Self {
+		Self {
+			name: name.into(),
+			required_functions: required_functions.into_iter().map(Into::into).collect(),
+			required_associated_functions: Vec::new(),
+			default_functions: Default::default(),
+		}
+	}
+
+	fn with_required_associated_functions(mut self, required_associated_functions: &[&str]) -> Self {
+		self
+			.required_associated_functions
+			.extend(required_associated_functions.iter().map(|name| (*name).to_owned()));
+		self
+	}
+
+	fn with_default_functions(mut self, default_functions: &[(&str, MemberFunction)]) -> Self {
+		self
+			.default_functions
+			.extend(super::member_functions(default_functions));
+		self
+	}
+}
+
+/**
+The interfaces that every context provides, which correspond to the traits of the same name in Rust:
+
+- `Clone` requires `clone`.
+- `Debug` requires the debug representation used by `{:?}`.
+- `Display` requires `to_string`.
+- `Default` requires the associated function `default`.
+- `PartialEq` requires `==` and provides `!=`.
+- `PartialOrd` requires `<` and `<=`, and provides `>` and `>=`.
+- `Ord` requires `==` and `<` of a total order, and provides `!=`, `<=`, `>` and `>=`.
+
+```rust
+# use ralik::{eval_str, Context};
+let context = Context::new();
+let integer = context.get_integer_type().unwrap();
+assert!(integer.implements("Ord"));
+assert_eq!(
+	integer.interfaces().collect::<Vec<_>>(),
+	vec!["Clone", "Debug", "Default", "Display", "Ord", "PartialEq", "PartialOrd"],
+);
+
+assert_eq!(eval_str("Integer::default()", &context).unwrap().as_integer().unwrap().to_string(), "0");
+assert!(eval_str("5.default()", &context).is_err());
+```
+*/
+pub fn new_core_interfaces() -> Vec<Interface> {
+	vec![
+		Interface::new("Clone", vec!["clone"]),
+		Interface::new("Debug", vec![crate::ops::DEBUG]),
+		Interface::new("Display", vec!["to_string"]),
+		Interface::new("Default", Vec::<String>::new()).with_required_associated_functions(&["default"]),
+		Interface::new("PartialEq", vec![crate::ops::EQUAL])
+			.with_default_functions(&[(crate::ops::NOT_EQUAL, ops::not_equal)]),
+		Interface::new("PartialOrd", vec![crate::ops::LESS, crate::ops::LESS_OR_EQUAL]).with_default_functions(&[
+			(crate::ops::GREATER, ops::greater),
+			(crate::ops::GREATER_OR_EQUAL, ops::greater_or_equal),
+		]),
+		Interface::new("Ord", vec![crate::ops::EQUAL, crate::ops::LESS]).with_default_functions(&[
+			(crate::ops::NOT_EQUAL, ops::not_equal),
+			(crate::ops::LESS_OR_EQUAL, ops::total_less_or_equal),
+			(crate::ops::GREATER, ops::greater),
+			(crate::ops::GREATER_OR_EQUAL, ops::total_greater_or_equal),
+		]),
+	]
+}
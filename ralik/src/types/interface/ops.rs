@@ -0,0 +1,54 @@
+use crate::error::RuntimeError;
+use crate::{Context, TypeHandle, Value};
+
+use super::super::arguments::Arguments;
+
+pub(crate) fn not_equal(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let equal = this_type.call_predicate(
+		context,
+		crate::ops::EQUAL,
+		&[arguments[0].clone(), arguments[1].clone()],
+	)?;
+	Ok(Value::new_bool(context, !equal)?)
+}
+
+pub(crate) fn greater(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let less = this_type.call_predicate(context, crate::ops::LESS, &[arguments[1].clone(), arguments[0].clone()])?;
+	Ok(Value::new_bool(context, less)?)
+}
+
+pub(crate) fn greater_or_equal(
+	context: &Context,
+	this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let less_or_equal = this_type.call_predicate(
+		context,
+		crate::ops::LESS_OR_EQUAL,
+		&[arguments[1].clone(), arguments[0].clone()],
+	)?;
+	Ok(Value::new_bool(context, less_or_equal)?)
+}
+
+pub(crate) fn total_less_or_equal(
+	context: &Context,
+	this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let greater = this_type.call_predicate(context, crate::ops::LESS, &[arguments[1].clone(), arguments[0].clone()])?;
+	Ok(Value::new_bool(context, !greater)?)
+}
+
+pub(crate) fn total_greater_or_equal(
+	context: &Context,
+	this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let less = this_type.call_predicate(context, crate::ops::LESS, &[arguments[0].clone(), arguments[1].clone()])?;
+	Ok(Value::new_bool(context, !less)?)
+}
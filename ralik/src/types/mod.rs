@@ -3,9 +3,10 @@ use std::sync::Arc;
 
 use crate::context::{TypeHandle, TypeId};
 use crate::error::RuntimeError;
-use crate::{Context, Value};
+use crate::{Context, TypedFunction, Value};
 
 mod arguments;
+pub(crate) use self::arguments::Arguments;
 
 mod array;
 pub use self::array::array_generic;
@@ -17,15 +18,44 @@ pub use self::bool::{make_bool_name, new_bool_type};
 mod char;
 pub use self::char::{make_char_name, new_char_type};
 
+mod compare;
+
+mod debug;
+pub(crate) use self::debug::{debug_list, debug_struct, debug_tuple};
+
+mod derive;
+pub use self::derive::Derive;
+
+mod function;
+pub(crate) use self::function::Callable;
+pub use self::function::{make_function_name, new_function_type};
+
+mod interface;
+pub use self::interface::{new_core_interfaces, Interface};
+
 mod r#enum;
 pub use self::r#enum::new_enum_type;
 
 mod integer;
 pub use self::integer::{make_integer_name, new_integer_type};
 
-//mod option;
-//pub(crate) use self::option::make_option_name;
-//pub(crate) use self::option::option_generic;
+mod opaque;
+pub use self::opaque::new_opaque_type;
+
+mod option;
+pub use self::option::option_generic;
+pub(crate) use self::option::{is_option_type, make_option_name, new_none, new_some};
+
+mod ralik_type;
+pub use self::ralik_type::{RalikMethods, RalikType};
+
+mod range;
+pub use self::range::new_range_types;
+pub(crate) use self::range::{as_bounds, new_range};
+
+mod result;
+pub(crate) use self::result::make_result_name;
+pub use self::result::result_generic;
 
 mod string;
 pub use self::string::{make_string_name, new_string_type};
@@ -45,6 +75,79 @@ pub use self::unit_struct::new_unit_struct_type;
 
 pub type MemberFunction = fn(&Context, &TypeHandle, &[Value]) -> Result<Value, RuntimeError>;
 
+/// A member function that is implemented by a closure, which may capture state of the host program.
+pub type MemberClosure = Arc<dyn Fn(&Context, &TypeHandle, &[Value]) -> Result<Value, RuntimeError> + Send + Sync>;
+
+/**
+A member function that is implemented by the host program, either as a plain `fn` or as a closure.
+
+```rust
+# use ralik::types::{new_unit_struct_type, MemberClosure};
+# use ralik::{eval_str, Context, Value};
+# use std::sync::Arc;
+let context = Context::new();
+let mut config_type = new_unit_struct_type("Config");
+let greeting = String::from("Hello");
+let greet: MemberClosure = Arc::new(move |context, _this_type, arguments| {
+	let name = arguments[1].as_string().unwrap();
+	Ok(Value::new_string(context, format!("{}, {}!", greeting, name))?)
+});
+config_type.functions.insert("greet".into(), greet.into());
+context.register_types(vec![config_type]);
+
+context.insert_variable("$", Value::new_unit_struct(&context, "Config").unwrap());
+let value = eval_str(r#"$.greet("World")"#, &context).unwrap();
+assert_eq!(value.as_string(), Some("Hello, World!"));
+```
+*/
+#[derive(Clone)]
+pub enum NativeMemberFunction {
+	Function(MemberFunction),
+	Closure(MemberClosure),
+}
+
+impl NativeMemberFunction {
+	/// Creates a member function from a Rust function or closure that takes `self` as its first argument. Arguments and
+	/// result are converted as for `Context::register_fn`.
+	pub fn from_typed<Arguments>(function: impl TypedFunction<Arguments>) -> Self {
+		let function = function.into_native_function();
+		NativeMemberFunction::Closure(Arc::new(move |context, _this_type, arguments| {
+			function.call(context, arguments)
+		}))
+	}
+
+	pub fn call(&self, context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+		match self {
+			NativeMemberFunction::Function(function) => function(context, this_type, arguments),
+			NativeMemberFunction::Closure(closure) => closure(context, this_type, arguments),
+		}
+	}
+}
+
+impl<F> From<F> for NativeMemberFunction
+where
+	F: Fn(&Context, &TypeHandle, &[Value]) -> Result<Value, RuntimeError> + Send + Sync + 'static,
+{
+	fn from(closure: F) -> Self {
+		NativeMemberFunction::Closure(Arc::new(closure))
+	}
+}
+
+impl From<MemberClosure> for NativeMemberFunction {
+	fn from(closure: MemberClosure) -> Self {
+		NativeMemberFunction::Closure(closure)
+	}
+}
+
+impl std::fmt::Debug for NativeMemberFunction {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			NativeMemberFunction::Function(function) => f.debug_tuple("Function").field(function).finish(),
+			NativeMemberFunction::Closure(closure) => f.debug_tuple("Closure").field(&Arc::as_ptr(closure)).finish(),
+		}
+	}
+}
+
 #[derive(Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Debug)]
 pub enum TypeKind {
 	Bool,
@@ -57,6 +160,8 @@ pub enum TypeKind {
 	Struct,
 	Enum,
 	Array,
+	/// Wraps a Rust value of the given type, see `new_opaque_type`.
+	Opaque(std::any::TypeId),
 }
 
 pub(crate) enum Variant {
@@ -87,7 +192,10 @@ pub(crate) struct Type {
 	pub variant_names: Arc<HashMap<Box<str>, usize>>,
 	pub variants: Arc<[Variant]>,
 
-	pub functions: Arc<HashMap<Box<str>, MemberFunction>>,
+	pub functions: Arc<HashMap<Box<str>, NativeMemberFunction>>,
+	pub associated_functions: Arc<HashMap<Box<str>, NativeMemberFunction>>,
+
+	pub interfaces: Arc<[Box<str>]>,
 }
 
 impl Default for Type {
@@ -101,6 +209,8 @@ impl Default for Type {
 			variant_names: Default::default(),
 			variants: Arc::new([]),
 			functions: Default::default(),
+			associated_functions: Default::default(),
+			interfaces: Arc::new([]),
 		}
 	}
 }
@@ -123,7 +233,29 @@ pub struct TypeBuilder {
 	pub variant_names: HashMap<String, usize>,
 	pub variants: Vec<VariantBuilder>,
 
-	pub functions: HashMap<String, MemberFunction>,
+	pub functions: HashMap<String, NativeMemberFunction>,
+	/// Functions that are called on the type itself, without a receiver, e.g., `String::new()`.
+	///
+	/// ```rust
+	/// # use ralik::types::{new_struct_type, MemberFunction};
+	/// # use ralik::{eval_str, Context, Value};
+	/// let context = Context::new();
+	/// let mut point_type = new_struct_type("Point", vec![("x", "Integer"), ("y", "Integer")].into_iter());
+	/// let origin: MemberFunction = |context, this_type, _arguments| {
+	///     let zero = Value::new_integer(context, 0)?;
+	///     Ok(Value::new_struct(context, &*this_type.name(), vec![("x", zero.clone()), ("y", zero)].into_iter())?)
+	/// };
+	/// point_type.associated_functions.insert("origin".into(), origin.into());
+	/// context.register_types(vec![point_type]);
+	///
+	/// let value = eval_str("Point::origin().x", &context).unwrap();
+	/// assert_eq!(value.as_i32(), Some(0));
+	/// assert!(format!("{:+?}", context).contains(r#"associated_functions: ["origin"]"#));
+	/// ```
+	pub associated_functions: HashMap<String, NativeMemberFunction>,
+
+	/// The names of the interfaces that the type implements, see `Interface`.
+	pub interfaces: Vec<String>,
 }
 
 impl TypeBuilder {
@@ -137,6 +269,8 @@ impl TypeBuilder {
 			variant_names: Default::default(),
 			variants: Default::default(),
 			functions: Default::default(),
+			associated_functions: Default::default(),
+			interfaces: Default::default(),
 		}
 	}
 
@@ -150,7 +284,29 @@ impl TypeBuilder {
 			variant_names: generic_type_builder.variant_names,
 			variants: generic_type_builder.variants,
 			functions: generic_type_builder.functions,
+			associated_functions: generic_type_builder.associated_functions,
+			interfaces: generic_type_builder.interfaces,
+		}
+	}
+
+	/// Generates the member functions for each of the given `derives`, and declares the interfaces of the same names.
+	/// Member functions that already exist take precedence over the derived ones.
+	pub fn derive(mut self, derives: impl IntoIterator<Item = Derive>) -> Self {
+		derive(
+			&mut self.functions,
+			&mut self.associated_functions,
+			&mut self.interfaces,
+			derives,
+		);
+		self
+	}
+
+	/// Declares that the type implements the given interfaces, see `Interface`.
+	pub fn implement(mut self, interfaces: impl IntoIterator<Item = impl Into<String>>) -> Self {
+		for interface in interfaces {
+			implement(&mut self.interfaces, interface);
 		}
+		self
 	}
 }
 
@@ -164,5 +320,72 @@ pub struct GenericTypeBuilder {
 	pub variant_names: HashMap<String, usize>,
 	pub variants: Vec<VariantBuilder>,
 
-	pub functions: HashMap<String, MemberFunction>,
+	pub functions: HashMap<String, NativeMemberFunction>,
+	/// Functions that are called on the type itself, without a receiver, e.g., `String::new()`.
+	pub associated_functions: HashMap<String, NativeMemberFunction>,
+
+	/// The names of the interfaces that the type implements, see `Interface`.
+	pub interfaces: Vec<String>,
+}
+
+impl GenericTypeBuilder {
+	/// Generates the member functions for each of the given `derives`, and declares the interfaces of the same names.
+	/// Member functions that already exist take precedence over the derived ones.
+	pub fn derive(mut self, derives: impl IntoIterator<Item = Derive>) -> Self {
+		derive(
+			&mut self.functions,
+			&mut self.associated_functions,
+			&mut self.interfaces,
+			derives,
+		);
+		self
+	}
+
+	/// Declares that the type implements the given interfaces, see `Interface`.
+	pub fn implement(mut self, interfaces: impl IntoIterator<Item = impl Into<String>>) -> Self {
+		for interface in interfaces {
+			implement(&mut self.interfaces, interface);
+		}
+		self
+	}
+}
+
+/// Collects the member functions of a built-in type, which are stored as plain `fn`s.
+fn member_functions(functions: &[(&str, MemberFunction)]) -> HashMap<String, NativeMemberFunction> {
+	functions
+		.iter()
+		.map(|(name, function)| ((*name).to_owned(), NativeMemberFunction::Function(*function)))
+		.collect()
+}
+
+fn derive(
+	functions: &mut HashMap<String, NativeMemberFunction>,
+	associated_functions: &mut HashMap<String, NativeMemberFunction>,
+	interfaces: &mut Vec<String>,
+	derives: impl IntoIterator<Item = Derive>,
+) {
+	for derive in derives {
+		for (name, function) in member_functions(derive.functions()) {
+			functions.entry(name).or_insert(function);
+		}
+		for (name, function) in member_functions(derive.associated_functions()) {
+			associated_functions.entry(name).or_insert(function);
+		}
+		implement(interfaces, derive.interface());
+	}
+}
+
+fn implement(interfaces: &mut Vec<String>, interface: impl Into<String>) {
+	let interface = interface.into();
+	if !interfaces.contains(&interface) {
+		interfaces.push(interface);
+	}
+}
+
+/// The interfaces that are implemented by the scalar core types, which are totally ordered.
+fn scalar_interfaces() -> Vec<String> {
+	["Clone", "Debug", "Default", "Display", "Ord", "PartialEq", "PartialOrd"]
+		.iter()
+		.map(|name| (*name).to_owned())
+		.collect()
 }
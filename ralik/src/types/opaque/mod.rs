@@ -0,0 +1,50 @@
+use std::any::{Any, TypeId};
+
+use super::{TypeBuilder, TypeKind};
+
+/**
+Creates a type whose values wrap Rust data of type `T`, which is only accessible to native functions via
+`Value::downcast_ref`. Scripts can only pass values of opaque types around and call their member functions.
+
+```rust
+# use ralik::{eval_str, Context, TypeHandle, Value};
+# use ralik::error::RuntimeError;
+# use ralik::types::new_opaque_type;
+struct Greeter {
+	greeting: String,
+}
+
+fn greet(context: &Context, _this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	let greeter = arguments[0].downcast_ref::<Greeter>().unwrap();
+	let name = arguments[1].as_string().unwrap();
+	Ok(Value::new_string(context, format!("{}, {}!", greeter.greeting, name))?)
+}
+
+let context = Context::new();
+let mut greeter_type = new_opaque_type::<Greeter>("Greeter");
+greeter_type.functions.insert("greet".into(), greet.into());
+context.register_types(vec![greeter_type]);
+
+let greeter = Greeter { greeting: "Hello".into() };
+context.insert_variable("$", Value::new_opaque(&context, "Greeter", greeter).unwrap());
+let result = eval_str(r#"$.greet("World")"#, &context).unwrap();
+assert_eq!(result.as_string(), Some("Hello, World!"));
+
+// values of an opaque type must wrap the Rust type that it was created for
+assert!(Value::new_opaque(&context, "Greeter", 42).is_err());
+```
+*/
+pub fn new_opaque_type<T: Any>(name: impl Into<String>) -> TypeBuilder {
+	TypeBuilder {
+		name: name.into(),
+		kind: TypeKind::Opaque(TypeId::of::<T>()),
+		type_parameters: Default::default(),
+		field_names: Default::default(),
+		field_types: Default::default(),
+		variant_names: Default::default(),
+		variants: Default::default(),
+		functions: Default::default(),
+		associated_functions: Default::default(),
+		interfaces: Default::default(),
+	}
+}
@@ -1,8 +1,48 @@
+use anyhow::anyhow;
+
 use crate::error::RuntimeError;
 use crate::{Context, TypeHandle, Value};
 
 use super::super::arguments::Arguments;
 
+/**
+Returns the contained `Some` value, or fails with the given message.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"abc\".find('b').expect(\"there is a b\")", &context).unwrap();
+assert_eq!(result, eval_str("1", &context).unwrap());
+```
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+eval_str("\"abc\".find('d').expect(\"there is no d\")", &context).unwrap_err();
+```
+*/
+pub(crate) fn expect(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let this = arguments.check_type(0, this_type)?;
+	let message = arguments.as_string(1, context)?;
+
+	match this.as_variant_id().unwrap() {
+		0 => Err(anyhow!("{}", message).into()),
+		1 => Ok(this.as_array().unwrap()[0].clone()),
+		_ => unreachable!(),
+	}
+}
+
+/**
+Checks if the option is a `None` value.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"abc\".find('d').is_none()", &context).unwrap();
+assert_eq!(result, eval_str("true", &context).unwrap());
+```
+*/
 pub(crate) fn is_none(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
 	arguments.check_len(1)?;
 	let this = arguments.check_type(0, this_type)?;
@@ -14,6 +54,16 @@ pub(crate) fn is_none(context: &Context, this_type: &TypeHandle, arguments: &[Va
 	}
 }
 
+/**
+Checks if the option is a `Some` value.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"abc\".find('a').is_some()", &context).unwrap();
+assert_eq!(result, eval_str("true", &context).unwrap());
+```
+*/
 pub(crate) fn is_some(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
 	arguments.check_len(1)?;
 	let this = arguments.check_type(0, this_type)?;
@@ -24,3 +74,26 @@ pub(crate) fn is_some(context: &Context, this_type: &TypeHandle, arguments: &[Va
 		_ => unreachable!(),
 	}
 }
+
+/**
+Returns the contained `Some` value or the provided default.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"abc\".find('d').unwrap_or(42)", &context).unwrap();
+assert_eq!(result, eval_str("42", &context).unwrap());
+```
+*/
+pub(crate) fn unwrap_or(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let this = arguments.check_type(0, this_type)?;
+	let element_type = TypeHandle::from_type_id(context.clone(), this_type.type_parameter_ids()[0]);
+	let default = arguments.check_type(1, &element_type)?;
+
+	match this.as_variant_id().unwrap() {
+		0 => Ok(default.clone()),
+		1 => Ok(this.as_array().unwrap()[0].clone()),
+		_ => unreachable!(),
+	}
+}
@@ -1,64 +1,89 @@
-use lazy_static::lazy_static;
+use anyhow::ensure;
 
-use std::collections::HashMap;
-
-use super::{BasicType, BasicTypeBase, TypeHandle, TypeKind, Variant};
+use super::{member_functions, Derive, GenericTypeBuilder, TypeKind, VariantBuilder};
+use crate::error::RuntimeError;
+use crate::{Context, TypeHandle, Value};
 
 mod functions;
 mod ops;
 
-pub(crate) type OptionType = BasicType<OptionImpl>;
-
-pub(crate) struct OptionImpl {
-	name: String,
-	element_type: [TypeHandle; 1],
-	variants: [Variant; 2],
-}
-
 pub fn make_option_name(element_type: &str) -> String {
 	format!("std::option::Option<{}>", element_type)
 }
 
-lazy_static! {
-	static ref VARIANT_NAMES: HashMap<Box<str>, usize> =
-		vec![("None".into(), 0), ("Some".into(), 1)].into_iter().collect();
+/// Checks whether `r#type` is one of the option types, i.e., the type that the option generic of its context creates
+/// for its element type.
+pub(crate) fn is_option_type(r#type: &TypeHandle) -> bool {
+	r#type.is_option()
 }
 
-impl OptionType {
-	pub fn new(name: impl Into<String>, element_type: TypeHandle) -> Self {
-		Self::from_base_with_functions(
-			OptionImpl {
-				name: name.into(),
-				element_type: [element_type.clone()],
-				variants: [
-					Variant::Unit("None".into()),
-					Variant::Tuple("Some".into(), Box::new([element_type.clone()])),
-				],
-			},
-			vec![
+/**
+Creates option types, which behave like `std::option::Option`.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"abc\".find('c') == \"abc\".find(\"c\")", &context).unwrap();
+assert_eq!(result, eval_str("true", &context).unwrap());
+```
+*/
+pub fn option_generic(_context: &Context, element_type: &[&str]) -> Result<GenericTypeBuilder, anyhow::Error> {
+	ensure!(
+		element_type.len() == 1,
+		"Can only create options with exactly one element type ({} provided)",
+		element_type.len()
+	);
+	let element_type = element_type[0];
+
+	Ok(
+		GenericTypeBuilder {
+			kind: TypeKind::Enum,
+			type_parameters: vec![element_type.into()],
+			field_names: Default::default(),
+			field_types: Default::default(),
+			variant_names: vec![("None".to_owned(), 0), ("Some".to_owned(), 1)]
+				.into_iter()
+				.collect(),
+			variants: vec![
+				VariantBuilder::Unit("None".into()),
+				VariantBuilder::Tuple("Some".into(), vec![element_type.into()]),
+			],
+			functions: member_functions(&[
 				(crate::ops::UNWRAP, ops::unwrap),
-				("unwrap", ops::unwrap),
+				("expect", functions::expect),
 				("is_none", functions::is_none),
 				("is_some", functions::is_some),
-			],
-		)
-	}
+				("unwrap", ops::unwrap),
+				("unwrap_or", functions::unwrap_or),
+			]),
+			associated_functions: Default::default(),
+			interfaces: Default::default(),
+		}
+		.derive(vec![
+			Derive::PartialEq,
+			Derive::PartialOrd,
+			Derive::Clone,
+			Derive::Debug,
+			Derive::Default,
+		]),
+	)
 }
 
-impl BasicTypeBase for OptionImpl {
-	fn name(&self) -> &str {
-		&self.name
-	}
-
-	fn kind(&self) -> TypeKind {
-		TypeKind::Enum
-	}
-
-	fn type_parameters(&self) -> &[TypeHandle] {
-		&self.element_type
-	}
+pub(crate) fn new_some(context: &Context, value: Value) -> Result<Value, RuntimeError> {
+	let option_type = context
+		.get_option_type(&value.get_type().name())
+		.map_err(|err| RuntimeError::InvalidCoreType(err.into()))?;
+	Ok(Value::new_enum_tuple_variant(
+		context,
+		option_type.name(),
+		"Some",
+		vec![value],
+	)?)
+}
 
-	fn variants(&self) -> Option<(&HashMap<Box<str>, usize>, &[Variant])> {
-		Some((&VARIANT_NAMES, &self.variants))
-	}
+pub(crate) fn new_none(context: &Context, element_type: &TypeHandle) -> Result<Value, RuntimeError> {
+	let option_type = context
+		.get_option_type(&element_type.name())
+		.map_err(|err| RuntimeError::InvalidCoreType(err.into()))?;
+	Ok(Value::new_enum_unit_variant(context, option_type.name(), "None")?)
 }
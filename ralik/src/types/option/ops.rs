@@ -1,5 +1,4 @@
 use anyhow::anyhow;
-use pretty_assertions::assert_eq;
 
 use crate::error::RuntimeError;
 use crate::{Context, TypeHandle, Value};
@@ -11,12 +10,8 @@ pub(crate) fn unwrap(_context: &Context, this_type: &TypeHandle, arguments: &[Va
 	let this = arguments.check_type(0, this_type)?;
 
 	match this.as_variant_id().unwrap() {
-		0 => Err(anyhow!("Cannot unwrap option: is `None`").into()),
-		1 => {
-			let elements = this.as_array().unwrap();
-			assert_eq!(elements.len(), 1);
-			Ok(elements[0].clone())
-		}
+		0 => Err(anyhow!("Called `Option::unwrap()` on a `None` value").into()),
+		1 => Ok(this.as_array().unwrap()[0].clone()),
 		_ => unreachable!(),
 	}
 }
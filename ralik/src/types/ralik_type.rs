@@ -0,0 +1,229 @@
+use num::BigInt;
+
+use crate::error::RuntimeError;
+use crate::{Context, TypeHandle};
+
+use super::{NativeMemberFunction, TypeBuilder};
+
+/**
+A Rust type that has a corresponding ralik type, which is usually implemented via `#[derive(RalikType)]` (requires the
+`derive` feature).
+
+```rust
+# use ralik::types::RalikType;
+let context = ralik::Context::new();
+let r#type = <Vec<(String, Option<u8>)>>::register(&context).unwrap();
+assert_eq!(&*r#type.name(), "[(std::string::String, std::option::Option<Integer>)]");
+assert_eq!(&*r#type.name(), <Vec<(String, Option<u8>)>>::type_name());
+```
+*/
+pub trait RalikType {
+	fn type_name() -> String;
+
+	/// Registers the ralik type and all types that it depends on with `context`, unless they are already registered.
+	fn register(context: &Context) -> Result<TypeHandle, RuntimeError>;
+
+	/// Adds the builders of the ralik type and the types that it depends on to `type_builders`, unless they are
+	/// registered with `context` or already added, so that types that refer to themselves can be registered together.
+	/// Types that cannot refer to themselves are registered right away.
+	fn collect_type_builders(context: &Context, _type_builders: &mut Vec<TypeBuilder>) -> Result<(), RuntimeError> {
+		Self::register(context).map(|_type| ())
+	}
+}
+
+/// Whether the type `name` is among the `type_builders` that are about to be registered.
+fn is_pending(type_builders: &[TypeBuilder], name: &str) -> bool {
+	type_builders.iter().any(|type_builder| type_builder.name == name)
+}
+
+fn add_pending(type_builders: &mut Vec<TypeBuilder>, type_builder: TypeBuilder) {
+	if !is_pending(type_builders, &type_builder.name) {
+		type_builders.push(type_builder);
+	}
+}
+
+/// The member functions of a Rust type, which are usually generated by marking the functions of an `impl` block that is
+/// annotated with `#[ralik::methods]` with `#[ralik(method)]` (requires the `derive` feature).
+pub trait RalikMethods {
+	fn methods() -> Vec<(&'static str, NativeMemberFunction)>;
+}
+
+impl RalikType for () {
+	fn type_name() -> String {
+		super::make_tuple_name(std::iter::empty::<&str>())
+	}
+
+	fn register(context: &Context) -> Result<TypeHandle, RuntimeError> {
+		context
+			.get_unit_type()
+			.map_err(|err| RuntimeError::InvalidCoreType(err.into()))
+	}
+}
+
+impl RalikType for bool {
+	fn type_name() -> String {
+		super::make_bool_name().into()
+	}
+
+	fn register(context: &Context) -> Result<TypeHandle, RuntimeError> {
+		context
+			.get_bool_type()
+			.map_err(|err| RuntimeError::InvalidCoreType(err.into()))
+	}
+}
+
+impl RalikType for char {
+	fn type_name() -> String {
+		super::make_char_name().into()
+	}
+
+	fn register(context: &Context) -> Result<TypeHandle, RuntimeError> {
+		context
+			.get_char_type()
+			.map_err(|err| RuntimeError::InvalidCoreType(err.into()))
+	}
+}
+
+macro_rules! impl_integer {
+	($($integer:ty),* $(,)?) => {
+		$(
+			impl RalikType for $integer {
+				fn type_name() -> String {
+					super::make_integer_name().into()
+				}
+
+				fn register(context: &Context) -> Result<TypeHandle, RuntimeError> {
+					context
+						.get_integer_type()
+						.map_err(|err| RuntimeError::InvalidCoreType(err.into()))
+				}
+			}
+		)*
+	};
+}
+
+impl_integer!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize, BigInt);
+
+impl RalikType for String {
+	fn type_name() -> String {
+		super::make_string_name().into()
+	}
+
+	fn register(context: &Context) -> Result<TypeHandle, RuntimeError> {
+		context
+			.get_string_type()
+			.map_err(|err| RuntimeError::InvalidCoreType(err.into()))
+	}
+}
+
+impl<T: RalikType> RalikType for Vec<T> {
+	fn type_name() -> String {
+		super::make_array_name(&T::type_name())
+	}
+
+	fn register(context: &Context) -> Result<TypeHandle, RuntimeError> {
+		context
+			.get_array_type(&T::register(context)?.name())
+			.map_err(|err| RuntimeError::InvalidCoreType(err.into()))
+	}
+
+	fn collect_type_builders(context: &Context, type_builders: &mut Vec<TypeBuilder>) -> Result<(), RuntimeError> {
+		T::collect_type_builders(context, type_builders)?;
+		if !is_pending(type_builders, &T::type_name()) {
+			return Self::register(context).map(|_type| ());
+		}
+		let type_builder = context
+			.array_type_builder(&T::type_name())
+			.map_err(|err| RuntimeError::InvalidCoreType(err.into()))?;
+		add_pending(type_builders, type_builder);
+		Ok(())
+	}
+}
+
+impl<T: RalikType> RalikType for Option<T> {
+	fn type_name() -> String {
+		super::make_option_name(&T::type_name())
+	}
+
+	fn register(context: &Context) -> Result<TypeHandle, RuntimeError> {
+		context
+			.get_option_type(&T::register(context)?.name())
+			.map_err(|err| RuntimeError::InvalidCoreType(err.into()))
+	}
+
+	fn collect_type_builders(context: &Context, type_builders: &mut Vec<TypeBuilder>) -> Result<(), RuntimeError> {
+		T::collect_type_builders(context, type_builders)?;
+		if !is_pending(type_builders, &T::type_name()) {
+			return Self::register(context).map(|_type| ());
+		}
+		let type_builder = context
+			.option_type_builder(&T::type_name())
+			.map_err(|err| RuntimeError::InvalidCoreType(err.into()))?;
+		add_pending(type_builders, type_builder);
+		Ok(())
+	}
+}
+
+impl<T: RalikType, E: RalikType> RalikType for Result<T, E> {
+	fn type_name() -> String {
+		super::make_result_name(&T::type_name(), &E::type_name())
+	}
+
+	fn register(context: &Context) -> Result<TypeHandle, RuntimeError> {
+		context
+			.get_result_type(&T::register(context)?.name(), &E::register(context)?.name())
+			.map_err(|err| RuntimeError::InvalidCoreType(err.into()))
+	}
+
+	fn collect_type_builders(context: &Context, type_builders: &mut Vec<TypeBuilder>) -> Result<(), RuntimeError> {
+		T::collect_type_builders(context, type_builders)?;
+		E::collect_type_builders(context, type_builders)?;
+		if !is_pending(type_builders, &T::type_name()) && !is_pending(type_builders, &E::type_name()) {
+			return Self::register(context).map(|_type| ());
+		}
+		let type_builder = context
+			.result_type_builder(&T::type_name(), &E::type_name())
+			.map_err(|err| RuntimeError::InvalidCoreType(err.into()))?;
+		add_pending(type_builders, type_builder);
+		Ok(())
+	}
+}
+
+macro_rules! impl_tuple {
+	($($element:ident),*) => {
+		impl<$($element: RalikType),*> RalikType for ($($element,)*) {
+			fn type_name() -> String {
+				super::make_tuple_name([$($element::type_name()),*].iter())
+			}
+
+			fn register(context: &Context) -> Result<TypeHandle, RuntimeError> {
+				let element_types: Vec<String> = vec![$((*$element::register(context)?.name()).into()),*];
+				context
+					.get_tuple_type(element_types)
+					.map_err(|err| RuntimeError::InvalidCoreType(err.into()))
+			}
+
+			fn collect_type_builders(context: &Context, type_builders: &mut Vec<TypeBuilder>) -> Result<(), RuntimeError> {
+				$($element::collect_type_builders(context, type_builders)?;)*
+				let element_types = vec![$($element::type_name()),*];
+				if !element_types.iter().any(|name| is_pending(type_builders, name)) {
+					return Self::register(context).map(|_type| ());
+				}
+				let type_builder = context
+					.tuple_type_builder(element_types)
+					.map_err(|err| RuntimeError::InvalidCoreType(err.into()))?;
+				add_pending(type_builders, type_builder);
+				Ok(())
+			}
+		}
+	};
+}
+
+impl_tuple!(A);
+impl_tuple!(A, B);
+impl_tuple!(A, B, C);
+impl_tuple!(A, B, C, D);
+impl_tuple!(A, B, C, D, E);
+impl_tuple!(A, B, C, D, E, F);
+impl_tuple!(A, B, C, D, E, F, G);
+impl_tuple!(A, B, C, D, E, F, G, H);
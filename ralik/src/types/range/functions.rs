@@ -0,0 +1,80 @@
+use std::ops::{Bound, RangeBounds};
+
+use crate::error::RuntimeError;
+use crate::{Context, TypeHandle, Value};
+
+use super::super::arguments::Arguments;
+use super::as_bounds;
+
+/**
+Checks if the given integer is contained in the range.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("(1..3).contains(3)", &context).unwrap();
+assert_eq!(result, eval_str("false", &context).unwrap());
+```
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("(..=3).contains(3)", &context).unwrap();
+assert_eq!(result, eval_str("true", &context).unwrap());
+```
+*/
+pub(crate) fn contains(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let bounds = as_bounds(arguments.check_type(0, this_type)?).unwrap();
+	let item = arguments.as_integer(1, context)?;
+	Ok(Value::new_bool(context, bounds.contains(item))?)
+}
+
+pub(crate) fn debug(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let (start, end) = as_bounds(arguments.check_type(0, this_type)?).unwrap();
+	let _alternate = arguments.as_bool(1, context)?;
+
+	let mut result = String::new();
+	if let Bound::Included(start) = start {
+		result.push_str(&start.to_string());
+	}
+	result.push_str("..");
+	match end {
+		Bound::Included(end) => {
+			result.push('=');
+			result.push_str(&end.to_string());
+		}
+		Bound::Excluded(end) => result.push_str(&end.to_string()),
+		Bound::Unbounded => (),
+	}
+
+	Ok(Value::new_string(context, result)?)
+}
+
+/**
+Checks if the range contains no items.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("(3..3).is_empty()", &context).unwrap();
+assert_eq!(result, eval_str("true", &context).unwrap());
+```
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("(3..=3).is_empty()", &context).unwrap();
+assert_eq!(result, eval_str("false", &context).unwrap());
+```
+*/
+pub(crate) fn is_empty(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(1)?;
+	let is_empty = match as_bounds(arguments.check_type(0, this_type)?).unwrap() {
+		(Bound::Included(start), Bound::Excluded(end)) => start >= end,
+		(Bound::Included(start), Bound::Included(end)) => start > end,
+		_ => false,
+	};
+	Ok(Value::new_bool(context, is_empty)?)
+}
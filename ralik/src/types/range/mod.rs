@@ -0,0 +1,150 @@
+use num::BigInt;
+
+use std::ops::Bound;
+
+use super::{member_functions, Derive, MemberFunction, TypeBuilder, TypeKind};
+use crate::error::ValueCreationError;
+use crate::{Context, Value};
+
+mod functions;
+
+pub const fn make_range_name() -> &'static str {
+	"std::ops::Range<Integer>"
+}
+
+pub const fn make_range_inclusive_name() -> &'static str {
+	"std::ops::RangeInclusive<Integer>"
+}
+
+pub const fn make_range_from_name() -> &'static str {
+	"std::ops::RangeFrom<Integer>"
+}
+
+pub const fn make_range_to_name() -> &'static str {
+	"std::ops::RangeTo<Integer>"
+}
+
+pub const fn make_range_to_inclusive_name() -> &'static str {
+	"std::ops::RangeToInclusive<Integer>"
+}
+
+pub const fn make_range_full_name() -> &'static str {
+	"std::ops::RangeFull"
+}
+
+/**
+Creates the integer range types, which are the result of the range expressions `a..b`, `a..=b`, `a..`, `..b`, `..=b`
+and `..`.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("(1..3).contains(2)", &context).unwrap();
+assert_eq!(result, eval_str("true", &context).unwrap());
+```
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("(1..=3).end", &context).unwrap();
+assert_eq!(result, eval_str("3", &context).unwrap());
+```
+*/
+pub fn new_range_types() -> Vec<TypeBuilder> {
+	vec![
+		new_range_type(
+			make_range_name(),
+			&["start", "end"],
+			&[("is_empty", functions::is_empty)],
+		),
+		new_range_type(
+			make_range_inclusive_name(),
+			&["start", "end"],
+			&[("is_empty", functions::is_empty)],
+		),
+		new_range_type(make_range_from_name(), &["start"], &[]),
+		new_range_type(make_range_to_name(), &["end"], &[]),
+		new_range_type(make_range_to_inclusive_name(), &["end"], &[]),
+		new_range_type(make_range_full_name(), &[], &[]),
+	]
+}
+
+fn new_range_type(name: &str, field_names: &[&str], functions: &[(&str, MemberFunction)]) -> TypeBuilder {
+	TypeBuilder {
+		name: name.into(),
+		kind: if field_names.is_empty() {
+			TypeKind::UnitStruct
+		} else {
+			TypeKind::Struct
+		},
+		type_parameters: Default::default(),
+		field_names: field_names
+			.iter()
+			.enumerate()
+			.map(|(i, field_name)| ((*field_name).to_owned(), i))
+			.collect(),
+		field_types: field_names
+			.iter()
+			.map(|_field_name| crate::types::make_integer_name().to_owned())
+			.collect(),
+		variant_names: Default::default(),
+		variants: Default::default(),
+		functions: member_functions(&[(crate::ops::DEBUG, functions::debug), ("contains", functions::contains)])
+			.into_iter()
+			.chain(member_functions(functions))
+			.collect(),
+		associated_functions: Default::default(),
+		interfaces: vec!["Debug".into()],
+	}
+	.derive(vec![Derive::PartialEq, Derive::Clone])
+}
+
+/// Creates a value of the range type that matches the given bounds.
+pub(crate) fn new_range(
+	context: &Context,
+	start: Option<Value>,
+	end: Option<Value>,
+	inclusive: bool,
+) -> Result<Value, ValueCreationError> {
+	let name = match (&start, &end, inclusive) {
+		(Some(_), Some(_), false) => make_range_name(),
+		(Some(_), Some(_), true) => make_range_inclusive_name(),
+		(Some(_), None, false) => make_range_from_name(),
+		(None, Some(_), false) => make_range_to_name(),
+		(None, Some(_), true) => make_range_to_inclusive_name(),
+		(None, None, false) => make_range_full_name(),
+		(_, None, true) => panic!("Inclusive ranges must have an end"),
+	};
+
+	if start.is_none() && end.is_none() {
+		Ok(Value::new_unit_struct(context, name)?)
+	} else {
+		let fields = start
+			.map(|start| ("start", start))
+			.into_iter()
+			.chain(end.map(|end| ("end", end)));
+		Ok(Value::new_struct(context, name, fields)?)
+	}
+}
+
+/// Converts a value of one of the range types into its bounds, or returns `None` if the value is not a range.
+pub(crate) fn as_bounds(value: &Value) -> Option<(Bound<&BigInt>, Bound<&BigInt>)> {
+	let field = |name| value.field(name).and_then(|field| field.as_integer());
+
+	let name = value.get_type().name();
+	if &*name == make_range_name() {
+		Some((Bound::Included(field("start")?), Bound::Excluded(field("end")?)))
+	} else if &*name == make_range_inclusive_name() {
+		Some((Bound::Included(field("start")?), Bound::Included(field("end")?)))
+	} else if &*name == make_range_from_name() {
+		Some((Bound::Included(field("start")?), Bound::Unbounded))
+	} else if &*name == make_range_to_name() {
+		Some((Bound::Unbounded, Bound::Excluded(field("end")?)))
+	} else if &*name == make_range_to_inclusive_name() {
+		Some((Bound::Unbounded, Bound::Included(field("end")?)))
+	} else if &*name == make_range_full_name() {
+		Some((Bound::Unbounded, Bound::Unbounded))
+	} else {
+		None
+	}
+}
@@ -0,0 +1,168 @@
+use anyhow::anyhow;
+
+use crate::error::RuntimeError;
+use crate::{Context, TypeHandle, Value};
+
+use super::super::arguments::Arguments;
+use super::super::option::{new_none, new_some};
+
+/**
+Converts the result into an option of its error value.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"12\".parse::<Integer>().err().is_none()", &context).unwrap();
+assert_eq!(result, eval_str("true", &context).unwrap());
+```
+*/
+pub(crate) fn err(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(1)?;
+	let this = arguments.check_type(0, this_type)?;
+
+	match this.as_variant_id().unwrap() {
+		0 => new_none(
+			context,
+			&TypeHandle::from_type_id(context.clone(), this_type.type_parameter_ids()[1]),
+		),
+		1 => new_some(context, this.as_array().unwrap()[0].clone()),
+		_ => unreachable!(),
+	}
+}
+
+/**
+Returns the contained `Ok` value, or fails with the given message.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"12\".parse::<Integer>().expect(\"is a number\")", &context).unwrap();
+assert_eq!(result, eval_str("12", &context).unwrap());
+```
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+eval_str("\"twelve\".parse::<Integer>().expect(\"is a number\")", &context).unwrap_err();
+```
+*/
+pub(crate) fn expect(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let this = arguments.check_type(0, this_type)?;
+	let message = arguments.as_string(1, context)?;
+
+	match this.as_variant_id().unwrap() {
+		0 => Ok(this.as_array().unwrap()[0].clone()),
+		1 => Err(anyhow!("{}: {}", message, this.as_array().unwrap()[0]).into()),
+		_ => unreachable!(),
+	}
+}
+
+/**
+Checks if the result is an `Err` value.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"x\".parse::<Integer>().is_err()", &context).unwrap();
+assert_eq!(result, eval_str("true", &context).unwrap());
+```
+*/
+pub(crate) fn is_err(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(1)?;
+	let this = arguments.check_type(0, this_type)?;
+	Ok(Value::new_bool(context, this.as_variant_id().unwrap() == 1)?)
+}
+
+/**
+Checks if the result is an `Ok` value.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"true\".parse::<bool>().is_ok()", &context).unwrap();
+assert_eq!(result, eval_str("true", &context).unwrap());
+```
+*/
+pub(crate) fn is_ok(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(1)?;
+	let this = arguments.check_type(0, this_type)?;
+	Ok(Value::new_bool(context, this.as_variant_id().unwrap() == 0)?)
+}
+
+/**
+Converts the result into an option of its `Ok` value.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"x\".parse::<char>().ok().unwrap()", &context).unwrap();
+assert_eq!(result, eval_str("'x'", &context).unwrap());
+```
+*/
+pub(crate) fn ok(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(1)?;
+	let this = arguments.check_type(0, this_type)?;
+
+	match this.as_variant_id().unwrap() {
+		0 => new_some(context, this.as_array().unwrap()[0].clone()),
+		1 => new_none(
+			context,
+			&TypeHandle::from_type_id(context.clone(), this_type.type_parameter_ids()[0]),
+		),
+		_ => unreachable!(),
+	}
+}
+
+/**
+Returns the contained `Err` value.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+eval_str("\"12\".parse::<Integer>().unwrap_err()", &context).unwrap_err();
+```
+*/
+pub(crate) fn unwrap_err(
+	_context: &Context,
+	this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	arguments.check_len(1)?;
+	let this = arguments.check_type(0, this_type)?;
+
+	match this.as_variant_id().unwrap() {
+		0 => Err(
+			anyhow!(
+				"Called `Result::unwrap_err()` on an `Ok` value: {}",
+				this.as_array().unwrap()[0]
+			)
+			.into(),
+		),
+		1 => Ok(this.as_array().unwrap()[0].clone()),
+		_ => unreachable!(),
+	}
+}
+
+/**
+Returns the contained `Ok` value or the provided default.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"x\".parse::<Integer>().unwrap_or(0)", &context).unwrap();
+assert_eq!(result, eval_str("0", &context).unwrap());
+```
+*/
+pub(crate) fn unwrap_or(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let this = arguments.check_type(0, this_type)?;
+	let ok_type = TypeHandle::from_type_id(context.clone(), this_type.type_parameter_ids()[0]);
+	let default = arguments.check_type(1, &ok_type)?;
+
+	match this.as_variant_id().unwrap() {
+		0 => Ok(this.as_array().unwrap()[0].clone()),
+		1 => Ok(default.clone()),
+		_ => unreachable!(),
+	}
+}
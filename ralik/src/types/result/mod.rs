@@ -0,0 +1,89 @@
+use anyhow::ensure;
+
+use super::{member_functions, Derive, GenericTypeBuilder, TypeKind, VariantBuilder};
+use crate::error::RuntimeError;
+use crate::{Context, TypeHandle, Value};
+
+mod functions;
+mod ops;
+
+pub fn make_result_name(ok_type: &str, err_type: &str) -> String {
+	format!("std::result::Result<{}, {}>", ok_type, err_type)
+}
+
+/**
+Creates result types, which behave like `std::result::Result`.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"12\".parse::<Integer>() == \"12\".parse::<Integer>()", &context).unwrap();
+assert_eq!(result, eval_str("true", &context).unwrap());
+```
+*/
+pub fn result_generic(_context: &Context, type_parameters: &[&str]) -> Result<GenericTypeBuilder, anyhow::Error> {
+	ensure!(
+		type_parameters.len() == 2,
+		"Can only create results with exactly two type parameters ({} provided)",
+		type_parameters.len()
+	);
+	let ok_type = type_parameters[0];
+	let err_type = type_parameters[1];
+
+	Ok(
+		GenericTypeBuilder {
+			kind: TypeKind::Enum,
+			type_parameters: vec![ok_type.into(), err_type.into()],
+			field_names: Default::default(),
+			field_types: Default::default(),
+			variant_names: vec![("Ok".to_owned(), 0), ("Err".to_owned(), 1)].into_iter().collect(),
+			variants: vec![
+				VariantBuilder::Tuple("Ok".into(), vec![ok_type.into()]),
+				VariantBuilder::Tuple("Err".into(), vec![err_type.into()]),
+			],
+			functions: member_functions(&[
+				(crate::ops::UNWRAP, ops::unwrap),
+				("err", functions::err),
+				("expect", functions::expect),
+				("is_err", functions::is_err),
+				("is_ok", functions::is_ok),
+				("ok", functions::ok),
+				("unwrap", ops::unwrap),
+				("unwrap_err", functions::unwrap_err),
+				("unwrap_or", functions::unwrap_or),
+			]),
+			associated_functions: Default::default(),
+			interfaces: Default::default(),
+		}
+		.derive(vec![
+			Derive::PartialEq,
+			Derive::PartialOrd,
+			Derive::Clone,
+			Derive::Debug,
+		]),
+	)
+}
+
+pub(crate) fn new_ok(context: &Context, value: Value, err_type: &TypeHandle) -> Result<Value, RuntimeError> {
+	let result_type = context
+		.get_result_type(&value.get_type().name(), &err_type.name())
+		.map_err(|err| RuntimeError::InvalidCoreType(err.into()))?;
+	Ok(Value::new_enum_tuple_variant(
+		context,
+		result_type.name(),
+		"Ok",
+		vec![value],
+	)?)
+}
+
+pub(crate) fn new_err(context: &Context, ok_type: &TypeHandle, value: Value) -> Result<Value, RuntimeError> {
+	let result_type = context
+		.get_result_type(&ok_type.name(), &value.get_type().name())
+		.map_err(|err| RuntimeError::InvalidCoreType(err.into()))?;
+	Ok(Value::new_enum_tuple_variant(
+		context,
+		result_type.name(),
+		"Err",
+		vec![value],
+	)?)
+}
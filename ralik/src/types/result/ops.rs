@@ -0,0 +1,23 @@
+use anyhow::anyhow;
+
+use crate::error::RuntimeError;
+use crate::{Context, TypeHandle, Value};
+
+use super::super::arguments::Arguments;
+
+pub(crate) fn unwrap(_context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(1)?;
+	let this = arguments.check_type(0, this_type)?;
+
+	match this.as_variant_id().unwrap() {
+		0 => Ok(this.as_array().unwrap()[0].clone()),
+		1 => Err(
+			anyhow!(
+				"Called `Result::unwrap()` on an `Err` value: {}",
+				this.as_array().unwrap()[0]
+			)
+			.into(),
+		),
+		_ => unreachable!(),
+	}
+}
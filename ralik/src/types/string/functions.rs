@@ -1,10 +1,16 @@
 use anyhow::anyhow;
-use num::ToPrimitive;
+use num::{BigInt, ToPrimitive};
+
+use std::ops::Bound;
 
 use crate::error::{Overflow, RuntimeError};
+use crate::types::{make_char_name, make_integer_name};
 use crate::{Context, TypeHandle, Value};
 
 use super::super::arguments::Arguments;
+use super::super::option::{new_none, new_some};
+use super::super::result::{new_err, new_ok};
+use super::pattern::{Pattern, PatternArgument};
 
 /**
 Get the string as an array of UTF-8 encoded bytes.
@@ -32,6 +38,78 @@ pub(crate) fn as_bytes(context: &Context, _this_type: &TypeHandle, arguments: &[
 	)?)
 }
 
+/**
+Get the string as an array of UTF-8 encoded bytes. This is the same as `as_bytes`, as ralik has no iterators.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"ä\".bytes()", &context).unwrap();
+assert_eq!(result, eval_str("[0xc3, 0xa4]", &context).unwrap());
+```
+*/
+pub(crate) fn bytes(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	as_bytes(context, this_type, arguments)
+}
+
+/**
+Get the characters of the string, paired with their byte offsets.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"äb\".char_indices()", &context).unwrap();
+assert_eq!(result, eval_str("[(0, 'ä'), (2, 'b')]", &context).unwrap());
+```
+*/
+pub(crate) fn char_indices(
+	context: &Context,
+	_this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	arguments.check_len(1)?;
+	let this = arguments.as_string(0, context)?;
+	let element_type = context
+		.get_tuple_type(vec![make_integer_name(), make_char_name()])
+		.map_err(|err| RuntimeError::InvalidCoreType(err.into()))?;
+	let array = this
+		.char_indices()
+		.map(|(index, c)| {
+			Ok(Value::new_tuple(
+				context,
+				vec![Value::new_integer(context, index)?, Value::new_char(context, c)?],
+			)?)
+		})
+		.collect::<Result<Vec<_>, RuntimeError>>()?;
+
+	Ok(Value::new_array(context, &element_type, array)?)
+}
+
+/**
+Get the characters of the string.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"abc\".chars()", &context).unwrap();
+assert_eq!(result, eval_str("['a', 'b', 'c']", &context).unwrap());
+```
+*/
+pub(crate) fn chars(context: &Context, _this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(1)?;
+	let this = arguments.as_string(0, context)?;
+	let array = this
+		.chars()
+		.map(|c| Value::new_char(context, c))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	Ok(Value::new_array(
+		context,
+		&context.get_char_type().map_err(|err| anyhow!(err))?,
+		array,
+	)?)
+}
+
 /**
 Cloning a string is effectively a no-op in ralik, as all operations have value-semantics.
 */
@@ -40,6 +118,79 @@ pub(crate) fn clone(_context: &Context, this_type: &TypeHandle, arguments: &[Val
 	arguments.check_type(0, this_type).map(|value| value.clone())
 }
 
+/**
+Checks if the string contains the pattern, which may be a `char` or a string.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"abc\".contains(\"bc\")", &context).unwrap();
+assert_eq!(result, eval_str("true", &context).unwrap());
+```
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"abc\".contains('d')", &context).unwrap();
+assert_eq!(result, eval_str("false", &context).unwrap());
+```
+*/
+pub(crate) fn contains(context: &Context, _this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let this = arguments.as_string(0, context)?;
+	let pattern = arguments.as_pattern(1, context)?;
+	Ok(Value::new_bool(context, with_pattern!(pattern, |p| this.contains(p)))?)
+}
+
+/**
+Creates the debug representation of a string, which is quoted and escaped.
+
+```rust
+# use ralik::{Context, Value};
+# let context = Context::new();
+let value = Value::new_string(&context, "a\"b").unwrap();
+let debug = value.get_type().get_function("[op]::Debug").unwrap();
+let alternate = Value::new_bool(&context, false).unwrap();
+let result = debug.call(&context, value.get_type(), &[value.clone(), alternate]).unwrap();
+assert_eq!(result.as_string(), Some("\"a\\\"b\""));
+```
+*/
+pub(crate) fn debug(context: &Context, _this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let this = arguments.as_string(0, context)?;
+	let _alternate = arguments.as_bool(1, context)?;
+	Ok(Value::new_string(context, format!("{:?}", this))?)
+}
+
+/**
+The default value of a string is the empty string.
+*/
+pub(crate) fn default(context: &Context, _this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(0)?;
+	Ok(Value::new_string(context, "")?)
+}
+
+/**
+Checks if the string ends with the pattern, which may be a `char` or a string.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"abc\".ends_with('c')", &context).unwrap();
+assert_eq!(result, eval_str("true", &context).unwrap());
+```
+*/
+pub(crate) fn ends_with(
+	context: &Context,
+	_this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let this = arguments.as_string(0, context)?;
+	let pattern = arguments.as_pattern(1, context)?;
+	Ok(Value::new_bool(context, with_pattern!(pattern, |p| this.ends_with(p)))?)
+}
+
 /**
 Case-insensitive comparison for ASCII strings.
 
@@ -68,6 +219,93 @@ pub(crate) fn eq_ignore_ascii_case(
 	Ok(Value::new_bool(context, this.eq_ignore_ascii_case(&arg))?)
 }
 
+/**
+Finds the byte index of the first match of the pattern, which may be a `char` or a string.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"abcabc\".find(\"bc\").unwrap()", &context).unwrap();
+assert_eq!(result, eval_str("1", &context).unwrap());
+```
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"abc\".find('d').is_none()", &context).unwrap();
+assert_eq!(result, eval_str("true", &context).unwrap());
+```
+*/
+pub(crate) fn find(context: &Context, _this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let this = arguments.as_string(0, context)?;
+	let pattern = arguments.as_pattern(1, context)?;
+	new_index_option(context, with_pattern!(pattern, |p| this.find(p)))
+}
+
+/**
+Associated function that creates a new string from a `char` or a string.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("String::from('a') + String::from(\"bc\")", &context).unwrap();
+assert_eq!(result, eval_str("\"abc\"", &context).unwrap());
+```
+*/
+pub(crate) fn from(context: &Context, _this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(1)?;
+	let value = match arguments.as_pattern(0, context)? {
+		Pattern::Char(value) => value.to_string(),
+		Pattern::String(value) => value.to_owned(),
+	};
+	Ok(Value::new_string(context, value)?)
+}
+
+/**
+Gets the substring in the given byte range, if the range is valid.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"abcd\".get(1..3).unwrap()", &context).unwrap();
+assert_eq!(result, eval_str("\"bc\"", &context).unwrap());
+```
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"abcd\".get(2..)", &context).unwrap();
+assert_eq!(result, eval_str("\"cd\".get(..)", &context).unwrap());
+```
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+// the first byte of `ä` is not a character boundary:
+let result = eval_str("\"äb\".get(1..).is_none()", &context).unwrap();
+assert_eq!(result, eval_str("true", &context).unwrap());
+```
+*/
+pub(crate) fn get(context: &Context, _this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let this = arguments.as_string(0, context)?;
+	let (start, end) = crate::types::as_bounds(&arguments[1]).ok_or_else(|| RuntimeError::InvalidArgumentType {
+		argument_number: 1,
+		actual_type_name: (*arguments[1].get_type().name()).into(),
+		expected_type_name: "std::ops::Range<Integer>".into(),
+	})?;
+	let to_usize = |bound: Bound<&BigInt>| -> Result<Bound<usize>, RuntimeError> {
+		Ok(match bound {
+			Bound::Included(value) => Bound::Included(value.to_usize().ok_or(Overflow::USize)?),
+			Bound::Excluded(value) => Bound::Excluded(value.to_usize().ok_or(Overflow::USize)?),
+			Bound::Unbounded => Bound::Unbounded,
+		})
+	};
+
+	new_substring_option(context, this.get((to_usize(start)?, to_usize(end)?)))
+}
+
 /**
 Checks if a string is ASCII-only.
 
@@ -158,6 +396,167 @@ pub(crate) fn len(context: &Context, _this_type: &TypeHandle, arguments: &[Value
 	Ok(Value::new_integer(context, value.len())?)
 }
 
+/**
+Splits the string into its lines, which may be terminated by `\n` or `\r\n`.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"a\\r\\nb\\n\\nc\\n\".lines()", &context).unwrap();
+assert_eq!(result, eval_str("[\"a\", \"b\", \"\", \"c\"]", &context).unwrap());
+```
+*/
+pub(crate) fn lines(context: &Context, _this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(1)?;
+	let this = arguments.as_string(0, context)?;
+	new_string_array(context, this.lines())
+}
+
+/**
+Associated function that creates a new, empty string.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("String::new()", &context).unwrap();
+assert_eq!(result, eval_str("\"\"", &context).unwrap());
+```
+*/
+pub(crate) fn new(context: &Context, _this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(0)?;
+	Ok(Value::new_string(context, "")?)
+}
+
+/**
+Parses the string as a `bool`.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"true\".parse::<bool>().unwrap()", &context).unwrap();
+assert_eq!(result, eval_str("true", &context).unwrap());
+```
+*/
+pub(crate) fn parse_bool(
+	context: &Context,
+	_this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	arguments.check_len(1)?;
+	let this = arguments.as_string(0, context)?;
+	let bool_type = context.get_bool_type().map_err(|err| anyhow!(err))?;
+	new_parse_result(context, &bool_type, this.parse::<bool>(), |value| {
+		Value::new_bool(context, value)
+	})
+}
+
+/**
+Parses the string as a `char`, which requires it to consist of exactly one character.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"ab\".parse::<char>().is_err()", &context).unwrap();
+assert_eq!(result, eval_str("true", &context).unwrap());
+```
+*/
+pub(crate) fn parse_char(
+	context: &Context,
+	_this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	arguments.check_len(1)?;
+	let this = arguments.as_string(0, context)?;
+	let char_type = context.get_char_type().map_err(|err| anyhow!(err))?;
+	new_parse_result(context, &char_type, this.parse::<char>(), |value| {
+		Value::new_char(context, value)
+	})
+}
+
+/**
+Parses the string as an `Integer`.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"-42\".parse::<Integer>().unwrap()", &context).unwrap();
+assert_eq!(result, eval_str("-42", &context).unwrap());
+```
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"4 2\".parse::<Integer>().is_err()", &context).unwrap();
+assert_eq!(result, eval_str("true", &context).unwrap());
+```
+*/
+pub(crate) fn parse_integer(
+	context: &Context,
+	_this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	arguments.check_len(1)?;
+	let this = arguments.as_string(0, context)?;
+	let integer_type = context.get_integer_type().map_err(|err| anyhow!(err))?;
+	new_parse_result(context, &integer_type, this.parse::<BigInt>(), |value| {
+		Value::new_integer(context, value)
+	})
+}
+
+/**
+Parses an integer of a fixed-size integer type such as `u8`, which fails like in Rust if the number does not fit into
+the type. The result holds an `Integer`.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("(\"255\".parse::<u8>().unwrap(), \"-1\".parse::<i8>().unwrap())", &context).unwrap();
+assert_eq!(result.to_string(), "(255, -1)");
+let result = eval_str("\"300\".parse::<u8>().unwrap_err()", &context).unwrap();
+assert_eq!(result.as_string(), Some("number too large to fit in target type"));
+let result = eval_str("\"-1\".parse::<usize>().unwrap_err()", &context).unwrap();
+assert_eq!(result.as_string(), Some("invalid digit found in string"));
+```
+*/
+pub(crate) fn parse_sized_integer<T: std::str::FromStr + Into<BigInt>>(
+	context: &Context,
+	_this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError>
+where
+	T::Err: std::fmt::Display,
+{
+	arguments.check_len(1)?;
+	let this = arguments.as_string(0, context)?;
+	let integer_type = context.get_integer_type().map_err(|err| anyhow!(err))?;
+	new_parse_result(context, &integer_type, this.parse::<T>(), |value| {
+		Value::new_integer(context, value)
+	})
+}
+
+/**
+Parsing a string as a string always succeeds.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"abc\".parse::<std::string::String>().unwrap()", &context).unwrap();
+assert_eq!(result, eval_str("\"abc\"", &context).unwrap());
+```
+*/
+pub(crate) fn parse_string(
+	context: &Context,
+	_this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	arguments.check_len(1)?;
+	let this = arguments.as_string(0, context)?;
+	let string_type = context.get_string_type().map_err(|err| anyhow!(err))?;
+	new_parse_result(context, &string_type, this.parse::<String>(), |value| {
+		Value::new_string(context, value)
+	})
+}
+
 /**
 Repeat the given string `n` times.
 
@@ -199,6 +598,218 @@ pub(crate) fn repeat(context: &Context, _this_type: &TypeHandle, arguments: &[Va
 	Ok(Value::new_string(context, this.repeat(arg))?)
 }
 
+/**
+Replaces all matches of the pattern, which may be a `char` or a string, with another string.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"a-b-c\".replace('-', \"+\")", &context).unwrap();
+assert_eq!(result, eval_str("\"a+b+c\"", &context).unwrap());
+```
+*/
+pub(crate) fn replace(context: &Context, _this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(3)?;
+	let this = arguments.as_string(0, context)?;
+	let pattern = arguments.as_pattern(1, context)?;
+	let to = arguments.as_string(2, context)?;
+	Ok(Value::new_string(
+		context,
+		with_pattern!(pattern, |p| this.replace(p, to)),
+	)?)
+}
+
+/**
+Replaces the first `count` matches of the pattern, which may be a `char` or a string, with another string.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"a-b-c\".replacen(\"-\", \"+\", 1)", &context).unwrap();
+assert_eq!(result, eval_str("\"a+b-c\"", &context).unwrap());
+```
+*/
+pub(crate) fn replacen(context: &Context, _this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(4)?;
+	let this = arguments.as_string(0, context)?;
+	let pattern = arguments.as_pattern(1, context)?;
+	let to = arguments.as_string(2, context)?;
+	let count = arguments.as_integer(3, context)?.to_usize().ok_or(Overflow::USize)?;
+	Ok(Value::new_string(
+		context,
+		with_pattern!(pattern, |p| this.replacen(p, to, count)),
+	)?)
+}
+
+/**
+Finds the byte index of the last match of the pattern, which may be a `char` or a string.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"abcabc\".rfind('b').unwrap()", &context).unwrap();
+assert_eq!(result, eval_str("4", &context).unwrap());
+```
+*/
+pub(crate) fn rfind(context: &Context, _this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let this = arguments.as_string(0, context)?;
+	let pattern = arguments.as_pattern(1, context)?;
+	new_index_option(context, with_pattern!(pattern, |p| this.rfind(p)))
+}
+
+/**
+Splits the string at each match of the pattern, which may be a `char` or a string, starting from the end.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"a,b,c\".rsplit(',')", &context).unwrap();
+assert_eq!(result, eval_str("[\"c\", \"b\", \"a\"]", &context).unwrap());
+```
+*/
+pub(crate) fn rsplit(context: &Context, _this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let this = arguments.as_string(0, context)?;
+	let pattern = arguments.as_pattern(1, context)?;
+	with_pattern!(pattern, |p| new_string_array(context, this.rsplit(p)))
+}
+
+/**
+Splits the string at each match of the pattern, which may be a `char` or a string.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"a, b, c\".split(\", \")", &context).unwrap();
+assert_eq!(result, eval_str("[\"a\", \"b\", \"c\"]", &context).unwrap());
+```
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"\".split(',')", &context).unwrap();
+assert_eq!(result, eval_str("[\"\"]", &context).unwrap());
+```
+*/
+pub(crate) fn split(context: &Context, _this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let this = arguments.as_string(0, context)?;
+	let pattern = arguments.as_pattern(1, context)?;
+	with_pattern!(pattern, |p| new_string_array(context, this.split(p)))
+}
+
+/**
+Splits the string at whitespace, discarding any empty parts.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\" a  b\\tc \".split_whitespace()", &context).unwrap();
+assert_eq!(result, eval_str("[\"a\", \"b\", \"c\"]", &context).unwrap());
+```
+*/
+pub(crate) fn split_whitespace(
+	context: &Context,
+	_this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	arguments.check_len(1)?;
+	let this = arguments.as_string(0, context)?;
+	new_string_array(context, this.split_whitespace())
+}
+
+/**
+Splits the string at the first `count - 1` matches of the pattern, which may be a `char` or a string.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"a=b=c\".splitn(2, '=')", &context).unwrap();
+assert_eq!(result, eval_str("[\"a\", \"b=c\"]", &context).unwrap());
+```
+*/
+pub(crate) fn splitn(context: &Context, _this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	arguments.check_len(3)?;
+	let this = arguments.as_string(0, context)?;
+	let count = arguments.as_integer(1, context)?.to_usize().ok_or(Overflow::USize)?;
+	let pattern = arguments.as_pattern(2, context)?;
+	with_pattern!(pattern, |p| new_string_array(context, this.splitn(count, p)))
+}
+
+/**
+Checks if the string starts with the pattern, which may be a `char` or a string.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"abc\".starts_with(\"ab\")", &context).unwrap();
+assert_eq!(result, eval_str("true", &context).unwrap());
+```
+*/
+pub(crate) fn starts_with(
+	context: &Context,
+	_this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let this = arguments.as_string(0, context)?;
+	let pattern = arguments.as_pattern(1, context)?;
+	Ok(Value::new_bool(
+		context,
+		with_pattern!(pattern, |p| this.starts_with(p)),
+	)?)
+}
+
+/**
+Removes the prefix, which may be a `char` or a string, if the string starts with it.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"v1.2\".strip_prefix('v').unwrap()", &context).unwrap();
+assert_eq!(result, eval_str("\"1.2\"", &context).unwrap());
+```
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"1.2\".strip_prefix(\"v\").is_none()", &context).unwrap();
+assert_eq!(result, eval_str("true", &context).unwrap());
+```
+*/
+pub(crate) fn strip_prefix(
+	context: &Context,
+	_this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let this = arguments.as_string(0, context)?;
+	let pattern = arguments.as_pattern(1, context)?;
+	new_substring_option(context, with_pattern!(pattern, |p| this.strip_prefix(p)))
+}
+
+/**
+Removes the suffix, which may be a `char` or a string, if the string ends with it.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("\"file.rs\".strip_suffix(\".rs\").unwrap()", &context).unwrap();
+assert_eq!(result, eval_str("\"file\"", &context).unwrap());
+```
+*/
+pub(crate) fn strip_suffix(
+	context: &Context,
+	_this_type: &TypeHandle,
+	arguments: &[Value],
+) -> Result<Value, RuntimeError> {
+	arguments.check_len(2)?;
+	let this = arguments.as_string(0, context)?;
+	let pattern = arguments.as_pattern(1, context)?;
+	new_substring_option(context, with_pattern!(pattern, |p| this.strip_suffix(p)))
+}
+
 pub(crate) fn to_ascii_lowercase(
 	context: &Context,
 	_this_type: &TypeHandle,
@@ -273,3 +884,46 @@ pub(crate) fn trim_start(
 	let this = arguments.as_string(0, context)?;
 	Ok(Value::new_string(context, this.trim_start())?)
 }
+
+fn new_string_array<'a>(context: &Context, strings: impl Iterator<Item = &'a str>) -> Result<Value, RuntimeError> {
+	let array = strings
+		.map(|string| Value::new_string(context, string))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	Ok(Value::new_array(
+		context,
+		&context.get_string_type().map_err(|err| anyhow!(err))?,
+		array,
+	)?)
+}
+
+fn new_index_option(context: &Context, index: Option<usize>) -> Result<Value, RuntimeError> {
+	match index {
+		Some(index) => new_some(context, Value::new_integer(context, index)?),
+		None => new_none(context, &context.get_integer_type().map_err(|err| anyhow!(err))?),
+	}
+}
+
+fn new_substring_option(context: &Context, substring: Option<&str>) -> Result<Value, RuntimeError> {
+	match substring {
+		Some(substring) => new_some(context, Value::new_string(context, substring)?),
+		None => new_none(context, &context.get_string_type().map_err(|err| anyhow!(err))?),
+	}
+}
+
+/// Creates a `Result<T, String>` from the result of `str::parse`, where the error is converted to a string.
+fn new_parse_result<T, E: std::fmt::Display, C: Into<crate::error::ValueCreationError>>(
+	context: &Context,
+	ok_type: &TypeHandle,
+	result: Result<T, E>,
+	new_value: impl FnOnce(T) -> Result<Value, C>,
+) -> Result<Value, RuntimeError> {
+	match result {
+		Ok(value) => new_ok(
+			context,
+			new_value(value).map_err(|err| RuntimeError::ValueCreationError(err.into()))?,
+			&context.get_string_type().map_err(|err| anyhow!(err))?,
+		),
+		Err(err) => new_err(context, ok_type, Value::new_string(context, err.to_string())?),
+	}
+}
@@ -1,4 +1,7 @@
-use super::{MemberFunction, TypeBuilder, TypeKind};
+use super::{make_bool_name, make_char_name, make_integer_name, member_functions, TypeBuilder, TypeKind};
+
+#[macro_use]
+mod pattern;
 
 mod functions;
 mod ops;
@@ -16,22 +19,41 @@ pub fn new_string_type() -> TypeBuilder {
 		field_types: Default::default(),
 		variant_names: Default::default(),
 		variants: Default::default(),
-		functions: [
-			(crate::ops::ADD, ops::add as MemberFunction),
+		functions: member_functions(&[
+			(crate::ops::ADD, ops::add),
 			(crate::ops::EQUAL, ops::equal),
 			(crate::ops::NOT_EQUAL, ops::not_equal),
 			(crate::ops::LESS, ops::less),
 			(crate::ops::LESS_OR_EQUAL, ops::less_or_equal),
 			(crate::ops::GREATER, ops::greater),
 			(crate::ops::GREATER_OR_EQUAL, ops::greater_or_equal),
+			(crate::ops::DEBUG, functions::debug),
 			("as_bytes", functions::as_bytes),
+			("bytes", functions::bytes),
+			("char_indices", functions::char_indices),
+			("chars", functions::chars),
 			("clone", functions::clone),
+			("contains", functions::contains),
+			("ends_with", functions::ends_with),
 			("eq_ignore_ascii_case", functions::eq_ignore_ascii_case),
+			("find", functions::find),
+			("get", functions::get),
 			("is_ascii", functions::is_ascii),
 			("is_char_boundary", functions::is_char_boundary),
 			("is_empty", functions::is_empty),
 			("len", functions::len),
+			("lines", functions::lines),
 			("repeat", functions::repeat),
+			("replace", functions::replace),
+			("replacen", functions::replacen),
+			("rfind", functions::rfind),
+			("rsplit", functions::rsplit),
+			("split", functions::split),
+			("split_whitespace", functions::split_whitespace),
+			("splitn", functions::splitn),
+			("starts_with", functions::starts_with),
+			("strip_prefix", functions::strip_prefix),
+			("strip_suffix", functions::strip_suffix),
 			("to_ascii_lowercase", functions::to_ascii_lowercase),
 			("to_ascii_uppercase", functions::to_ascii_uppercase),
 			("to_lowercase", functions::to_lowercase),
@@ -40,9 +62,43 @@ pub fn new_string_type() -> TypeBuilder {
 			("trim", functions::trim),
 			("trim_end", functions::trim_end),
 			("trim_start", functions::trim_start),
-		]
-		.iter()
-		.map(|(name, function)| ((*name).to_owned(), *function))
+		])
+		.into_iter()
+		.chain(
+			member_functions(&[
+				(make_bool_name(), functions::parse_bool),
+				(make_char_name(), functions::parse_char),
+				(make_integer_name(), functions::parse_integer),
+				(make_string_name(), functions::parse_string),
+			])
+			.into_iter()
+			.map(|(type_name, function)| (format!("parse::<{}>", type_name), function)),
+		)
+		.chain(
+			// the fixed-size integer types of the prelude, which are checked against their range
+			member_functions(&[
+				("u8", functions::parse_sized_integer::<u8>),
+				("u16", functions::parse_sized_integer::<u16>),
+				("u32", functions::parse_sized_integer::<u32>),
+				("u64", functions::parse_sized_integer::<u64>),
+				("u128", functions::parse_sized_integer::<u128>),
+				("usize", functions::parse_sized_integer::<usize>),
+				("i8", functions::parse_sized_integer::<i8>),
+				("i16", functions::parse_sized_integer::<i16>),
+				("i32", functions::parse_sized_integer::<i32>),
+				("i64", functions::parse_sized_integer::<i64>),
+				("i128", functions::parse_sized_integer::<i128>),
+				("isize", functions::parse_sized_integer::<isize>),
+			])
+			.into_iter()
+			.map(|(type_name, function)| (format!("parse::<{}>", type_name), function)),
+		)
 		.collect(),
+		associated_functions: member_functions(&[
+			("default", functions::default),
+			("from", functions::from),
+			("new", functions::new),
+		]),
+		interfaces: super::scalar_interfaces(),
 	}
 }
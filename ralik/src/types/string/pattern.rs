@@ -0,0 +1,47 @@
+use crate::error::RuntimeError;
+use crate::{Context, Value};
+
+/// A pattern argument of a string function, which may be either a `char` or a string, as in Rust.
+pub(super) enum Pattern<'a> {
+	Char(char),
+	String(&'a str),
+}
+
+/// Evaluates `$body` with `$p` bound to the contents of the given `Pattern`, since `char` and `&str` are different
+/// types that both implement `std::str::pattern::Pattern`.
+macro_rules! with_pattern {
+	($pattern:expr, |$p:ident| $body:expr) => {
+		match $pattern {
+			super::pattern::Pattern::Char($p) => $body,
+			super::pattern::Pattern::String($p) => $body,
+		}
+	};
+}
+
+pub(super) trait PatternArgument {
+	fn as_pattern(&self, index: usize, context: &Context) -> Result<Pattern<'_>, RuntimeError>;
+}
+
+impl PatternArgument for [Value] {
+	fn as_pattern(&self, index: usize, context: &Context) -> Result<Pattern<'_>, RuntimeError> {
+		let value = &self[index];
+		let char_type = context
+			.get_char_type()
+			.map_err(|err| RuntimeError::InvalidCoreType(err.into()))?;
+		let string_type = context
+			.get_string_type()
+			.map_err(|err| RuntimeError::InvalidCoreType(err.into()))?;
+
+		if value.has_type(&char_type) {
+			Ok(Pattern::Char(value.as_char().unwrap()))
+		} else if value.has_type(&string_type) {
+			Ok(Pattern::String(value.as_string().unwrap()))
+		} else {
+			Err(RuntimeError::InvalidArgumentType {
+				argument_number: index,
+				actual_type_name: (*value.get_type().name()).into(),
+				expected_type_name: format!("{}` or `{}", char_type.name(), string_type.name()),
+			})
+		}
+	}
+}
@@ -20,5 +20,7 @@ pub fn new_struct_type(
 		variant_names: Default::default(),
 		variants: Default::default(),
 		functions: Default::default(),
+		associated_functions: Default::default(),
+		interfaces: Default::default(),
 	}
 }
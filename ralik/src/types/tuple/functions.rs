@@ -0,0 +1,123 @@
+use crate::error::RuntimeError;
+use crate::{Context, TypeHandle, Value};
+
+use super::super::arguments::Arguments;
+
+/**
+Clones a tuple by cloning each of its elements.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("(1, \"a\", ('b', true)).clone()", &context).unwrap();
+assert_eq!(result, eval_str("(1, \"a\", ('b', true))", &context).unwrap());
+```
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("().clone()", &context).unwrap();
+assert_eq!(result, eval_str("()", &context).unwrap());
+```
+*/
+pub(crate) fn clone(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	assert!(this_type.kind() == crate::types::TypeKind::Tuple);
+	arguments.check_len(1)?;
+	let this = arguments.check_type(0, this_type)?;
+	let elements = this.as_array().unwrap();
+	if elements.is_empty() {
+		return Ok(this.clone());
+	}
+
+	let values = elements
+		.iter()
+		.zip(this_type.type_parameter_ids().iter())
+		.map(|(element, element_type)| {
+			TypeHandle::from_type_id(context.clone(), *element_type).call_function(
+				context,
+				"clone",
+				std::slice::from_ref(element),
+			)
+		})
+		.collect::<Result<Vec<Value>, RuntimeError>>()?;
+	Ok(Value::new_tuple(context, values)?)
+}
+
+/**
+Converts a tuple to a string by converting each of its elements to a string.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("(1, \"a\", 'b').to_string()", &context).unwrap();
+assert_eq!(result, eval_str("\"(1, a, b)\"", &context).unwrap());
+```
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("(1,).to_string()", &context).unwrap();
+assert_eq!(result, eval_str("\"(1,)\"", &context).unwrap());
+```
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("().to_string()", &context).unwrap();
+assert_eq!(result, eval_str("\"()\"", &context).unwrap());
+```
+*/
+pub(crate) fn to_string(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	assert!(this_type.kind() == crate::types::TypeKind::Tuple);
+	arguments.check_len(1)?;
+	let elements = arguments.check_type(0, this_type)?.as_array().unwrap();
+
+	let mut result = "(".to_owned();
+	for (i, (element, element_type)) in elements.iter().zip(this_type.type_parameter_ids().iter()).enumerate() {
+		if i > 0 {
+			result.push_str(", ");
+		}
+		let element_type = TypeHandle::from_type_id(context.clone(), *element_type);
+		result.push_str(&element_type.call_string_function(context, "to_string", std::slice::from_ref(element))?);
+	}
+	if elements.len() == 1 {
+		result.push(',');
+	}
+	result.push(')');
+
+	Ok(Value::new_string(context, result)?)
+}
+
+pub(crate) fn debug(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	assert!(this_type.kind() == crate::types::TypeKind::Tuple);
+	arguments.check_len(2)?;
+	let elements = arguments.check_type(0, this_type)?.as_array().unwrap();
+	let alternate = arguments.as_bool(1, context)?;
+
+	let fields = elements
+		.iter()
+		.zip(this_type.type_parameter_ids().iter())
+		.map(|(element, element_type)| {
+			TypeHandle::from_type_id(context.clone(), *element_type).call_debug(context, element, alternate)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	Ok(Value::new_string(
+		context,
+		crate::types::debug_tuple("", &fields, alternate),
+	)?)
+}
+
+pub(crate) fn default(context: &Context, this_type: &TypeHandle, arguments: &[Value]) -> Result<Value, RuntimeError> {
+	assert!(this_type.kind() == crate::types::TypeKind::Tuple);
+	arguments.check_len(0)?;
+
+	let values = this_type
+		.type_parameter_ids()
+		.iter()
+		.map(|element_type| {
+			TypeHandle::from_type_id(context.clone(), *element_type).call_associated_function(context, "default", &[])
+		})
+		.collect::<Result<Vec<Value>, RuntimeError>>()?;
+	Ok(Value::new_tuple(context, values)?)
+}
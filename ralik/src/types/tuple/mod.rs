@@ -1,6 +1,9 @@
-use super::{GenericTypeBuilder, TypeKind};
+use super::{compare, member_functions, GenericTypeBuilder, TypeKind};
 use crate::context::Context;
 
+mod functions;
+mod ops;
+
 pub fn make_tuple_name(element_types: impl Iterator<Item = impl AsRef<str>>) -> String {
 	let mut name = "(".to_owned();
 	for (i, element_type_name) in element_types.enumerate() {
@@ -14,7 +17,50 @@ pub fn make_tuple_name(element_types: impl Iterator<Item = impl AsRef<str>>) ->
 	name.into()
 }
 
-pub fn tuple_generic(_context: &Context, element_types: &[&str]) -> Result<GenericTypeBuilder, anyhow::Error> {
+/**
+Creates tuple types (including the unit type `()`), which compare lexicographically and can be cloned and converted to
+strings as long as all their element types support the respective operations. A tuple type implements each of the core
+interfaces that all its element types implement.
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("(1, 'a') < (1, 'b')", &context).unwrap();
+assert_eq!(result, eval_str("true", &context).unwrap());
+```
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("(2, \"a\") <= (1, \"b\")", &context).unwrap();
+assert_eq!(result, eval_str("false", &context).unwrap());
+```
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("() == ()", &context).unwrap();
+assert_eq!(result, eval_str("true", &context).unwrap());
+```
+
+```rust
+# use ralik::eval_str;
+# let context = ralik::Context::new();
+let result = eval_str("[(2, 'a'), (1, 'b')].sort()", &context).unwrap();
+assert_eq!(result.to_string(), "[(1, 'b'), (2, 'a')]");
+assert!(eval_str("{ struct S; [(1, S)].sort() }", &context).is_err());
+```
+*/
+pub fn tuple_generic(context: &Context, element_types: &[&str]) -> Result<GenericTypeBuilder, anyhow::Error> {
+	// element types that are registered together with the tuple cannot be inspected yet and count as implementing nothing
+	let element_types_implement = |interface: &str| {
+		element_types.iter().all(|element_type| {
+			context
+				.get_type(element_type)
+				.is_some_and(|element_type| element_type.implements(interface))
+		})
+	};
+
 	Ok(GenericTypeBuilder {
 		kind: TypeKind::Tuple,
 		type_parameters: element_types
@@ -25,6 +71,25 @@ pub fn tuple_generic(_context: &Context, element_types: &[&str]) -> Result<Gener
 		field_types: Default::default(),
 		variant_names: Default::default(),
 		variants: Default::default(),
-		functions: Default::default(),
+		functions: member_functions(&[
+			(crate::ops::EQUAL, compare::equal::<ops::TupleElements>),
+			(crate::ops::NOT_EQUAL, compare::not_equal::<ops::TupleElements>),
+			(crate::ops::LESS, compare::less::<ops::TupleElements>),
+			(crate::ops::LESS_OR_EQUAL, compare::less_or_equal::<ops::TupleElements>),
+			(crate::ops::GREATER, compare::greater::<ops::TupleElements>),
+			(
+				crate::ops::GREATER_OR_EQUAL,
+				compare::greater_or_equal::<ops::TupleElements>,
+			),
+			(crate::ops::DEBUG, functions::debug),
+			("clone", functions::clone),
+			("to_string", functions::to_string),
+		]),
+		associated_functions: member_functions(&[("default", functions::default)]),
+		interfaces: ["Clone", "Debug", "Default", "Display", "Ord", "PartialEq", "PartialOrd"]
+			.iter()
+			.filter(|name| element_types_implement(name))
+			.map(|name| (*name).to_owned())
+			.collect(),
 	})
 }
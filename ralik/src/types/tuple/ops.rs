@@ -0,0 +1,36 @@
+use std::cmp::Ordering;
+
+use crate::error::RuntimeError;
+use crate::{Context, TypeHandle, Value};
+
+use super::super::arguments::Arguments;
+use super::super::compare::{ElementPairs, Elements};
+
+/// Tuples compare their elements in order, and are equal if all of them are.
+pub(super) struct TupleElements;
+
+impl Elements for TupleElements {
+	fn elements(
+		context: &Context,
+		this_type: &TypeHandle,
+		arguments: &[Value],
+	) -> Result<(ElementPairs, Ordering), RuntimeError> {
+		assert!(this_type.kind() == crate::types::TypeKind::Tuple);
+		arguments.check_len(2)?;
+		let lhs = arguments.check_type(0, this_type)?.as_array().unwrap();
+		let rhs = arguments.check_type(1, this_type)?.as_array().unwrap();
+
+		let elements = lhs
+			.iter()
+			.zip(rhs.iter())
+			.zip(this_type.type_parameter_ids().iter())
+			.map(|((lhs, rhs), element_type)| {
+				(
+					TypeHandle::from_type_id(context.clone(), *element_type),
+					[lhs.clone(), rhs.clone()],
+				)
+			})
+			.collect();
+		Ok((elements, Ordering::Equal))
+	}
+}
@@ -13,5 +13,7 @@ pub fn new_tuple_struct_type(name: impl Into<String>, field_types: impl Into<Vec
 		variant_names: Default::default(),
 		variants: Default::default(),
 		functions: Default::default(),
+		associated_functions: Default::default(),
+		interfaces: Default::default(),
 	}
 }
@@ -0,0 +1,415 @@
+use anyhow::anyhow;
+use num::{BigInt, ToPrimitive};
+
+use crate::error::{ConversionError, RuntimeError};
+use crate::types::TypeKind;
+use crate::{Context, TypeHandle, Value};
+
+/**
+Conversion of a `Value` into a Rust value, as performed for the arguments of functions registered via
+`Context::register_fn`.
+
+Functions can also take `&str` arguments, which borrow from the arguments instead of being converted, see
+`FromArgument`.
+
+```rust
+# use ralik::{eval_str, Context, FromValue};
+let context = Context::new();
+let value = eval_str(r#"(["a", "b"], "abc".find('c'), 42)"#, &context).unwrap();
+let (strings, index, x) = <(Vec<String>, Option<usize>, u8)>::from_value(&value).unwrap();
+assert_eq!((strings, index, x), (vec!["a".to_owned(), "b".to_owned()], Some(2), 42));
+assert!(u8::from_value(&eval_str("256", &context).unwrap()).is_err());
+```
+*/
+pub trait FromValue: Sized {
+	/// The name of the type that values are converted from, which is used in error messages.
+	fn type_name() -> String;
+
+	fn from_value(value: &Value) -> Result<Self, ConversionError>;
+}
+
+/**
+An argument of a function registered via `Context::register_fn`. Arguments are either converted via `FromValue` or, for
+`&str`, borrowed from the value that is passed to the function.
+
+```rust
+# use ralik::{eval_str, Context};
+let context = Context::new();
+context.register_fn("shout", |s: &str, times: usize| -> String { s.to_uppercase().repeat(times) });
+let value = eval_str(r#"shout("ab", 2)"#, &context).unwrap();
+assert_eq!(value.as_string(), Some("ABAB"));
+assert!(eval_str("shout('a', 2)", &context).is_err());
+```
+*/
+pub trait FromArgument {
+	/// The type that the function receives for an argument that is borrowed for `'a`.
+	type Argument<'a>;
+
+	fn from_argument(value: &Value) -> Result<Self::Argument<'_>, ConversionError>;
+}
+
+impl<T: FromValue> FromArgument for T {
+	type Argument<'a> = T;
+
+	fn from_argument(value: &Value) -> Result<T, ConversionError> {
+		T::from_value(value)
+	}
+}
+
+impl FromArgument for &str {
+	type Argument<'a> = &'a str;
+
+	fn from_argument(value: &Value) -> Result<&str, ConversionError> {
+		value.as_string().ok_or_else(|| type_mismatch::<String>(value))
+	}
+}
+
+/**
+Conversion of a Rust value into a `Value`, as performed for the results of functions registered via
+`Context::register_fn`.
+*/
+pub trait IntoValue {
+	/// Returns the type that values are converted to, which is needed to create values like empty arrays or `None`.
+	fn value_type(context: &Context) -> Result<TypeHandle, RuntimeError>;
+
+	fn into_value(self, context: &Context) -> Result<Value, RuntimeError>;
+}
+
+/**
+The result of a function registered via `Context::register_fn`, which is either a value that implements `IntoValue` or
+a `Result` that allows the function to fail.
+*/
+pub trait FunctionResult {
+	fn into_result(self, context: &Context) -> Result<Value, RuntimeError>;
+}
+
+impl<T: IntoValue> FunctionResult for T {
+	fn into_result(self, context: &Context) -> Result<Value, RuntimeError> {
+		self.into_value(context)
+	}
+}
+
+impl<T: IntoValue, E: Into<RuntimeError>> FunctionResult for Result<T, E> {
+	fn into_result(self, context: &Context) -> Result<Value, RuntimeError> {
+		self.map_err(Into::into)?.into_value(context)
+	}
+}
+
+fn type_mismatch<T: FromValue>(value: &Value) -> ConversionError {
+	ConversionError::type_mismatch(value, T::type_name())
+}
+
+/// Reports a type mismatch in an element of `value` as a mismatch of the whole value, which is more helpful when the
+/// element type is part of a larger type.
+fn element_error<T: FromValue>(error: ConversionError, value: &Value) -> ConversionError {
+	match error {
+		ConversionError::TypeMismatch { .. } => type_mismatch::<T>(value),
+		error => error,
+	}
+}
+
+/// `Value` converts to and from itself, so its type is only known at runtime.
+impl FromValue for Value {
+	fn type_name() -> String {
+		"_".into()
+	}
+
+	fn from_value(value: &Value) -> Result<Self, ConversionError> {
+		Ok(value.clone())
+	}
+}
+
+impl IntoValue for Value {
+	fn value_type(_context: &Context) -> Result<TypeHandle, RuntimeError> {
+		Err(anyhow!("The type of a `Value` is only known at runtime").into())
+	}
+
+	fn into_value(self, _context: &Context) -> Result<Value, RuntimeError> {
+		Ok(self)
+	}
+}
+
+impl FromValue for bool {
+	fn type_name() -> String {
+		crate::types::make_bool_name().into()
+	}
+
+	fn from_value(value: &Value) -> Result<Self, ConversionError> {
+		value.as_bool().ok_or_else(|| type_mismatch::<Self>(value))
+	}
+}
+
+impl IntoValue for bool {
+	fn value_type(context: &Context) -> Result<TypeHandle, RuntimeError> {
+		context
+			.get_bool_type()
+			.map_err(|err| RuntimeError::InvalidCoreType(err.into()))
+	}
+
+	fn into_value(self, context: &Context) -> Result<Value, RuntimeError> {
+		Ok(Value::new_bool(context, self)?)
+	}
+}
+
+impl FromValue for char {
+	fn type_name() -> String {
+		crate::types::make_char_name().into()
+	}
+
+	fn from_value(value: &Value) -> Result<Self, ConversionError> {
+		value.as_char().ok_or_else(|| type_mismatch::<Self>(value))
+	}
+}
+
+impl IntoValue for char {
+	fn value_type(context: &Context) -> Result<TypeHandle, RuntimeError> {
+		context
+			.get_char_type()
+			.map_err(|err| RuntimeError::InvalidCoreType(err.into()))
+	}
+
+	fn into_value(self, context: &Context) -> Result<Value, RuntimeError> {
+		Ok(Value::new_char(context, self)?)
+	}
+}
+
+impl FromValue for BigInt {
+	fn type_name() -> String {
+		crate::types::make_integer_name().into()
+	}
+
+	fn from_value(value: &Value) -> Result<Self, ConversionError> {
+		value.as_integer().cloned().ok_or_else(|| type_mismatch::<Self>(value))
+	}
+}
+
+impl IntoValue for BigInt {
+	fn value_type(context: &Context) -> Result<TypeHandle, RuntimeError> {
+		context
+			.get_integer_type()
+			.map_err(|err| RuntimeError::InvalidCoreType(err.into()))
+	}
+
+	fn into_value(self, context: &Context) -> Result<Value, RuntimeError> {
+		Ok(Value::new_integer(context, self)?)
+	}
+}
+
+macro_rules! impl_integer {
+	($($integer:ident => $to_integer:ident),* $(,)?) => {
+		$(
+			impl FromValue for $integer {
+				fn type_name() -> String {
+					crate::types::make_integer_name().into()
+				}
+
+				fn from_value(value: &Value) -> Result<Self, ConversionError> {
+					let integer = value.as_integer().ok_or_else(|| type_mismatch::<Self>(value))?;
+					integer.$to_integer().ok_or_else(|| ConversionError::OutOfRange {
+						value: integer.to_string(),
+						target_type_name: stringify!($integer),
+					})
+				}
+			}
+
+			impl IntoValue for $integer {
+				fn value_type(context: &Context) -> Result<TypeHandle, RuntimeError> {
+					BigInt::value_type(context)
+				}
+
+				fn into_value(self, context: &Context) -> Result<Value, RuntimeError> {
+					Ok(Value::new_integer(context, self)?)
+				}
+			}
+		)*
+	};
+}
+
+impl_integer!(
+	i8 => to_i8,
+	u8 => to_u8,
+	i16 => to_i16,
+	u16 => to_u16,
+	i32 => to_i32,
+	u32 => to_u32,
+	i64 => to_i64,
+	u64 => to_u64,
+	i128 => to_i128,
+	u128 => to_u128,
+	isize => to_isize,
+	usize => to_usize,
+);
+
+impl FromValue for String {
+	fn type_name() -> String {
+		crate::types::make_string_name().into()
+	}
+
+	fn from_value(value: &Value) -> Result<Self, ConversionError> {
+		value
+			.as_string()
+			.map(ToOwned::to_owned)
+			.ok_or_else(|| type_mismatch::<Self>(value))
+	}
+}
+
+impl IntoValue for String {
+	fn value_type(context: &Context) -> Result<TypeHandle, RuntimeError> {
+		context
+			.get_string_type()
+			.map_err(|err| RuntimeError::InvalidCoreType(err.into()))
+	}
+
+	fn into_value(self, context: &Context) -> Result<Value, RuntimeError> {
+		Ok(Value::new_string(context, self)?)
+	}
+}
+
+impl IntoValue for &str {
+	fn value_type(context: &Context) -> Result<TypeHandle, RuntimeError> {
+		String::value_type(context)
+	}
+
+	fn into_value(self, context: &Context) -> Result<Value, RuntimeError> {
+		Ok(Value::new_string(context, self)?)
+	}
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+	fn type_name() -> String {
+		crate::types::make_option_name(&T::type_name())
+	}
+
+	fn from_value(value: &Value) -> Result<Self, ConversionError> {
+		if !crate::types::is_option_type(value.get_type()) {
+			return Err(type_mismatch::<Self>(value));
+		}
+		match value.as_array() {
+			Some([]) => Ok(None),
+			Some([element]) => Ok(Some(
+				T::from_value(element).map_err(|err| element_error::<Self>(err, value))?,
+			)),
+			_ => Err(type_mismatch::<Self>(value)),
+		}
+	}
+}
+
+impl<T: IntoValue> IntoValue for Option<T> {
+	fn value_type(context: &Context) -> Result<TypeHandle, RuntimeError> {
+		context
+			.get_option_type(&T::value_type(context)?.name())
+			.map_err(|err| RuntimeError::InvalidCoreType(err.into()))
+	}
+
+	fn into_value(self, context: &Context) -> Result<Value, RuntimeError> {
+		match self {
+			Some(value) => crate::types::new_some(context, value.into_value(context)?),
+			None => crate::types::new_none(context, &T::value_type(context)?),
+		}
+	}
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+	fn type_name() -> String {
+		crate::types::make_array_name(&T::type_name())
+	}
+
+	fn from_value(value: &Value) -> Result<Self, ConversionError> {
+		if value.get_type().kind() != TypeKind::Array {
+			return Err(type_mismatch::<Self>(value));
+		}
+		let mut result = Vec::new();
+		for element in value.as_array().unwrap() {
+			result.push(T::from_value(element).map_err(|err| element_error::<Self>(err, value))?);
+		}
+		Ok(result)
+	}
+}
+
+impl<T: IntoValue> IntoValue for Vec<T> {
+	fn value_type(context: &Context) -> Result<TypeHandle, RuntimeError> {
+		context
+			.get_array_type(&T::value_type(context)?.name())
+			.map_err(|err| RuntimeError::InvalidCoreType(err.into()))
+	}
+
+	fn into_value(self, context: &Context) -> Result<Value, RuntimeError> {
+		let element_type = T::value_type(context)?;
+		let mut values = Vec::with_capacity(self.len());
+		for element in self {
+			values.push(element.into_value(context)?);
+		}
+		Ok(Value::new_array(context, &element_type, values)?)
+	}
+}
+
+impl FromValue for () {
+	fn type_name() -> String {
+		crate::types::make_tuple_name(std::iter::empty::<&str>())
+	}
+
+	fn from_value(value: &Value) -> Result<Self, ConversionError> {
+		match value.get_type().kind() {
+			TypeKind::Tuple if value.as_array() == Some(&[]) => Ok(()),
+			_ => Err(type_mismatch::<Self>(value)),
+		}
+	}
+}
+
+impl IntoValue for () {
+	fn value_type(context: &Context) -> Result<TypeHandle, RuntimeError> {
+		context
+			.get_unit_type()
+			.map_err(|err| RuntimeError::InvalidCoreType(err.into()))
+	}
+
+	fn into_value(self, context: &Context) -> Result<Value, RuntimeError> {
+		Ok(Value::new_unit(context)?)
+	}
+}
+
+macro_rules! impl_tuple {
+	($len:literal: $($element:ident $index:tt),*) => {
+		impl<$($element: FromValue),*> FromValue for ($($element,)*) {
+			fn type_name() -> String {
+				let element_type_names: [String; $len] = [$($element::type_name()),*];
+				crate::types::make_tuple_name(element_type_names.iter())
+			}
+
+			fn from_value(value: &Value) -> Result<Self, ConversionError> {
+				if value.get_type().kind() != TypeKind::Tuple {
+					return Err(type_mismatch::<Self>(value));
+				}
+				match value.as_array() {
+					Some(elements) if elements.len() == $len => Ok((
+						$($element::from_value(&elements[$index]).map_err(|err| element_error::<Self>(err, value))?,)*
+					)),
+					_ => Err(type_mismatch::<Self>(value)),
+				}
+			}
+		}
+
+		impl<$($element: IntoValue),*> IntoValue for ($($element,)*) {
+			fn value_type(context: &Context) -> Result<TypeHandle, RuntimeError> {
+				let element_types: Vec<String> = vec![$((*$element::value_type(context)?.name()).into()),*];
+				context
+					.get_tuple_type(element_types)
+					.map_err(|err| RuntimeError::InvalidCoreType(err.into()))
+			}
+
+			fn into_value(self, context: &Context) -> Result<Value, RuntimeError> {
+				let values: Vec<Value> = vec![$(self.$index.into_value(context)?),*];
+				Ok(Value::new_tuple(context, values)?)
+			}
+		}
+	};
+}
+
+impl_tuple!(1: A 0);
+impl_tuple!(2: A 0, B 1);
+impl_tuple!(3: A 0, B 1, C 2);
+impl_tuple!(4: A 0, B 1, C 2, D 3);
+impl_tuple!(5: A 0, B 1, C 2, D 3, E 4);
+impl_tuple!(6: A 0, B 1, C 2, D 3, E 4, F 5);
+impl_tuple!(7: A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_tuple!(8: A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
@@ -1,10 +1,94 @@
 use num::BigInt;
 
+use std::collections::HashMap;
 use std::fmt;
+use std::sync::Arc;
 
-use super::Data;
+use crate::types::{TypeKind, Variant};
+
+use super::{Data, Value};
 use Data::*;
 
+/**
+Formats the value similar to how `#[derive(Debug)]` would format the corresponding Rust value, e.g., `Some((1, 'x'))`.
+
+Passing the `+` flag (as in `{:+?}` or `{:+#?}`) instead dumps the internal representation of the value.
+
+This is also what `{:?}` uses in format strings for types that do not provide `[op]::Debug`.
+
+```rust
+# use ralik::eval_str;
+let context = ralik::Context::new();
+let value = eval_str(r#"(1, "two", ['3'])"#, &context).unwrap();
+assert_eq!(format!("{:?}", value), r#"(1, "two", ['3'])"#);
+let formatted = eval_str(r#"format!("{:?}", (1, "two", ['3']))"#, &context).unwrap();
+assert_eq!(formatted.as_string(), Some(r#"(1, "two", ['3'])"#));
+```
+*/
+impl fmt::Debug for Value {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if f.sign_plus() {
+			return f
+				.debug_struct("Value")
+				.field("type", &self.r#type)
+				.field("data", &self.data)
+				.finish();
+		}
+
+		match (self.r#type.kind(), &self.data) {
+			(TypeKind::Bool, Bool(value)) => fmt::Debug::fmt(value, f),
+			(TypeKind::Integer, Integer(value)) => fmt::Display::fmt(value, f),
+			(TypeKind::Char, Char(value)) => fmt::Debug::fmt(value, f),
+			(TypeKind::String, String(value)) => fmt::Debug::fmt(value, f),
+			(TypeKind::Tuple, Empty) => f.write_str("()"),
+			(TypeKind::Tuple, Array(value)) => fields_tuple(f, "", value),
+			(TypeKind::Array, Empty) => f.write_str("[]"),
+			(TypeKind::Array, Array(value)) => f.debug_list().entries(value.iter()).finish(),
+			(TypeKind::UnitStruct, Empty) => f.write_str(&self.r#type.name()),
+			(TypeKind::TupleStruct, Empty) => fields_tuple(f, &self.r#type.name(), &[]),
+			(TypeKind::TupleStruct, Array(value)) => fields_tuple(f, &self.r#type.name(), value),
+			(TypeKind::Struct, Empty) => fields_struct(f, &self.r#type.name(), &HashMap::new(), &[]),
+			(TypeKind::Struct, Array(value)) => fields_struct(f, &self.r#type.name(), &self.r#type.fields().0, value),
+			(TypeKind::Enum, UnitVariant(id)) => variant(f, &self.r#type.variant_table().1[*id], &[]),
+			(TypeKind::Enum, Variant(id, value)) => variant(f, &self.r#type.variant_table().1[*id], value),
+			(TypeKind::Opaque(_), Opaque(_value)) => f.debug_struct(&self.r#type.name()).finish_non_exhaustive(),
+			_ => Err(fmt::Error),
+		}
+	}
+}
+
+fn variant(f: &mut fmt::Formatter, variant: &Variant, values: &[Value]) -> fmt::Result {
+	match variant {
+		Variant::Unit(name) => f.write_str(name),
+		Variant::Tuple(name, _field_types) => fields_tuple(f, name, values),
+		Variant::Struct(name, field_names, _field_types) => fields_struct(f, name, field_names, values),
+	}
+}
+
+fn fields_tuple(f: &mut fmt::Formatter, name: &str, values: &[Value]) -> fmt::Result {
+	let mut builder = f.debug_tuple(name);
+	for value in values {
+		builder.field(value);
+	}
+	builder.finish()
+}
+
+fn fields_struct(
+	f: &mut fmt::Formatter,
+	name: &str,
+	field_names: &HashMap<Box<str>, usize>,
+	values: &[Value],
+) -> fmt::Result {
+	let mut field_names = field_names.iter().collect::<Vec<_>>();
+	field_names.sort_by_key(|(_name, id)| **id);
+
+	let mut builder = f.debug_struct(name);
+	for (field_name, id) in field_names {
+		builder.field(field_name, &values[*id]);
+	}
+	builder.finish()
+}
+
 impl fmt::Debug for Data {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		match self {
@@ -16,6 +100,7 @@ impl fmt::Debug for Data {
 			UnitVariant(id) => f.debug_tuple("UnitVariant").field(id).finish(),
 			Variant(id, value) => f.debug_tuple("Variant").field(id).field(&value).finish(),
 			Array(value) => f.debug_tuple("Array").field(&value).finish(),
+			Opaque(value) => f.debug_tuple("Opaque").field(&Arc::as_ptr(value)).finish(),
 		}
 	}
 }
@@ -0,0 +1,132 @@
+use my_serde::de::{self, IntoDeserializer};
+
+use crate::types::Variant;
+use crate::{TypeHandle, Value};
+
+use super::{Deserializer, DeserializerError, MapAccess, SeqAccess};
+
+/// The contents of an enum variant, with the fields of struct variants in the order of their declaration.
+pub(super) enum VariantData<'de> {
+	Unit,
+	Tuple(&'de [Value]),
+	Struct(Vec<(String, &'de Value)>),
+}
+
+impl<'de> VariantData<'de> {
+	/// Returns the name and contents of the variant with the index `id` of the enum type `r#type`.
+	pub fn new(id: usize, r#type: &TypeHandle, values: &'de [Value]) -> (String, Self) {
+		match &r#type.variant_table().1[id] {
+			Variant::Unit(name) => ((**name).into(), VariantData::Unit),
+			Variant::Tuple(name, _field_types) => ((**name).into(), VariantData::Tuple(values)),
+			Variant::Struct(name, field_names, _field_types) => {
+				let mut field_names = field_names.iter().collect::<Vec<_>>();
+				field_names.sort_by_key(|(_name, id)| **id);
+				let fields = field_names
+					.into_iter()
+					.map(|(field_name, id)| ((**field_name).into(), &values[*id]))
+					.collect();
+				((**name).into(), VariantData::Struct(fields))
+			}
+		}
+	}
+
+	pub fn deserialize_any<V: de::Visitor<'de>>(self, path: String, visitor: V) -> Result<V::Value, DeserializerError> {
+		match self {
+			VariantData::Unit => visitor.visit_unit(),
+			VariantData::Tuple(values) if values.len() == 1 => {
+				de::Deserializer::deserialize_any(Deserializer::nested(&values[0], format!("{}.0", path)), visitor)
+			}
+			VariantData::Tuple(values) => visitor.visit_seq(SeqAccess::tuple(values, &path)),
+			VariantData::Struct(fields) => visitor.visit_map(MapAccess::fields(fields, &path)),
+		}
+	}
+}
+
+pub(super) struct EnumAccess<'de> {
+	name: String,
+	data: VariantData<'de>,
+	path: String,
+}
+
+impl<'de> EnumAccess<'de> {
+	pub fn new(id: usize, r#type: &TypeHandle, values: &'de [Value], path: &str) -> Self {
+		let (name, data) = VariantData::new(id, r#type, values);
+		Self {
+			name,
+			data,
+			path: path.into(),
+		}
+	}
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess<'de> {
+	type Error = DeserializerError;
+	type Variant = VariantAccess<'de>;
+
+	fn variant_seed<V: de::DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error> {
+		let variant = seed.deserialize(self.name.into_deserializer())?;
+		Ok((
+			variant,
+			VariantAccess {
+				data: self.data,
+				path: self.path,
+			},
+		))
+	}
+}
+
+pub(super) struct VariantAccess<'de> {
+	data: VariantData<'de>,
+	path: String,
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess<'de> {
+	type Error = DeserializerError;
+
+	fn unit_variant(self) -> Result<(), Self::Error> {
+		match self.data {
+			VariantData::Unit => Ok(()),
+			VariantData::Tuple([]) => Ok(()),
+			_ => Err(de::Error::invalid_type(
+				de::Unexpected::Other("non-unit variant"),
+				&"unit variant",
+			)),
+		}
+	}
+
+	fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Self::Error> {
+		match self.data {
+			VariantData::Tuple(values) if values.len() == 1 => {
+				seed.deserialize(Deserializer::nested(&values[0], format!("{}.0", self.path)))
+			}
+			_ => Err(de::Error::invalid_type(
+				de::Unexpected::Other("non-newtype variant"),
+				&"newtype variant",
+			)),
+		}
+	}
+
+	fn tuple_variant<V: de::Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error> {
+		match self.data {
+			VariantData::Tuple(values) => visitor.visit_seq(SeqAccess::tuple(values, &self.path)),
+			_ => Err(de::Error::invalid_type(
+				de::Unexpected::Other("non-tuple variant"),
+				&"tuple variant",
+			)),
+		}
+	}
+
+	fn struct_variant<V: de::Visitor<'de>>(
+		self,
+		_fields: &'static [&'static str],
+		visitor: V,
+	) -> Result<V::Value, Self::Error> {
+		match self.data {
+			VariantData::Struct(fields) => visitor.visit_map(MapAccess::fields(fields, &self.path)),
+			_ => Err(de::Error::invalid_type(
+				de::Unexpected::Other("non-struct variant"),
+				&"struct variant",
+			)),
+		}
+	}
+}
@@ -0,0 +1,86 @@
+use my_serde::de::{self, IntoDeserializer};
+
+use crate::Value;
+
+use super::enums::VariantData;
+use super::{Deserializer, DeserializerError};
+
+enum Content<'de> {
+	Value(&'de Value),
+	Variant(VariantData<'de>),
+}
+
+/// Accesses the fields of structs and struct variants, as well as other enum variants as a map with a single entry.
+pub(super) struct MapAccess<'de> {
+	entries: std::vec::IntoIter<(String, Content<'de>, String)>,
+	value: Option<(Content<'de>, String)>,
+}
+
+impl<'de> MapAccess<'de> {
+	pub fn fields(fields: Vec<(String, &'de Value)>, path: &str) -> Self {
+		let entries = fields
+			.into_iter()
+			.map(|(name, value)| {
+				let path = format!("{}.{}", path, name);
+				(name, Content::Value(value), path)
+			})
+			.collect::<Vec<_>>();
+		Self {
+			entries: entries.into_iter(),
+			value: None,
+		}
+	}
+
+	pub fn variant(name: String, data: VariantData<'de>, path: &str) -> Self {
+		Self {
+			entries: vec![(name, Content::Variant(data), path.into())].into_iter(),
+			value: None,
+		}
+	}
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess<'de> {
+	type Error = DeserializerError;
+
+	fn next_key_seed<K: de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error> {
+		match self.entries.next() {
+			Some((key, content, path)) => {
+				self.value = Some((content, path));
+				seed.deserialize(key.into_deserializer()).map(Some)
+			}
+			None => Ok(None),
+		}
+	}
+
+	fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Self::Error> {
+		match self.value.take() {
+			Some((Content::Value(value), path)) => seed.deserialize(Deserializer::nested(value, path)),
+			Some((Content::Variant(data), path)) => seed.deserialize(VariantDeserializer { data, path }),
+			None => Err(de::Error::custom("value is missing")),
+		}
+	}
+
+	fn size_hint(&self) -> Option<usize> {
+		Some(self.entries.len())
+	}
+}
+
+/// Deserializes the contents of an enum variant that is accessed as a map.
+struct VariantDeserializer<'de> {
+	data: VariantData<'de>,
+	path: String,
+}
+
+impl<'de> de::Deserializer<'de> for VariantDeserializer<'de> {
+	type Error = DeserializerError;
+
+	fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.data.deserialize_any(self.path, visitor)
+	}
+
+	my_serde::forward_to_deserialize_any! {
+		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+		bytes byte_buf option unit unit_struct newtype_struct seq tuple
+		tuple_struct map struct enum identifier ignored_any
+	}
+}
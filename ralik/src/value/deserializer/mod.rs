@@ -0,0 +1,260 @@
+use my_serde::de::{self, IntoDeserializer};
+use num::{BigInt, ToPrimitive};
+use thiserror::Error;
+
+use crate::types::TypeKind;
+use crate::Value;
+
+use super::Data;
+
+mod enums;
+use enums::{EnumAccess, VariantData};
+
+mod maps;
+use maps::MapAccess;
+
+mod sequences;
+use sequences::SeqAccess;
+
+impl Value {
+	/// Converts the value into a Rust type via serde. This is the inverse of `Value::from_serde` and mirrors the
+	/// representation used by `Value`'s `serde::Serialize` implementation, e.g., structs are read as maps and enums as
+	/// externally tagged variants. Errors record the path into the value at which deserialization failed.
+	///
+	/// ```rust
+	/// # use ralik::eval_str;
+	/// use std::ops::Range;
+	///
+	/// let context = ralik::Context::new();
+	/// let value = eval_str(r#"[("a", 1..3)]"#, &context).unwrap();
+	/// let ranges = value.into_serde::<Vec<(String, Range<u16>)>>().unwrap();
+	/// assert_eq!(ranges, vec![("a".into(), 1..3)]);
+	///
+	/// let value = eval_str(r#"[("a", 1..3), ("b", 1..300)]"#, &context).unwrap();
+	/// let error = value.into_serde::<Vec<(String, Range<u8>)>>().unwrap_err();
+	/// assert_eq!(error.path(), "[1].1.end");
+	/// assert_eq!(error.type_name(), "Integer");
+	/// ```
+	pub fn into_serde<T: de::DeserializeOwned>(self) -> Result<T, DeserializerError> {
+		T::deserialize(Deserializer::new(&self))
+	}
+}
+
+/// Allows deserializing from a borrowed value, e.g., to deserialize types that borrow strings from it.
+impl<'de> IntoDeserializer<'de, DeserializerError> for &'de Value {
+	type Deserializer = Deserializer<'de>;
+
+	fn into_deserializer(self) -> Self::Deserializer {
+		Deserializer::new(self)
+	}
+}
+
+/// The error of `Value::into_serde`, which records where in the value the deserialization failed.
+#[derive(Error, Debug)]
+#[error("Could not deserialize the value at `{}` of type `{}`: {message}", display_path(.path), .type_name.as_deref().unwrap_or("_"))]
+pub struct DeserializerError {
+	path: Option<String>,
+	type_name: Option<String>,
+	message: String,
+}
+
+fn display_path(path: &Option<String>) -> &str {
+	match path.as_deref() {
+		None | Some("") => ".",
+		Some(path) => path,
+	}
+}
+
+impl DeserializerError {
+	/// The path to the value that could not be deserialized, e.g., `.servers[2].port`. The path of the value on which
+	/// `Value::into_serde` was called is empty.
+	pub fn path(&self) -> &str {
+		self.path.as_deref().unwrap_or("")
+	}
+
+	/// The name of the ralik type of the value that could not be deserialized.
+	pub fn type_name(&self) -> &str {
+		self.type_name.as_deref().unwrap_or("")
+	}
+
+	pub fn message(&self) -> &str {
+		&self.message
+	}
+
+	/// Records the value at which the error occurred, unless a more deeply nested value was already recorded.
+	fn at(mut self, path: &str, value: &Value) -> Self {
+		if self.path.is_none() {
+			self.path = Some(path.into());
+			self.type_name = Some((*value.get_type().name()).into());
+		}
+		self
+	}
+}
+
+impl de::Error for DeserializerError {
+	fn custom<T: std::fmt::Display>(msg: T) -> Self {
+		DeserializerError {
+			path: None,
+			type_name: None,
+			message: msg.to_string(),
+		}
+	}
+}
+
+/// A serde deserializer that reads from a `Value`.
+pub struct Deserializer<'de> {
+	value: &'de Value,
+	path: String,
+}
+
+impl<'de> Deserializer<'de> {
+	pub fn new(value: &'de Value) -> Self {
+		Self {
+			value,
+			path: String::new(),
+		}
+	}
+
+	fn nested(value: &'de Value, path: String) -> Self {
+		Self { value, path }
+	}
+
+	fn invalid_type(&self, expected: &str) -> DeserializerError {
+		de::Error::custom(format_args!("invalid type: expected {}", expected))
+	}
+
+	fn visit_integer<V: de::Visitor<'de>>(value: &BigInt, visitor: V) -> Result<V::Value, DeserializerError> {
+		if let Some(value) = value.to_u64() {
+			visitor.visit_u64(value)
+		} else if let Some(value) = value.to_i64() {
+			visitor.visit_i64(value)
+		} else if let Some(value) = value.to_u128() {
+			visitor.visit_u128(value)
+		} else if let Some(value) = value.to_i128() {
+			visitor.visit_i128(value)
+		} else {
+			visitor.visit_string(value.to_string())
+		}
+	}
+
+	fn deserialize_any_impl<V: de::Visitor<'de>>(&self, visitor: V) -> Result<V::Value, DeserializerError> {
+		let r#type = self.value.get_type();
+		match (r#type.kind(), &self.value.data) {
+			(TypeKind::Bool, Data::Bool(value)) => visitor.visit_bool(*value),
+			(TypeKind::Integer, Data::Integer(value)) => Self::visit_integer(value, visitor),
+			(TypeKind::Char, Data::Char(value)) => visitor.visit_char(*value),
+			(TypeKind::String, Data::String(value)) => visitor.visit_borrowed_str(value),
+			(TypeKind::Tuple, Data::Empty) => visitor.visit_unit(),
+			(TypeKind::Tuple, Data::Array(values)) => visitor.visit_seq(SeqAccess::tuple(values, &self.path)),
+			(TypeKind::Array, Data::Empty) => visitor.visit_seq(SeqAccess::array(&[], &self.path)),
+			(TypeKind::Array, Data::Array(values)) => visitor.visit_seq(SeqAccess::array(values, &self.path)),
+			(TypeKind::UnitStruct, Data::Empty) => visitor.visit_unit(),
+			(TypeKind::TupleStruct, Data::Empty) => visitor.visit_seq(SeqAccess::tuple(&[], &self.path)),
+			(TypeKind::TupleStruct, Data::Array(values)) if values.len() == 1 => {
+				Deserializer::nested(&values[0], format!("{}.0", self.path)).deserialize_any_impl(visitor)
+			}
+			(TypeKind::TupleStruct, Data::Array(values)) => visitor.visit_seq(SeqAccess::tuple(values, &self.path)),
+			(TypeKind::Struct, Data::Empty) => visitor.visit_map(MapAccess::fields(Vec::new(), &self.path)),
+			(TypeKind::Struct, Data::Array(values)) => {
+				let mut field_names = r#type
+					.fields()
+					.0
+					.iter()
+					.map(|(name, id)| ((**name).to_owned(), *id))
+					.collect::<Vec<_>>();
+				field_names.sort_by_key(|(_name, id)| *id);
+				let fields = field_names.into_iter().map(|(name, id)| (name, &values[id])).collect();
+				visitor.visit_map(MapAccess::fields(fields, &self.path))
+			}
+			(TypeKind::Enum, Data::UnitVariant(_id)) if crate::types::is_option_type(r#type) => visitor.visit_none(),
+			(TypeKind::Enum, Data::Variant(_id, values)) if crate::types::is_option_type(r#type) => {
+				visitor.visit_some(Deserializer::nested(&values[0], self.path.clone()))
+			}
+			(TypeKind::Enum, Data::UnitVariant(id)) => match VariantData::new(*id, r#type, &[]) {
+				(name, VariantData::Unit) => visitor.visit_string(name),
+				(name, data) => visitor.visit_map(MapAccess::variant(name, data, &self.path)),
+			},
+			(TypeKind::Enum, Data::Variant(id, values)) => {
+				let (name, data) = VariantData::new(*id, r#type, values);
+				visitor.visit_map(MapAccess::variant(name, data, &self.path))
+			}
+			(TypeKind::Opaque(_), Data::Opaque(_value)) => Err(de::Error::custom("opaque values cannot be deserialized")),
+			(kind, data) => panic!("Invalid {:?} representation {:?}", kind, data),
+		}
+	}
+
+	fn deserialize_option_impl<V: de::Visitor<'de>>(&self, visitor: V) -> Result<V::Value, DeserializerError> {
+		match &self.value.data {
+			_ if !crate::types::is_option_type(self.value.get_type()) => {
+				visitor.visit_some(Deserializer::nested(self.value, self.path.clone()))
+			}
+			Data::Variant(_id, values) => visitor.visit_some(Deserializer::nested(&values[0], self.path.clone())),
+			_ => visitor.visit_none(),
+		}
+	}
+
+	fn deserialize_enum_impl<V: de::Visitor<'de>>(&self, visitor: V) -> Result<V::Value, DeserializerError> {
+		let r#type = self.value.get_type();
+		match (r#type.kind(), &self.value.data) {
+			(TypeKind::Enum, Data::UnitVariant(id)) => visitor.visit_enum(EnumAccess::new(*id, r#type, &[], &self.path)),
+			(TypeKind::Enum, Data::Variant(id, values)) => {
+				visitor.visit_enum(EnumAccess::new(*id, r#type, values, &self.path))
+			}
+			// unit variants may also be given by their name
+			(TypeKind::String, Data::String(value)) => visitor.visit_enum(value.as_ref().into_deserializer()),
+			_ => Err(self.invalid_type("an enum")),
+		}
+	}
+
+	fn deserialize_newtype_struct_impl<V: de::Visitor<'de>>(&self, visitor: V) -> Result<V::Value, DeserializerError> {
+		match (self.value.get_type().kind(), &self.value.data) {
+			(TypeKind::TupleStruct, Data::Array(values)) if values.len() == 1 => {
+				visitor.visit_newtype_struct(Deserializer::nested(&values[0], format!("{}.0", self.path)))
+			}
+			_ => visitor.visit_newtype_struct(Deserializer::nested(self.value, self.path.clone())),
+		}
+	}
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'de> {
+	type Error = DeserializerError;
+
+	fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self
+			.deserialize_any_impl(visitor)
+			.map_err(|err| err.at(&self.path, self.value))
+	}
+
+	fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self
+			.deserialize_option_impl(visitor)
+			.map_err(|err| err.at(&self.path, self.value))
+	}
+
+	fn deserialize_enum<V: de::Visitor<'de>>(
+		self,
+		_name: &'static str,
+		_variants: &'static [&'static str],
+		visitor: V,
+	) -> Result<V::Value, Self::Error> {
+		self
+			.deserialize_enum_impl(visitor)
+			.map_err(|err| err.at(&self.path, self.value))
+	}
+
+	fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+		self,
+		_name: &'static str,
+		visitor: V,
+	) -> Result<V::Value, Self::Error> {
+		self
+			.deserialize_newtype_struct_impl(visitor)
+			.map_err(|err| err.at(&self.path, self.value))
+	}
+
+	my_serde::forward_to_deserialize_any! {
+		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+		bytes byte_buf unit unit_struct seq tuple
+		tuple_struct map struct identifier ignored_any
+	}
+}
@@ -0,0 +1,52 @@
+use my_serde::de;
+
+use crate::Value;
+
+use super::{Deserializer, DeserializerError};
+
+/// Accesses the elements of arrays, tuples and tuple structs.
+pub(super) struct SeqAccess<'de> {
+	values: std::iter::Enumerate<std::slice::Iter<'de, Value>>,
+	path: String,
+	is_array: bool,
+}
+
+impl<'de> SeqAccess<'de> {
+	pub fn array(values: &'de [Value], path: &str) -> Self {
+		Self {
+			values: values.iter().enumerate(),
+			path: path.into(),
+			is_array: true,
+		}
+	}
+
+	pub fn tuple(values: &'de [Value], path: &str) -> Self {
+		Self {
+			values: values.iter().enumerate(),
+			path: path.into(),
+			is_array: false,
+		}
+	}
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess<'de> {
+	type Error = DeserializerError;
+
+	fn next_element_seed<T: de::DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error> {
+		match self.values.next() {
+			Some((index, value)) => {
+				let path = if self.is_array {
+					format!("{}[{}]", self.path, index)
+				} else {
+					format!("{}.{}", self.path, index)
+				};
+				seed.deserialize(Deserializer::nested(value, path)).map(Some)
+			}
+			None => Ok(None),
+		}
+	}
+
+	fn size_hint(&self) -> Option<usize> {
+		Some(self.values.len())
+	}
+}
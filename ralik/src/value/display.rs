@@ -94,12 +94,12 @@ impl fmt::Display for Value {
 			TypeKind::Enum => {
 				write!(f, "{}::", self.r#type.name())?;
 				match &self.data {
-					Data::UnitVariant(id) => match &self.r#type.variants().1[*id] {
+					Data::UnitVariant(id) => match &self.r#type.variant_table().1[*id] {
 						Variant::Unit(name) => write!(f, "{}", name),
 						Variant::Tuple(name, _field_types) => write!(f, "{}()", name),
 						Variant::Struct(name, _field_names, _field_types) => write!(f, "{} {{ }}", name),
 					},
-					Data::Variant(id, value) => match &self.r#type.variants().1[*id] {
+					Data::Variant(id, value) => match &self.r#type.variant_table().1[*id] {
 						Variant::Unit(name) => write!(f, "{}", name),
 						Variant::Tuple(name, _field_types) => {
 							write!(f, "{}(", name)?;
@@ -142,6 +142,10 @@ impl fmt::Display for Value {
 				}
 				_ => panic!("Invalid array representation"),
 			},
+			TypeKind::Opaque(_) => match &self.data {
+				Data::Opaque(_value) => write!(f, "{} {{ .. }}", self.r#type.name()),
+				_ => panic!("Invalid opaque representation"),
+			},
 		}
 	}
 }
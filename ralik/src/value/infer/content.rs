@@ -0,0 +1,325 @@
+use my_serde::ser::{self, Serialize};
+use num::BigInt;
+
+use super::super::serializer::SerializerError;
+
+/// A self-describing representation of serialized data, before any ralik types are known.
+pub(super) enum Content {
+	Unit,
+	Bool(bool),
+	Integer(BigInt),
+	Char(char),
+	String(String),
+	None,
+	Some(Box<Content>),
+	Seq(Vec<Content>),
+	Map(Vec<(String, Content)>),
+}
+
+/// Serializes into `Content`, representing enums as externally tagged variants.
+pub(super) struct ContentSerializer;
+
+impl ser::Serializer for ContentSerializer {
+	type Ok = Content;
+	type Error = SerializerError;
+	type SerializeSeq = SerializeSeq;
+	type SerializeTuple = SerializeSeq;
+	type SerializeTupleStruct = SerializeSeq;
+	type SerializeTupleVariant = SerializeVariant<SerializeSeq>;
+	type SerializeMap = SerializeMap;
+	type SerializeStruct = SerializeMap;
+	type SerializeStructVariant = SerializeVariant<SerializeMap>;
+
+	fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Bool(v))
+	}
+
+	fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Integer(v.into()))
+	}
+
+	fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Integer(v.into()))
+	}
+
+	fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Integer(v.into()))
+	}
+
+	fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Integer(v.into()))
+	}
+
+	fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Integer(v.into()))
+	}
+
+	fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Integer(v.into()))
+	}
+
+	fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Integer(v.into()))
+	}
+
+	fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Integer(v.into()))
+	}
+
+	fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Integer(v.into()))
+	}
+
+	fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Integer(v.into()))
+	}
+
+	fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
+		Err(SerializerError::Float)
+	}
+
+	fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
+		Err(SerializerError::Float)
+	}
+
+	fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Char(v))
+	}
+
+	fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::String(v.into()))
+	}
+
+	fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Seq(
+			v.iter().map(|byte| Content::Integer((*byte).into())).collect(),
+		))
+	}
+
+	fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::None)
+	}
+
+	fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Some(Box::new(value.serialize(self)?)))
+	}
+
+	fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Unit)
+	}
+
+	fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Unit)
+	}
+
+	fn serialize_unit_variant(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+	) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::String(variant.into()))
+	}
+
+	fn serialize_newtype_struct<T: ?Sized + Serialize>(
+		self,
+		_name: &'static str,
+		value: &T,
+	) -> Result<Self::Ok, Self::Error> {
+		value.serialize(self)
+	}
+
+	fn serialize_newtype_variant<T: ?Sized + Serialize>(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+		value: &T,
+	) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Map(vec![(variant.into(), value.serialize(self)?)]))
+	}
+
+	fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+		Ok(SerializeSeq(Vec::with_capacity(len.unwrap_or(0))))
+	}
+
+	fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+		self.serialize_seq(Some(len))
+	}
+
+	fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeTupleStruct, Self::Error> {
+		self.serialize_seq(Some(len))
+	}
+
+	fn serialize_tuple_variant(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+		len: usize,
+	) -> Result<Self::SerializeTupleVariant, Self::Error> {
+		Ok(SerializeVariant {
+			variant,
+			inner: self.serialize_seq(Some(len))?,
+		})
+	}
+
+	fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+		Ok(SerializeMap {
+			entries: Vec::with_capacity(len.unwrap_or(0)),
+			key: None,
+		})
+	}
+
+	fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct, Self::Error> {
+		self.serialize_map(Some(len))
+	}
+
+	fn serialize_struct_variant(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+		len: usize,
+	) -> Result<Self::SerializeStructVariant, Self::Error> {
+		Ok(SerializeVariant {
+			variant,
+			inner: self.serialize_map(Some(len))?,
+		})
+	}
+}
+
+pub(super) struct SerializeSeq(Vec<Content>);
+
+impl SerializeSeq {
+	fn push<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), SerializerError> {
+		self.0.push(value.serialize(ContentSerializer)?);
+		Ok(())
+	}
+}
+
+impl ser::SerializeSeq for SerializeSeq {
+	type Ok = Content;
+	type Error = SerializerError;
+
+	fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+		self.push(value)
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Seq(self.0))
+	}
+}
+
+impl ser::SerializeTuple for SerializeSeq {
+	type Ok = Content;
+	type Error = SerializerError;
+
+	fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+		self.push(value)
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Seq(self.0))
+	}
+}
+
+impl ser::SerializeTupleStruct for SerializeSeq {
+	type Ok = Content;
+	type Error = SerializerError;
+
+	fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+		self.push(value)
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Seq(self.0))
+	}
+}
+
+pub(super) struct SerializeMap {
+	entries: Vec<(String, Content)>,
+	key: Option<String>,
+}
+
+impl ser::SerializeMap for SerializeMap {
+	type Ok = Content;
+	type Error = SerializerError;
+
+	fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Self::Error> {
+		self.key = Some(match key.serialize(ContentSerializer)? {
+			Content::String(key) => key,
+			Content::Char(key) => key.to_string(),
+			Content::Integer(key) => key.to_string(),
+			Content::Bool(key) => key.to_string(),
+			_ => {
+				return Err(SerializerError::Custom(
+					"map keys must be strings, chars, integers or bools".into(),
+				))
+			}
+		});
+		Ok(())
+	}
+
+	fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+		let key = self
+			.key
+			.take()
+			.ok_or_else(|| SerializerError::Custom("map value serialized without a key".into()))?;
+		self.entries.push((key, value.serialize(ContentSerializer)?));
+		Ok(())
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Map(self.entries))
+	}
+}
+
+impl ser::SerializeStruct for SerializeMap {
+	type Ok = Content;
+	type Error = SerializerError;
+
+	fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error> {
+		self.entries.push((key.into(), value.serialize(ContentSerializer)?));
+		Ok(())
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Map(self.entries))
+	}
+}
+
+/// Wraps the contents of an enum variant into a map with a single entry.
+pub(super) struct SerializeVariant<T> {
+	variant: &'static str,
+	inner: T,
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant<SerializeSeq> {
+	type Ok = Content;
+	type Error = SerializerError;
+
+	fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+		self.inner.push(value)
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Map(vec![(self.variant.into(), Content::Seq(self.inner.0))]))
+	}
+}
+
+impl ser::SerializeStructVariant for SerializeVariant<SerializeMap> {
+	type Ok = Content;
+	type Error = SerializerError;
+
+	fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error> {
+		ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(Content::Map(vec![(
+			self.variant.into(),
+			Content::Map(self.inner.entries),
+		)]))
+	}
+}
@@ -0,0 +1,281 @@
+use my_serde::ser;
+
+use crate::types::{make_array_name, make_string_name, new_enum_type, new_struct_type, Derive, VariantBuilder};
+use crate::{Context, TypeHandle, Value};
+
+use super::serializer::SerializerError;
+
+mod content;
+use content::{Content, ContentSerializer};
+
+/// The name of the enum that holds data whose type could not be inferred, e.g., the elements of heterogeneous arrays.
+pub(crate) const JSON_TYPE_NAME: &str = "$Json";
+
+impl Value {
+	/// Creates a value from self-describing data, such as `serde_json::Value`, inferring and registering the necessary
+	/// types as it goes. Unlike `Value::from_serde`, no target type needs to be registered beforehand:
+	/// - Maps become anonymous struct types, which are named after their fields, e.g., `{ x: Integer, y: Integer }`.
+	///   Fields that are missing from some of the maps in a sequence become options.
+	/// - Homogeneous sequences become arrays, and nulls turn the type of their surroundings into an option.
+	/// - Everything else, e.g., the elements of heterogeneous sequences, becomes the enum `$Json`, which has the variants
+	///   `Null`, `Bool`, `Integer`, `Char`, `String`, `Array` and `Object`. Objects are arrays of key-value pairs.
+	///
+	/// Enums are represented as externally tagged variants. The inferred struct types derive `PartialEq`, `Clone` and
+	/// `Debug`.
+	///
+	/// ```rust
+	/// # use ralik::{eval_str, Value};
+	/// let context = ralik::Context::new();
+	/// let json = serde_json::json!({
+	///     "servers": [{ "host": "a", "port": 80 }, { "host": "b", "port": null, "tls": true }],
+	///     "mixed": [1, "two"],
+	/// });
+	/// context.insert_variable("$", Value::from_serde_inferred(&context, &json).unwrap());
+	/// assert_eq!(eval_str("$.servers[0].port.unwrap()", &context).unwrap().as_u32(), Some(80));
+	/// assert_eq!(eval_str("$.servers[1].tls.unwrap()", &context).unwrap().as_bool(), Some(true));
+	/// assert_eq!(eval_str("$.servers[0].tls.is_none()", &context).unwrap().as_bool(), Some(true));
+	/// assert_eq!(&*eval_str("$.mixed", &context).unwrap().get_type().name(), "[$Json]");
+	/// ```
+	pub fn from_serde_inferred<T: ser::Serialize>(context: &Context, value: T) -> Result<Self, SerializerError> {
+		let content = value.serialize(ContentSerializer)?;
+		let shape = Shape::infer(&content);
+		shape.build(context, content)
+	}
+}
+
+/// The inferred type of some `Content`.
+#[derive(Clone, PartialEq)]
+enum Shape {
+	/// The element type of empty sequences, which can be unified with anything.
+	Unknown,
+	Null,
+	Bool,
+	Integer,
+	Char,
+	String,
+	Option(Box<Shape>),
+	Array(Box<Shape>),
+	Struct(Vec<(String, Shape)>),
+	Json,
+}
+
+impl Shape {
+	fn infer(content: &Content) -> Shape {
+		match content {
+			// self-describing formats such as JSON serialize their nulls as units
+			Content::Unit | Content::None => Shape::Null,
+			Content::Bool(_) => Shape::Bool,
+			Content::Integer(_) => Shape::Integer,
+			Content::Char(_) => Shape::Char,
+			Content::String(_) => Shape::String,
+			Content::Some(content) => Shape::unify(Shape::Null, Shape::infer(content)),
+			Content::Seq(elements) => Shape::Array(Box::new(elements.iter().fold(Shape::Unknown, |shape, element| {
+				Shape::unify(shape, Shape::infer(element))
+			}))),
+			Content::Map(entries) => {
+				let mut fields: Vec<(String, Shape)> = Vec::with_capacity(entries.len());
+				for (name, content) in entries {
+					let shape = Shape::infer(content);
+					match fields.iter_mut().find(|(field_name, _)| field_name == name) {
+						Some((_, field_shape)) => *field_shape = Shape::unify(field_shape.clone(), shape),
+						None => fields.push((name.clone(), shape)),
+					}
+				}
+				Shape::Struct(fields)
+			}
+		}
+	}
+
+	/// Finds a shape that can hold the values of both `lhs` and `rhs`, falling back to `Shape::Json`.
+	fn unify(lhs: Shape, rhs: Shape) -> Shape {
+		match (lhs, rhs) {
+			(Shape::Unknown, shape) | (shape, Shape::Unknown) => shape,
+			(Shape::Json, _) | (_, Shape::Json) => Shape::Json,
+			(Shape::Null, Shape::Null) => Shape::Null,
+			(Shape::Null, Shape::Option(shape)) | (Shape::Option(shape), Shape::Null) => Shape::Option(shape),
+			(Shape::Null, shape) | (shape, Shape::Null) => Shape::Option(Box::new(shape)),
+			(Shape::Option(lhs), Shape::Option(rhs)) => Shape::Option(Box::new(Shape::unify(*lhs, *rhs))),
+			(Shape::Option(lhs), rhs) | (rhs, Shape::Option(lhs)) => Shape::Option(Box::new(Shape::unify(*lhs, rhs))),
+			(Shape::Array(lhs), Shape::Array(rhs)) => Shape::Array(Box::new(Shape::unify(*lhs, *rhs))),
+			(Shape::Struct(lhs), Shape::Struct(mut rhs)) => {
+				let mut fields = Vec::with_capacity(lhs.len());
+				for (name, shape) in lhs {
+					match rhs.iter().position(|(field_name, _)| *field_name == name) {
+						Some(index) => fields.push((name, Shape::unify(shape, rhs.remove(index).1))),
+						None => fields.push((name, Shape::unify(Shape::Null, shape))),
+					}
+				}
+				fields.extend(
+					rhs
+						.into_iter()
+						.map(|(name, shape)| (name, Shape::unify(Shape::Null, shape))),
+				);
+				Shape::Struct(fields)
+			}
+			(lhs, rhs) if lhs == rhs => lhs,
+			_ => Shape::Json,
+		}
+	}
+
+	/// Registers the ralik type for this shape, unless it already exists.
+	fn register(&self, context: &Context) -> Result<TypeHandle, SerializerError> {
+		match self {
+			Shape::Unknown | Shape::Null | Shape::Json => register_json_type(context),
+			Shape::Bool => context.get_bool_type().map_err(core_type_error),
+			Shape::Integer => context.get_integer_type().map_err(core_type_error),
+			Shape::Char => context.get_char_type().map_err(core_type_error),
+			Shape::String => context.get_string_type().map_err(core_type_error),
+			Shape::Option(shape) => {
+				let element_type = shape.register(context)?;
+				context.get_option_type(&element_type.name()).map_err(core_type_error)
+			}
+			Shape::Array(shape) => {
+				let element_type = shape.register(context)?;
+				context.get_array_type(&element_type.name()).map_err(core_type_error)
+			}
+			Shape::Struct(fields) => {
+				let fields = fields
+					.iter()
+					.map(|(name, shape)| Ok((name.as_str(), String::from(&*shape.register(context)?.name()))))
+					.collect::<Result<Vec<_>, SerializerError>>()?;
+				let name = make_struct_name(&fields);
+				if let Some(struct_type) = context.get_type(&name) {
+					return Ok(struct_type);
+				}
+				context.register_types(vec![new_struct_type(&name, fields.into_iter()).derive(vec![
+					Derive::PartialEq,
+					Derive::Clone,
+					Derive::Debug,
+				])]);
+				Ok(context.get_type(&name).unwrap())
+			}
+		}
+	}
+
+	fn build(&self, context: &Context, content: Content) -> Result<Value, SerializerError> {
+		let r#type = self.register(context)?;
+		Ok(match (self, content) {
+			(Shape::Unknown | Shape::Null | Shape::Json, content) => build_json(context, content)?,
+			(Shape::Bool, Content::Bool(value)) => Value::new_bool(context, value)?,
+			(Shape::Integer, Content::Integer(value)) => Value::new_integer(context, value)?,
+			(Shape::Char, Content::Char(value)) => Value::new_char(context, value)?,
+			(Shape::String, Content::String(value)) => Value::new_string(context, value)?,
+			(Shape::Option(_shape), Content::None | Content::Unit) => {
+				Value::new_enum_unit_variant(context, r#type.name(), "None")?
+			}
+			(Shape::Option(shape), Content::Some(content)) => {
+				let value = shape.build(context, *content)?;
+				Value::new_enum_tuple_variant(context, r#type.name(), "Some", vec![value])?
+			}
+			(Shape::Option(shape), content) => {
+				let value = shape.build(context, content)?;
+				Value::new_enum_tuple_variant(context, r#type.name(), "Some", vec![value])?
+			}
+			(Shape::Array(shape), Content::Seq(elements)) => {
+				let elements = elements
+					.into_iter()
+					.map(|element| shape.build(context, element))
+					.collect::<Result<Vec<_>, _>>()?;
+				Value::new_array(context, &shape.register(context)?, elements)?
+			}
+			(Shape::Struct(fields), Content::Map(mut entries)) => {
+				let values = fields
+					.iter()
+					.map(|(name, shape)| {
+						let content = match entries.iter().position(|(key, _)| key == name) {
+							Some(index) => entries.swap_remove(index).1,
+							None => Content::None,
+						};
+						Ok((name, shape.build(context, content)?))
+					})
+					.collect::<Result<Vec<_>, SerializerError>>()?;
+				Value::new_struct(context, r#type.name(), values.into_iter())?
+			}
+			_ => unreachable!("content does not match its inferred shape"),
+		})
+	}
+}
+
+fn core_type_error(err: impl Into<crate::error::InvalidCoreType>) -> SerializerError {
+	SerializerError::InvalidCoreType(err.into())
+}
+
+fn make_struct_name(fields: &[(&str, String)]) -> String {
+	if fields.is_empty() {
+		return "{}".into();
+	}
+	let fields = fields
+		.iter()
+		.map(|(name, type_name)| format!("{}: {}", name, type_name))
+		.collect::<Vec<_>>();
+	format!("{{ {} }}", fields.join(", "))
+}
+
+fn register_json_type(context: &Context) -> Result<TypeHandle, SerializerError> {
+	if let Some(json_type) = context.get_type(JSON_TYPE_NAME) {
+		return Ok(json_type);
+	}
+
+	let bool_type = context.get_bool_type().map_err(core_type_error)?;
+	let integer_type = context.get_integer_type().map_err(core_type_error)?;
+	let char_type = context.get_char_type().map_err(core_type_error)?;
+	let string_type = context.get_string_type().map_err(core_type_error)?;
+
+	// `$Json` is recursive, so it has to be registered together with the types that contain it
+	let entry_type_name = crate::types::make_tuple_name([make_string_name(), JSON_TYPE_NAME].iter());
+	let json_type = new_enum_type(
+		JSON_TYPE_NAME,
+		vec![
+			VariantBuilder::Unit("Null".into()),
+			VariantBuilder::Tuple("Bool".into(), vec![(*bool_type.name()).into()]),
+			VariantBuilder::Tuple("Integer".into(), vec![(*integer_type.name()).into()]),
+			VariantBuilder::Tuple("Char".into(), vec![(*char_type.name()).into()]),
+			VariantBuilder::Tuple("String".into(), vec![(*string_type.name()).into()]),
+			VariantBuilder::Tuple("Array".into(), vec![make_array_name(JSON_TYPE_NAME)]),
+			VariantBuilder::Tuple("Object".into(), vec![make_array_name(&entry_type_name)]),
+		],
+	)
+	.derive(vec![Derive::PartialEq, Derive::Clone, Derive::Debug]);
+	context.register_types(vec![
+		json_type,
+		context.array_type_builder(JSON_TYPE_NAME).map_err(core_type_error)?,
+		context
+			.tuple_type_builder(vec![make_string_name(), JSON_TYPE_NAME])
+			.map_err(core_type_error)?,
+		context.array_type_builder(&entry_type_name).map_err(core_type_error)?,
+	]);
+	Ok(context.get_type(JSON_TYPE_NAME).unwrap())
+}
+
+fn build_json(context: &Context, content: Content) -> Result<Value, SerializerError> {
+	let json_type = register_json_type(context)?;
+	let variant = |name: &str, value: Value| Value::new_enum_tuple_variant(context, JSON_TYPE_NAME, name, vec![value]);
+	Ok(match content {
+		Content::Unit | Content::None => Value::new_enum_unit_variant(context, JSON_TYPE_NAME, "Null")?,
+		Content::Bool(value) => variant("Bool", Value::new_bool(context, value)?)?,
+		Content::Integer(value) => variant("Integer", Value::new_integer(context, value)?)?,
+		Content::Char(value) => variant("Char", Value::new_char(context, value)?)?,
+		Content::String(value) => variant("String", Value::new_string(context, value)?)?,
+		Content::Some(content) => build_json(context, *content)?,
+		Content::Seq(elements) => {
+			let elements = elements
+				.into_iter()
+				.map(|element| build_json(context, element))
+				.collect::<Result<Vec<_>, _>>()?;
+			variant("Array", Value::new_array(context, &json_type, elements)?)?
+		}
+		Content::Map(entries) => {
+			let entries = entries
+				.into_iter()
+				.map(|(key, content)| {
+					let key = Value::new_string(context, key)?;
+					Ok(Value::new_tuple(context, vec![key, build_json(context, content)?])?)
+				})
+				.collect::<Result<Vec<_>, SerializerError>>()?;
+			let entry_type = context
+				.get_tuple_type(vec![make_string_name(), JSON_TYPE_NAME])
+				.map_err(core_type_error)?;
+			variant("Object", Value::new_array(context, &entry_type, entries)?)?
+		}
+	})
+}
@@ -1,24 +1,46 @@
+use anyhow::anyhow;
 use num::BigInt;
 use num::ToPrimitive;
 
+use std::any::Any;
+use std::sync::Arc;
+
 use crate::context::TypeId;
-use crate::types::Variant;
-use crate::TypeHandle;
+use crate::error::RuntimeError;
+use crate::types::{Callable, Variant};
+use crate::{Context, TypeHandle};
+
+mod convert;
+pub use convert::{FromArgument, FromValue, FunctionResult, IntoValue};
 
 mod debug;
 mod display;
 mod new;
+mod pretty;
+pub use pretty::PrettyOptions;
+mod source;
+pub use source::SourceError;
 
+#[cfg(feature = "serde")]
+mod deserializer;
+#[cfg(feature = "serde")]
+pub use deserializer::DeserializerError;
+#[cfg(feature = "serde")]
+mod infer;
+#[cfg(feature = "serde")]
+mod serialize;
 #[cfg(feature = "serde")]
 mod serializer;
+#[cfg(feature = "serde")]
+pub use serializer::SerializerError;
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq)]
 pub struct Value {
 	r#type: TypeHandle,
 	data: Data,
 }
 
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone)]
 enum Data {
 	Empty,
 	Bool(bool),
@@ -28,8 +50,29 @@ enum Data {
 	Array(Box<[Value]>),
 	UnitVariant(usize),
 	Variant(usize, Box<[Value]>),
+	Opaque(Arc<dyn Any + Send + Sync>),
+}
+
+/// Values of opaque types are only equal if they share the same Rust object.
+impl PartialEq for Data {
+	fn eq(&self, other: &Self) -> bool {
+		match (self, other) {
+			(Data::Empty, Data::Empty) => true,
+			(Data::Bool(lhs), Data::Bool(rhs)) => lhs == rhs,
+			(Data::Integer(lhs), Data::Integer(rhs)) => lhs == rhs,
+			(Data::Char(lhs), Data::Char(rhs)) => lhs == rhs,
+			(Data::String(lhs), Data::String(rhs)) => lhs == rhs,
+			(Data::Array(lhs), Data::Array(rhs)) => lhs == rhs,
+			(Data::UnitVariant(lhs), Data::UnitVariant(rhs)) => lhs == rhs,
+			(Data::Variant(lhs_id, lhs), Data::Variant(rhs_id, rhs)) => lhs_id == rhs_id && lhs == rhs,
+			(Data::Opaque(lhs), Data::Opaque(rhs)) => Arc::ptr_eq(lhs, rhs),
+			_ => false,
+		}
+	}
 }
 
+impl Eq for Data {}
+
 impl Value {
 	pub fn get_type(&self) -> &TypeHandle {
 		&self.r#type
@@ -145,6 +188,40 @@ impl Value {
 		}
 	}
 
+	/// Returns the Rust object wrapped by a value of an opaque type.
+	pub fn as_opaque(&self) -> Option<&Arc<dyn Any + Send + Sync>> {
+		match &self.data {
+			Data::Opaque(value) => Some(value),
+			_ => None,
+		}
+	}
+
+	/// Returns the Rust object wrapped by a value of an opaque type, if it has the type `T`.
+	pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+		self.as_opaque().and_then(|value| value.downcast_ref())
+	}
+
+	/// Calls a function value, e.g., one that was created by `Value::new_function` or by naming a function in a script.
+	///
+	/// ```rust
+	/// # use ralik::eval_str;
+	/// let context = ralik::Context::new();
+	/// let len = eval_str("String::len", &context).unwrap();
+	/// let argument = eval_str("\"abc\"", &context).unwrap();
+	/// assert_eq!(len.call(&context, &[argument]).unwrap().as_i32(), Some(3));
+	/// ```
+	pub fn call(&self, context: &Context, arguments: &[Value]) -> Result<Value, RuntimeError> {
+		match self.downcast_ref::<Callable>() {
+			Some(callable) => callable.call(context, arguments),
+			None => Err(anyhow!("A value of type `{}` cannot be called", self.r#type.name()).into()),
+		}
+	}
+
+	/// Checks whether this is a function value, which can be passed to `Value::call`.
+	pub fn is_function(&self) -> bool {
+		self.downcast_ref::<Callable>().is_some()
+	}
+
 	pub fn field(&self, name: &str) -> Option<&Value> {
 		match &self.data {
 			Data::Array(fields) => {
@@ -152,7 +229,7 @@ impl Value {
 				Some(&fields[*field_names.get(name)?])
 			}
 			Data::Variant(id, fields) => {
-				let variant = &self.r#type.variants().1[*id];
+				let variant = &self.r#type.variant_table().1[*id];
 				match variant {
 					Variant::Struct(_name, field_names, _field_types) => Some(&fields[*field_names.get(name)?]),
 					_ => None,
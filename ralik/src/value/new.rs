@@ -1,12 +1,17 @@
 use num::BigInt;
 
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use crate::error::{
 	ArrayCreationError, BoolCreationError, CharCreationError, EnumStructVariantCreationError,
-	EnumTupleVariantCreationError, EnumUnitVariantCreationError, IntegerCreationError, InvalidArrayType,
-	StringCreationError, StructCreationError, TupleCreationError, TupleStructCreationError, UnitStructCreationError,
+	EnumTupleVariantCreationError, EnumUnitVariantCreationError, FunctionCreationError, IntegerCreationError,
+	InvalidArrayType, OpaqueCreationError, StringCreationError, StructCreationError, TupleCreationError,
+	TupleStructCreationError, UnitStructCreationError,
 };
-use crate::types::{TypeKind, Variant};
-use crate::{Context, TypeHandle};
+use crate::types::{Callable, TypeKind, Variant};
+use crate::{Context, NativeFunction, TypeHandle};
 
 use super::{Data, Value};
 
@@ -155,6 +160,12 @@ impl Value {
 			};
 
 			fields.sort_unstable_by_key(|(key, _name, _value)| *key);
+			if let Some(field_name) = missing_field(&field_names, fields.iter().map(|(key, _name, _value)| *key)) {
+				return Err(StructCreationError::MissingField {
+					r#type: struct_type,
+					field_name,
+				});
+			}
 
 			let fields = fields
 				.into_iter()
@@ -207,6 +218,67 @@ impl Value {
 		})
 	}
 
+	/// Creates a value of the opaque type `name` that wraps `value`, see `types::new_opaque_type`.
+	pub fn new_opaque(
+		context: &Context,
+		name: impl AsRef<str>,
+		value: impl Any + Send + Sync,
+	) -> Result<Value, OpaqueCreationError> {
+		Value::new_opaque_from_arc(context, name, Arc::new(value))
+	}
+
+	/// Creates a value of the opaque type `name` that shares `value` with the host program.
+	pub fn new_opaque_from_arc(
+		context: &Context,
+		name: impl AsRef<str>,
+		value: Arc<dyn Any + Send + Sync>,
+	) -> Result<Value, OpaqueCreationError> {
+		let name = name.as_ref();
+
+		let opaque_type = context
+			.get_type(name)
+			.ok_or_else(|| crate::error::InvalidOpaqueType::Missing { type_name: name.into() })?
+			.clone();
+		match opaque_type.kind() {
+			TypeKind::Opaque(type_id) if type_id == (*value).type_id() => (),
+			TypeKind::Opaque(_) => return Err(crate::error::InvalidOpaqueType::WrongRustType { r#type: opaque_type }.into()),
+			_ => return Err(crate::error::InvalidOpaqueType::NotOpaqueType { r#type: opaque_type }.into()),
+		}
+
+		Ok(Value {
+			r#type: opaque_type,
+			data: Data::Opaque(value),
+		})
+	}
+
+	/// Creates a function value that scripts can call or pass to functions taking a function, e.g., `map`.
+	///
+	/// ```rust
+	/// # use ralik::{eval_str, Context, Value};
+	/// let context = Context::new();
+	/// let double = |context: &Context, arguments: &[Value]| {
+	///     Ok(Value::new_integer(context, arguments[0].as_integer().unwrap() * 2)?)
+	/// };
+	/// context.insert_variable("double", Value::new_function(&context, "double", double).unwrap());
+	/// assert_eq!(eval_str("double(21)", &context).unwrap().as_i32(), Some(42));
+	/// let value = eval_str("[1, 2].map(double)", &context).unwrap();
+	/// assert_eq!(value, eval_str("[2, 4]", &context).unwrap());
+	/// ```
+	pub fn new_function(
+		context: &Context,
+		name: impl Into<String>,
+		function: impl Into<NativeFunction>,
+	) -> Result<Value, FunctionCreationError> {
+		Value::new_callable(context, Callable::Function(name.into(), function.into()))
+	}
+
+	pub(crate) fn new_callable(context: &Context, callable: Callable) -> Result<Value, FunctionCreationError> {
+		Ok(Value {
+			r#type: context.get_function_type()?,
+			data: Data::Opaque(Arc::new(callable)),
+		})
+	}
+
 	pub fn new_array(
 		context: &Context,
 		element_type: &TypeHandle,
@@ -251,7 +323,7 @@ impl Value {
 		}
 
 		let variant_name = variant_name.as_ref();
-		let (variant_names, variant_ids) = enum_type.variants();
+		let (variant_names, variant_ids) = enum_type.variant_table();
 
 		let variant_id = if let Some(variant_id) = variant_names.get(variant_name) {
 			*variant_id
@@ -290,7 +362,7 @@ impl Value {
 		}
 
 		let variant_name = variant_name.as_ref();
-		let (variant_names, variant_ids) = enum_type.variants();
+		let (variant_names, variant_ids) = enum_type.variant_table();
 
 		let variant_id = if let Some(variant_id) = variant_names.get(variant_name) {
 			*variant_id
@@ -344,7 +416,7 @@ impl Value {
 		}
 
 		let variant_name = variant_name.as_ref();
-		let (variant_names, variant_ids) = enum_type.variants();
+		let (variant_names, variant_ids) = enum_type.variant_table();
 
 		let variant_id = if let Some(variant_id) = variant_names.get(variant_name) {
 			*variant_id
@@ -391,6 +463,12 @@ impl Value {
 					};
 
 					fields.sort_unstable_by_key(|(key, _name, _value)| *key);
+					if let Some(field_name) = missing_field(field_names, fields.iter().map(|(key, _name, _value)| *key)) {
+						return Err(EnumStructVariantCreationError::MissingField {
+							r#type: enum_type.clone(),
+							field_name,
+						});
+					}
 
 					let fields = fields
 						.into_iter()
@@ -432,3 +510,17 @@ impl Value {
 		}
 	}
 }
+
+/// Returns the name of the first field that is not initialized by any of the field indices in `keys`, which are sorted.
+fn missing_field(field_names: &HashMap<Box<str>, usize>, keys: impl Iterator<Item = usize>) -> Option<String> {
+	let mut next = 0;
+	for key in keys {
+		if key == next {
+			next += 1;
+		}
+	}
+	field_names
+		.iter()
+		.find(|(_name, index)| **index == next)
+		.map(|(name, _index)| name.as_ref().into())
+}
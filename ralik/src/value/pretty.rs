@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+
+use crate::types::{TypeKind, Variant};
+
+use super::{Data, Value};
+
+/// The number of spaces by which the contents of a broken array, tuple or struct are indented.
+const INDENT: usize = 4;
+
+/// The ANSI escape sequence that resets all styles.
+const RESET: &str = "\x1b[0m";
+
+/// The style of elisions, i.e., `…` and `… 997 more`.
+const ELISION_STYLE: &str = "\x1b[2m";
+
+/// Controls the layout of `Value::pretty`.
+#[derive(Clone, Debug)]
+pub struct PrettyOptions {
+	/// The width that lines should not exceed, if possible.
+	pub width: usize,
+	/// Arrays, tuples, structs and variants that are nested deeper than this are elided as, e.g., `[…]`.
+	pub max_depth: Option<usize>,
+	/// Only this many elements of an array are shown, followed by, e.g., `… 997 more`.
+	pub max_length: Option<usize>,
+	/// Whether to colorize the output with ANSI escape sequences, depending on the `TypeKind` of each value.
+	pub color: bool,
+}
+
+impl Default for PrettyOptions {
+	fn default() -> Self {
+		PrettyOptions {
+			width: 80,
+			max_depth: None,
+			max_length: None,
+			color: false,
+		}
+	}
+}
+
+impl Value {
+	/// Formats the value like `{:?}`, but breaks arrays, tuples and structs that do not fit into `options.width` over
+	/// multiple lines, similar to `{:#?}`. Short values are kept on a single line.
+	///
+	/// ```rust
+	/// # use ralik::{eval_str, PrettyOptions};
+	/// let context = ralik::Context::new();
+	/// let value = eval_str(r#"(["a", "b"], "abc".find('c'))"#, &context).unwrap();
+	/// assert_eq!(value.pretty(&PrettyOptions::default()), r#"(["a", "b"], Some(2))"#);
+	/// let options = PrettyOptions { width: 16, ..Default::default() };
+	/// assert_eq!(value.pretty(&options), "(\n    [\"a\", \"b\"],\n    Some(2),\n)");
+	///
+	/// let value = eval_str("[1, 2, 3, 4, 5, 6]", &context).unwrap();
+	/// let options = PrettyOptions { max_length: Some(3), ..Default::default() };
+	/// assert_eq!(value.pretty(&options), "[1, 2, 3, … 3 more]");
+	///
+	/// let value = eval_str("[[[1]], [[2]]]", &context).unwrap();
+	/// let options = PrettyOptions { max_depth: Some(2), ..Default::default() };
+	/// assert_eq!(value.pretty(&options), "[[[…]], [[…]]]");
+	/// ```
+	pub fn pretty(&self, options: &PrettyOptions) -> String {
+		let mut out = String::new();
+		render(&value_doc(self, options, 0), options, &mut out);
+		out
+	}
+}
+
+/// A document in the sense of Wadler's "A prettier printer", which describes all possible layouts of a value.
+enum Doc {
+	/// Text that does not contain line breaks, with an optional ANSI style.
+	Text(String, Option<&'static str>),
+	/// A space, or a line break if the enclosing group is broken.
+	Line,
+	/// Nothing, or a line break if the enclosing group is broken.
+	SoftLine,
+	/// Text that only appears if the enclosing group is broken, such as trailing commas.
+	IfBroken(&'static str),
+	/// Increases the indentation of all line breaks in the contained documents.
+	Nest(Vec<Doc>),
+	/// Documents that are either laid out on a single line, or all of whose line breaks are taken.
+	Group(Vec<Doc>),
+}
+
+fn text(text: impl Into<String>) -> Doc {
+	Doc::Text(text.into(), None)
+}
+
+fn styled(text: impl Into<String>, kind: TypeKind, options: &PrettyOptions) -> Doc {
+	Doc::Text(text.into(), if options.color { Some(style(kind)) } else { None })
+}
+
+fn elision(text: impl Into<String>, options: &PrettyOptions) -> Doc {
+	Doc::Text(text.into(), if options.color { Some(ELISION_STYLE) } else { None })
+}
+
+fn style(kind: TypeKind) -> &'static str {
+	match kind {
+		TypeKind::Bool => "\x1b[33m",
+		TypeKind::Integer => "\x1b[36m",
+		TypeKind::Char | TypeKind::String => "\x1b[32m",
+		TypeKind::UnitStruct | TypeKind::TupleStruct | TypeKind::Struct => "\x1b[34m",
+		TypeKind::Enum => "\x1b[35m",
+		TypeKind::Tuple | TypeKind::Array | TypeKind::Opaque(_) => "\x1b[2m",
+	}
+}
+
+fn value_doc(value: &Value, options: &PrettyOptions, depth: usize) -> Doc {
+	let kind = value.r#type.kind();
+	let elided = matches!(options.max_depth, Some(max_depth) if depth >= max_depth);
+	match (kind, &value.data) {
+		(TypeKind::Bool, Data::Bool(value)) => styled(value.to_string(), kind, options),
+		(TypeKind::Integer, Data::Integer(value)) => styled(value.to_string(), kind, options),
+		(TypeKind::Char, Data::Char(value)) => styled(format!("{:?}", value), kind, options),
+		(TypeKind::String, Data::String(value)) => styled(format!("{:?}", value), kind, options),
+		(TypeKind::Tuple, Data::Empty) => text("()"),
+		(TypeKind::Tuple, Data::Array(values)) => {
+			let items = elements(values, None, options, depth, elided);
+			// the comma of a tuple with a single element is not optional
+			let trailing_comma = if values.len() == 1 && !elided {
+				text(",")
+			} else {
+				Doc::IfBroken(",")
+			};
+			list(text("("), items, trailing_comma, ")", true)
+		}
+		(TypeKind::Array, Data::Empty) => text("[]"),
+		(TypeKind::Array, Data::Array(values)) => list(
+			text("["),
+			elements(values, options.max_length, options, depth, elided),
+			Doc::IfBroken(","),
+			"]",
+			true,
+		),
+		(TypeKind::UnitStruct, Data::Empty) => styled(&*value.r#type.name(), kind, options),
+		(TypeKind::TupleStruct, Data::Empty) => styled(&*value.r#type.name(), kind, options),
+		(TypeKind::TupleStruct, Data::Array(values)) => list(
+			styled(format!("{}(", value.r#type.name()), kind, options),
+			elements(values, None, options, depth, elided),
+			Doc::IfBroken(","),
+			")",
+			true,
+		),
+		(TypeKind::Struct, Data::Empty) => styled(&*value.r#type.name(), kind, options),
+		(TypeKind::Struct, Data::Array(values)) => fields(
+			styled(&*value.r#type.name(), kind, options),
+			&value.r#type.fields().0,
+			values,
+			options,
+			depth,
+			elided,
+		),
+		(TypeKind::Enum, Data::UnitVariant(id)) => match &value.r#type.variant_table().1[*id] {
+			Variant::Unit(name) | Variant::Tuple(name, _) | Variant::Struct(name, _, _) => styled(&**name, kind, options),
+		},
+		(TypeKind::Enum, Data::Variant(id, values)) => match &value.r#type.variant_table().1[*id] {
+			Variant::Unit(name) => styled(&**name, kind, options),
+			Variant::Tuple(name, _field_types) => list(
+				styled(format!("{}(", name), kind, options),
+				elements(values, None, options, depth, elided),
+				Doc::IfBroken(","),
+				")",
+				true,
+			),
+			Variant::Struct(name, field_names, _field_types) => fields(
+				styled(&**name, kind, options),
+				field_names,
+				values,
+				options,
+				depth,
+				elided,
+			),
+		},
+		(TypeKind::Opaque(_), Data::Opaque(_value)) => styled(format!("{} {{ .. }}", value.r#type.name()), kind, options),
+		(kind, data) => panic!("Invalid {:?} representation {:?}", kind, data),
+	}
+}
+
+/// The documents of the elements of an array, tuple or tuple struct, of which at most `max_length` are shown.
+fn elements(
+	values: &[Value],
+	max_length: Option<usize>,
+	options: &PrettyOptions,
+	depth: usize,
+	elided: bool,
+) -> Vec<Doc> {
+	if elided {
+		return vec![elision("…", options)];
+	}
+
+	let shown = max_length.map_or(values.len(), |max_length| max_length.min(values.len()));
+	let mut items = values[..shown]
+		.iter()
+		.map(|value| value_doc(value, options, depth + 1))
+		.collect::<Vec<Doc>>();
+	if shown < values.len() {
+		items.push(elision(format!("… {} more", values.len() - shown), options));
+	}
+	items
+}
+
+fn fields(
+	name: Doc,
+	field_names: &HashMap<Box<str>, usize>,
+	values: &[Value],
+	options: &PrettyOptions,
+	depth: usize,
+	elided: bool,
+) -> Doc {
+	if elided {
+		return list(name, vec![elision("…", options)], Doc::IfBroken(","), "}", false);
+	}
+
+	let mut field_names = field_names.iter().collect::<Vec<_>>();
+	field_names.sort_by_key(|(_name, id)| **id);
+	let items = field_names
+		.into_iter()
+		.map(|(name, id)| {
+			Doc::Group(vec![
+				text(format!("{}: ", name)),
+				value_doc(&values[*id], options, depth + 1),
+			])
+		})
+		.collect();
+	list(name, items, Doc::IfBroken(","), "}", false)
+}
+
+/// Lays out `items` separated by commas, e.g., as `[a, b]` if `tight`, as `Name { a, b }` otherwise, or with one item
+/// per line if they do not fit.
+fn list(open: Doc, items: Vec<Doc>, trailing_comma: Doc, close: &'static str, tight: bool) -> Doc {
+	let (open, line): (Doc, fn() -> Doc) = if tight {
+		(open, || Doc::SoftLine)
+	} else {
+		(Doc::Group(vec![open, text(" {")]), || Doc::Line)
+	};
+
+	let mut nested = Vec::with_capacity(2 * items.len() + 1);
+	for (i, item) in items.into_iter().enumerate() {
+		if i > 0 {
+			nested.push(text(","));
+			nested.push(Doc::Line);
+		} else {
+			nested.push(line());
+		}
+		nested.push(item);
+	}
+	nested.push(trailing_comma);
+	Doc::Group(vec![open, Doc::Nest(nested), line(), text(close)])
+}
+
+fn render(doc: &Doc, options: &PrettyOptions, out: &mut String) {
+	let mut column = 0;
+	let mut stack = vec![(0, false, doc)];
+	while let Some((indent, flat, doc)) = stack.pop() {
+		match doc {
+			Doc::Text(text, style) => {
+				match style {
+					Some(style) => {
+						out.push_str(style);
+						out.push_str(text);
+						out.push_str(RESET);
+					}
+					None => out.push_str(text),
+				}
+				column += text.chars().count();
+			}
+			Doc::Line | Doc::SoftLine if !flat => {
+				out.push('\n');
+				out.extend(std::iter::repeat_n(' ', indent));
+				column = indent;
+			}
+			Doc::Line => {
+				out.push(' ');
+				column += 1;
+			}
+			Doc::SoftLine => (),
+			Doc::IfBroken(text) => {
+				if !flat {
+					out.push_str(text);
+					column += text.chars().count();
+				}
+			}
+			Doc::Nest(docs) => stack.extend(docs.iter().rev().map(|doc| (indent + INDENT, flat, doc))),
+			Doc::Group(docs) => {
+				let flat = flat || fits(options.width as isize - column as isize, docs, &stack);
+				stack.extend(docs.iter().rev().map(|doc| (indent, flat, doc)));
+			}
+		}
+	}
+}
+
+/// Checks whether `docs` fit into `width` when laid out on a single line, together with whatever follows them on the
+/// same line.
+fn fits(mut width: isize, docs: &[Doc], rest: &[(usize, bool, &Doc)]) -> bool {
+	let mut rest = rest.iter().rev().map(|(_indent, flat, doc)| (*flat, *doc));
+	let mut stack = docs.iter().rev().map(|doc| (true, doc)).collect::<Vec<_>>();
+	while width >= 0 {
+		let (flat, doc) = match stack.pop().or_else(|| rest.next()) {
+			Some(next) => next,
+			None => return true,
+		};
+		match doc {
+			Doc::Text(text, _style) => width -= text.chars().count() as isize,
+			Doc::Line | Doc::SoftLine if !flat => return true,
+			Doc::Line => width -= 1,
+			Doc::SoftLine => (),
+			Doc::IfBroken(text) => {
+				if !flat {
+					width -= text.chars().count() as isize;
+				}
+			}
+			Doc::Nest(docs) | Doc::Group(docs) => stack.extend(docs.iter().rev().map(|doc| (flat, doc))),
+		}
+	}
+	false
+}
@@ -0,0 +1,167 @@
+use my_serde::ser::{self, Error, SerializeMap, SerializeSeq, SerializeTuple};
+use num::{BigInt, ToPrimitive};
+
+use std::collections::HashMap;
+
+use crate::types::{TypeKind, Variant};
+
+use super::infer::JSON_TYPE_NAME;
+use super::{Data, Value};
+
+/**
+Serializes the value into any serde data format. Structs become maps, enums become externally tagged variants (except
+for options, which map to serde's options), arrays and tuples become sequences, and integers are serialized as the
+smallest integer type that they fit in. Integers that do not even fit into an `i128` or `u128` are serialized as
+strings instead. Values of the `$Json` type created by `Value::from_serde_inferred` are serialized untagged. Values
+of opaque types cannot be serialized.
+
+```rust
+# use ralik::eval_str;
+let context = ralik::Context::new();
+let value = eval_str(r#"("abc".find('c'), [1, -300], 'x', "y", 1..3)"#, &context).unwrap();
+assert_eq!(serde_json::to_string(&value).unwrap(), r#"[2,[1,-300],"x","y",{"start":1,"end":3}]"#);
+
+let value = eval_str("340282366920938463463374607431768211456", &context).unwrap();
+assert_eq!(serde_json::to_string(&value).unwrap(), r#""340282366920938463463374607431768211456""#);
+```
+*/
+impl ser::Serialize for Value {
+	fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		match (self.r#type.kind(), &self.data) {
+			(TypeKind::Bool, Data::Bool(value)) => serializer.serialize_bool(*value),
+			(TypeKind::Integer, Data::Integer(value)) => serialize_integer(value, serializer),
+			(TypeKind::Char, Data::Char(value)) => serializer.serialize_char(*value),
+			(TypeKind::String, Data::String(value)) => serializer.serialize_str(value),
+			(TypeKind::Tuple, Data::Empty) => serializer.serialize_unit(),
+			(TypeKind::Tuple, Data::Array(values)) => {
+				let mut tuple = serializer.serialize_tuple(values.len())?;
+				for value in values.iter() {
+					tuple.serialize_element(value)?;
+				}
+				tuple.end()
+			}
+			(TypeKind::Array, Data::Empty) => serializer.serialize_seq(Some(0))?.end(),
+			(TypeKind::Array, Data::Array(values)) => serializer.collect_seq(values.iter()),
+			(TypeKind::UnitStruct, Data::Empty) => serializer.serialize_unit(),
+			(TypeKind::TupleStruct, Data::Empty) => serializer.serialize_seq(Some(0))?.end(),
+			(TypeKind::TupleStruct, Data::Array(values)) if values.len() == 1 => values[0].serialize(serializer),
+			(TypeKind::TupleStruct, Data::Array(values)) => serializer.collect_seq(values.iter()),
+			(TypeKind::Struct, Data::Empty) => serializer.serialize_map(Some(0))?.end(),
+			(TypeKind::Struct, Data::Array(values)) => serialize_fields(&self.r#type.fields().0, values, serializer),
+			(TypeKind::Enum, Data::UnitVariant(_id)) if crate::types::is_option_type(&self.r#type) => {
+				serializer.serialize_none()
+			}
+			(TypeKind::Enum, Data::Variant(_id, values)) if crate::types::is_option_type(&self.r#type) => {
+				serializer.serialize_some(&values[0])
+			}
+			(TypeKind::Enum, _) if *self.r#type.name() == *JSON_TYPE_NAME => serialize_json(self, serializer),
+			(TypeKind::Enum, Data::UnitVariant(id)) => {
+				serialize_variant(&self.r#type.variant_table().1[*id], &[], serializer)
+			}
+			(TypeKind::Enum, Data::Variant(id, values)) => {
+				serialize_variant(&self.r#type.variant_table().1[*id], values, serializer)
+			}
+			(TypeKind::Opaque(_), Data::Opaque(_value)) => Err(S::Error::custom(format!(
+				"values of the opaque type `{}` cannot be serialized",
+				self.r#type.name()
+			))),
+			(kind, data) => panic!("Invalid {:?} representation {:?}", kind, data),
+		}
+	}
+}
+
+fn serialize_integer<S: ser::Serializer>(value: &BigInt, serializer: S) -> Result<S::Ok, S::Error> {
+	if let Some(value) = value.to_u8() {
+		serializer.serialize_u8(value)
+	} else if let Some(value) = value.to_i8() {
+		serializer.serialize_i8(value)
+	} else if let Some(value) = value.to_u16() {
+		serializer.serialize_u16(value)
+	} else if let Some(value) = value.to_i16() {
+		serializer.serialize_i16(value)
+	} else if let Some(value) = value.to_u32() {
+		serializer.serialize_u32(value)
+	} else if let Some(value) = value.to_i32() {
+		serializer.serialize_i32(value)
+	} else if let Some(value) = value.to_u64() {
+		serializer.serialize_u64(value)
+	} else if let Some(value) = value.to_i64() {
+		serializer.serialize_i64(value)
+	} else if let Some(value) = value.to_u128() {
+		serializer.serialize_u128(value)
+	} else if let Some(value) = value.to_i128() {
+		serializer.serialize_i128(value)
+	} else {
+		serializer.collect_str(value)
+	}
+}
+
+/// Serializes the values of `$Json` untagged, so that data loaded by `Value::from_serde_inferred` keeps its shape.
+fn serialize_json<S: ser::Serializer>(value: &Value, serializer: S) -> Result<S::Ok, S::Error> {
+	match &value.data {
+		Data::Variant(id, values) if value.r#type.variant_table().1[*id].name() == "Object" => {
+			let entries = values[0].as_array().unwrap();
+			let mut map = serializer.serialize_map(Some(entries.len()))?;
+			for entry in entries {
+				map.serialize_entry(entry.tuple_field(0).unwrap(), entry.tuple_field(1).unwrap())?;
+			}
+			map.end()
+		}
+		Data::Variant(_id, values) => ser::Serialize::serialize(&values[0], serializer),
+		_ => serializer.serialize_unit(),
+	}
+}
+
+/// Serializes an enum variant externally tagged, i.e., as its name for unit variants and as a map with a single entry
+/// from its name to its contents otherwise.
+fn serialize_variant<S: ser::Serializer>(
+	variant: &Variant,
+	values: &[Value],
+	serializer: S,
+) -> Result<S::Ok, S::Error> {
+	match variant {
+		Variant::Unit(name) => serializer.serialize_str(name),
+		Variant::Tuple(name, _field_types) if values.len() == 1 => serialize_entry(name, &values[0], serializer),
+		Variant::Tuple(name, _field_types) => serialize_entry(name, values, serializer),
+		Variant::Struct(name, field_names, _field_types) => {
+			serialize_entry(name, &Fields { field_names, values }, serializer)
+		}
+	}
+}
+
+fn serialize_entry<S: ser::Serializer, T: ?Sized + ser::Serialize>(
+	key: &str,
+	value: &T,
+	serializer: S,
+) -> Result<S::Ok, S::Error> {
+	let mut map = serializer.serialize_map(Some(1))?;
+	map.serialize_entry(key, value)?;
+	map.end()
+}
+
+struct Fields<'a> {
+	field_names: &'a HashMap<Box<str>, usize>,
+	values: &'a [Value],
+}
+
+impl<'a> ser::Serialize for Fields<'a> {
+	fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serialize_fields(self.field_names, self.values, serializer)
+	}
+}
+
+/// Serializes the fields of a struct as a map, in the order in which the fields were declared.
+fn serialize_fields<S: ser::Serializer>(
+	field_names: &HashMap<Box<str>, usize>,
+	values: &[Value],
+	serializer: S,
+) -> Result<S::Ok, S::Error> {
+	let mut field_names = field_names.iter().collect::<Vec<_>>();
+	field_names.sort_by_key(|(_name, id)| **id);
+
+	let mut map = serializer.serialize_map(Some(field_names.len()))?;
+	for (name, id) in field_names {
+		map.serialize_entry(&**name, &values[*id])?;
+	}
+	map.end()
+}
@@ -92,6 +92,9 @@ pub enum SerializerError {
 	#[error(transparent)]
 	ValueCreationError(crate::error::ValueCreationError),
 
+	#[error("Could not instantiate a core type")]
+	InvalidCoreType(crate::error::InvalidCoreType),
+
 	#[error("Custom Error: {0}")]
 	Custom(String),
 }
@@ -209,7 +212,7 @@ impl<'a> ser::Serializer for Serializer<'a> {
 		let value = match self.expected_type.kind() {
 			TypeKind::String => Value::new_string(self.context, value)?,
 			TypeKind::Array => {
-				let element_type = TypeHandle::from_type_id(self.context.clone(), self.expected_type.type_parameters()[0]);
+				let element_type = TypeHandle::from_type_id(self.context.clone(), self.expected_type.type_parameter_ids()[0]);
 				match element_type.kind() {
 					TypeKind::Char => {
 						let chars: Result<Vec<Value>, _> = value.chars().map(|c| Value::new_char(self.context, c)).collect();
@@ -256,7 +259,7 @@ impl<'a> ser::Serializer for Serializer<'a> {
 	fn serialize_some<T: ?Sized + ser::Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error> {
 		let value = match self.expected_type.kind() {
 			TypeKind::Enum => {
-				let (variant_names, variants) = self.expected_type.variants();
+				let (variant_names, variants) = self.expected_type.variant_table();
 				match variant_names.get("Some").map(|&id| &variants[id]) {
 					Some(Variant::Tuple(_, element_types)) => {
 						if element_types.len() != 1 {
@@ -329,7 +332,7 @@ impl<'a> ser::Serializer for Serializer<'a> {
 		} else {
 			match self.expected_type.kind() {
 				TypeKind::Enum => {
-					let (variant_names, variants) = self.expected_type.variants();
+					let (variant_names, variants) = self.expected_type.variant_table();
 					if let Some(variant_id) = variant_names.get(variant) {
 						match variants[*variant_id] {
 							Variant::Unit(_) => Ok(Value::new_enum_unit_variant(self.context, name, variant)?),
@@ -362,10 +365,33 @@ impl<'a> ser::Serializer for Serializer<'a> {
 
 	fn serialize_newtype_struct<T: ?Sized + ser::Serialize>(
 		self,
-		_name: &'static str,
-		_value: &T,
+		name: &'static str,
+		value: &T,
 	) -> Result<Self::Ok, Self::Error> {
-		unimplemented!("`serialize_newtype_struct` for type {}", self.expected_type.name())
+		match self.expected_type.kind() {
+			TypeKind::TupleStruct if (&*self.expected_type.name()) != name => Err(SerializerError::TypeNameMismatch {
+				expected: self.expected_type,
+				actual: name.into(),
+			}),
+			TypeKind::TupleStruct => {
+				let field_types = self.expected_type.fields().1;
+				if field_types.len() != 1 {
+					return Err(SerializerError::TooFewValues {
+						count: field_types.len().saturating_sub(1),
+						r#type: self.expected_type,
+					});
+				}
+				let field = Value::from_serde_by_type(
+					self.context,
+					value,
+					TypeHandle::from_type_id(self.context.clone(), field_types[0]),
+				)?;
+				let value = Value::new_tuple_struct(self.context, name, vec![field])?;
+				self.expect_typed_value(value)
+			}
+			// newtypes that are not mirrored by a tuple struct are transparent
+			_ => value.serialize(self),
+		}
 	}
 
 	fn serialize_newtype_variant<T: ?Sized + ser::Serialize>(
@@ -383,7 +409,7 @@ impl<'a> ser::Serializer for Serializer<'a> {
 		} else {
 			match self.expected_type.kind() {
 				TypeKind::Enum => {
-					let (variant_names, variants) = self.expected_type.variants();
+					let (variant_names, variants) = self.expected_type.variant_table();
 					if let Some(variant_id) = variant_names.get(variant) {
 						match &variants[*variant_id] {
 							Variant::Tuple(_, types) => Ok(Value::new_enum_tuple_variant(
@@ -481,7 +507,7 @@ impl<'a> ser::Serializer for Serializer<'a> {
 		} else {
 			match self.expected_type.kind() {
 				TypeKind::Enum => {
-					let (variant_names, variants) = self.expected_type.variants();
+					let (variant_names, variants) = self.expected_type.variant_table();
 					if let Some(&variant_id) = variant_names.get(variant) {
 						match &variants[variant_id] {
 							Variant::Struct(_, _, _) => {
@@ -35,7 +35,7 @@ impl<'a> SerializeSequence<'a> {
 			),
 			TypeKind::Array => ElementTypes::Repeating(TypeHandle::from_type_id(
 				context.clone(),
-				expected_type.type_parameters()[0],
+				expected_type.type_parameter_ids()[0],
 			)),
 			_ => {
 				return Err(SerializerError::InvalidTypeForSequence {
@@ -112,7 +112,7 @@ impl<'a> SerializeSequence<'a> {
 			TypeKind::Array => {
 				let value = Value::new_array(
 					self.context,
-					&TypeHandle::from_type_id(self.context.clone(), self.expected_type.type_parameters()[0]),
+					&TypeHandle::from_type_id(self.context.clone(), self.expected_type.type_parameter_ids()[0]),
 					self.result,
 				)?;
 				assert!(value.has_type(&self.expected_type));
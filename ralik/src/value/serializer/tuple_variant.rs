@@ -22,7 +22,7 @@ impl<'a> SerializeTupleVariant<'a> {
 	) -> Result<Self, SerializerError> {
 		match expected_type.kind() {
 			TypeKind::Enum => {
-				let (variant_names, variants) = expected_type.variants();
+				let (variant_names, variants) = expected_type.variant_table();
 				match variant_names.get(variant_name).map(|id| &variants[*id]) {
 					Some(Variant::Tuple(_, types)) => {
 						let element_types = types
@@ -0,0 +1,209 @@
+use proc_macro2::TokenStream;
+use syn::ext::IdentExt;
+use syn::Ident;
+use thiserror::Error;
+
+use std::fmt::Write;
+
+use crate::types::{TypeKind, Variant};
+use crate::TypeHandle;
+
+use super::{Data, Value};
+
+#[derive(Error, Debug)]
+pub enum SourceError {
+	#[error("Values of the opaque type `{type_name}` cannot be written as source code")]
+	Opaque { type_name: String },
+
+	#[error("The type `{type_name}` cannot be named in source code")]
+	UnnameableType { type_name: String },
+
+	#[error("The field or variant `{name}` of type `{type_name}` cannot be named in source code")]
+	UnnameableMember { type_name: String, name: String },
+}
+
+impl Value {
+	/// Returns source code that evaluates to this value in the same context, i.e., `eval_str(&value.to_source()?,
+	/// &context)` is equal to `value`. Structs and enums are written with their full type name, e.g.,
+	/// `std::option::Option::<Integer>::Some(1)`, and empty arrays are written as `Vec::<T>::default()`, as their
+	/// element type cannot be inferred from an empty array literal.
+	///
+	/// Values of opaque types and of types whose names are not valid Rust types, such as the types inferred by
+	/// `Value::from_serde_inferred`, cannot be written as source code.
+	///
+	/// ```rust
+	/// # use ralik::eval_str;
+	/// let context = ralik::Context::new();
+	/// context.get_array_type("Integer").unwrap();
+	/// let fields = vec![("name", "std::string::String"), ("type", "[Integer]")];
+	/// context.register_types(vec![ralik::types::new_struct_type("Item", fields.into_iter())]);
+	///
+	/// let source = r#"(Item { name: "a\"b", r#type: Vec::<Integer>::default() }, "1".parse::<Integer>(), -3, '\n')"#;
+	/// let value = eval_str(source, &context).unwrap();
+	/// assert_eq!(
+	///     value.to_source().unwrap(),
+	///     concat!(
+	///         r#"(Item { name: "a\"b", r#type: Vec::<Integer>::default() }, "#,
+	///         r#"std::result::Result::<Integer, std::string::String>::Ok(1), -3, '\n')"#,
+	///     ),
+	/// );
+	/// assert_eq!(eval_str(&value.to_source().unwrap(), &context).unwrap(), value);
+	/// ```
+	pub fn to_source(&self) -> Result<String, SourceError> {
+		let mut source = String::new();
+		write_source(self, &mut source)?;
+		Ok(source)
+	}
+}
+
+/**
+Emits the same tokens as `Value::to_source`.
+
+```rust
+# use ralik::eval_tokens;
+use quote::quote;
+
+let context = ralik::Context::new();
+let value = ralik::eval_str("[(1, 'a')]", &context).unwrap();
+assert_eq!(eval_tokens(quote!(#value), &context).unwrap(), value);
+```
+
+# Panics
+Panics if the value cannot be written as source code.
+*/
+impl quote::ToTokens for Value {
+	fn to_tokens(&self, tokens: &mut TokenStream) {
+		let source = self.to_source().unwrap_or_else(|err| panic!("{}", err));
+		tokens.extend(source.parse::<TokenStream>().unwrap());
+	}
+}
+
+fn write_source(value: &Value, out: &mut String) -> Result<(), SourceError> {
+	match (value.r#type.kind(), &value.data) {
+		(TypeKind::Bool, Data::Bool(value)) => write!(out, "{}", value).unwrap(),
+		(TypeKind::Integer, Data::Integer(value)) => write!(out, "{}", value).unwrap(),
+		(TypeKind::Char, Data::Char(value)) => write!(out, "{:?}", value).unwrap(),
+		(TypeKind::String, Data::String(value)) => write!(out, "{:?}", value).unwrap(),
+		(TypeKind::Tuple, Data::Empty) => out.push_str("()"),
+		(TypeKind::Tuple, Data::Array(values)) => {
+			write_list(values, "(", ")", out)?;
+			if values.len() == 1 {
+				out.insert(out.len() - 1, ',');
+			}
+		}
+		(TypeKind::Array, Data::Array(values)) if !values.is_empty() => write_list(values, "[", "]", out)?,
+		(TypeKind::Array, Data::Empty | Data::Array(_)) => {
+			let element_type = value.r#type.type_parameter(0);
+			write!(out, "Vec::<{}>::default()", type_name(&element_type)?).unwrap();
+		}
+		(TypeKind::UnitStruct, Data::Empty) => out.push_str(&type_path(&value.r#type)?),
+		(TypeKind::TupleStruct, Data::Empty) => write!(out, "{}()", type_path(&value.r#type)?).unwrap(),
+		(TypeKind::TupleStruct, Data::Array(values)) => {
+			out.push_str(&type_path(&value.r#type)?);
+			write_list(values, "(", ")", out)?;
+		}
+		(TypeKind::Struct, Data::Empty) => write!(out, "{} {{}}", type_path(&value.r#type)?).unwrap(),
+		(TypeKind::Struct, Data::Array(values)) => {
+			out.push_str(&type_path(&value.r#type)?);
+			write_fields(&value.r#type, &value.r#type.fields().0, values, out)?;
+		}
+		(TypeKind::Enum, Data::UnitVariant(id)) => write_variant(&value.r#type, *id, &[], out)?,
+		(TypeKind::Enum, Data::Variant(id, values)) => write_variant(&value.r#type, *id, values, out)?,
+		(TypeKind::Opaque(_), _) => {
+			return Err(SourceError::Opaque {
+				type_name: (*value.r#type.name()).into(),
+			})
+		}
+		(kind, _) => panic!("Invalid {:?} representation", kind),
+	}
+	Ok(())
+}
+
+fn write_list(values: &[Value], open: &str, close: &str, out: &mut String) -> Result<(), SourceError> {
+	out.push_str(open);
+	for (i, value) in values.iter().enumerate() {
+		if i > 0 {
+			out.push_str(", ");
+		}
+		write_source(value, out)?;
+	}
+	out.push_str(close);
+	Ok(())
+}
+
+fn write_fields(
+	r#type: &TypeHandle,
+	field_names: &std::collections::HashMap<Box<str>, usize>,
+	values: &[Value],
+	out: &mut String,
+) -> Result<(), SourceError> {
+	let mut fields = field_names.iter().collect::<Vec<_>>();
+	fields.sort_by_key(|(_name, id)| **id);
+	out.push_str(" {");
+	for (i, (name, id)) in fields.into_iter().enumerate() {
+		if i > 0 {
+			out.push(',');
+		}
+		write!(out, " {}: ", member_name(r#type, name)?).unwrap();
+		write_source(&values[*id], out)?;
+	}
+	out.push_str(" }");
+	Ok(())
+}
+
+fn write_variant(r#type: &TypeHandle, id: usize, values: &[Value], out: &mut String) -> Result<(), SourceError> {
+	let variants = r#type.variant_table().1;
+	let (name, field_names) = match &variants[id] {
+		Variant::Unit(name) | Variant::Tuple(name, _) => (name, None),
+		Variant::Struct(name, field_names, _) => (name, Some(field_names)),
+	};
+	write!(out, "{}::{}", type_path(r#type)?, member_name(r#type, name)?).unwrap();
+	match (&variants[id], field_names) {
+		(Variant::Unit(_), _) => (),
+		(_, Some(field_names)) if field_names.is_empty() => out.push_str(" {}"),
+		(_, Some(field_names)) => write_fields(r#type, field_names, values, out)?,
+		(_, None) => write_list(values, "(", ")", out)?,
+	}
+	Ok(())
+}
+
+/// The name of `r#type` as used in generic arguments, e.g., `[(Integer, std::string::String)]`.
+fn type_name(r#type: &TypeHandle) -> Result<String, SourceError> {
+	let name = r#type.name();
+	match syn::parse_str::<syn::Type>(&name) {
+		Ok(_) => Ok((*name).into()),
+		Err(_) => Err(SourceError::UnnameableType {
+			type_name: (*name).into(),
+		}),
+	}
+}
+
+/// The name of `r#type` as used in expressions, which requires a turbofish before generic arguments, e.g.,
+/// `std::option::Option::<Integer>`.
+fn type_path(r#type: &TypeHandle) -> Result<String, SourceError> {
+	let name = r#type.name();
+	match syn::parse_str::<syn::Type>(&name) {
+		Ok(syn::Type::Path(_)) => Ok(match name.find('<') {
+			Some(index) => format!("{}::{}", &name[..index], &name[index..]),
+			None => (*name).into(),
+		}),
+		_ => Err(SourceError::UnnameableType {
+			type_name: (*name).into(),
+		}),
+	}
+}
+
+/// The name of a field or variant, which is written as a raw identifier if it is a keyword.
+fn member_name(r#type: &TypeHandle, name: &str) -> Result<String, SourceError> {
+	if syn::parse_str::<Ident>(name).is_ok() {
+		return Ok(name.into());
+	}
+	let raw_name = format!("r#{}", name);
+	match syn::parse_str::<Ident>(&raw_name) {
+		Ok(ident) if ident.unraw() == name => Ok(raw_name),
+		_ => Err(SourceError::UnnameableMember {
+			type_name: (*r#type.name()).into(),
+			name: name.into(),
+		}),
+	}
+}